@@ -0,0 +1,121 @@
+//! The derive macro `FromDom` of [sitescraper](https://crates.io/crates/sitescraper), which is used through its `derive` feature.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, LitStr, Token};
+
+// What is taken from the tags matching the selector of a field
+enum Extract {
+    Text,
+    Html,
+    Attr(LitStr),
+    // the tags themselves, which are extracted into a type implementing FromDom
+    Tags,
+}
+
+/// Implements `sitescraper::FromDom` for a struct with named fields. See the documentation of sitescraper for how to use it.
+#[proc_macro_derive(FromDom, attributes(select))]
+pub fn derive_from_dom(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(v) => v.into(),
+        Err(e) => e.to_compile_error().into()
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(syn::Error::new_spanned(&input.ident, "FromDom can only be derived for structs with named fields"))
+        },
+        _ => return Err(syn::Error::new_spanned(&input.ident, "FromDom can only be derived for structs"))
+    };
+
+    let mut values: Vec<TokenStream2> = vec![];
+
+    for field in fields {
+
+        let ident = field.ident.as_ref().unwrap();
+        let name = ident.to_string();
+
+        let attr = match field.attrs.iter().find(|a| a.path().is_ident("select")) {
+            Some(v) => v,
+            // fields without a selector are left at their default
+            None => {
+                values.push(quote!(#ident: ::std::default::Default::default()));
+                continue;
+            }
+        };
+
+        let (selector, extract) = attr.parse_args_with(parse_select)?;
+
+        let value = match extract {
+            Extract::Tags => quote! {
+                ::sitescraper::extract::FromTags::from_tags(::sitescraper::extract::tags(dom, #selector), #name, #selector)?
+            },
+            extract => {
+                let get = match extract {
+                    Extract::Text => quote!(::std::option::Option::Some(::sitescraper::extract::text(tag))),
+                    Extract::Html => quote!(::std::option::Option::Some(tag.get_inner_html())),
+                    Extract::Attr(attr) => quote!(tag.try_get_attr_value(#attr)),
+                    Extract::Tags => unreachable!()
+                };
+                quote! {
+                    ::sitescraper::extract::FromValues::from_values(
+                        dom.filter(#selector).iter().filter_map(|tag| #get).collect(), #name, #selector
+                    )?
+                }
+            }
+        };
+
+        values.push(quote!(#ident: #value));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::sitescraper::FromDom for #ident #type_generics #where_clause {
+            fn from_dom(dom: &::sitescraper::Dom) -> ::std::result::Result<Self, ::std::io::Error> {
+                ::std::result::Result::Ok(Self {
+                    #(#values,)*
+                })
+            }
+        }
+    })
+}
+
+// Reads the arguments of `#[select("selector")]`, `#[select("selector", text)]`, `#[select("selector", html)]`
+// or `#[select("selector", attr = "name")]`
+fn parse_select(input: ParseStream) -> syn::Result<(LitStr, Extract)> {
+
+    let selector: LitStr = input.parse()?;
+
+    if input.is_empty() {
+        return Ok((selector, Extract::Tags));
+    }
+
+    input.parse::<Token![,]>()?;
+
+    let kind: Ident = input.parse()?;
+
+    let extract = match kind.to_string().as_str() {
+        "text" => Extract::Text,
+        "html" => Extract::Html,
+        "attr" => {
+            input.parse::<Token![=]>()?;
+            Extract::Attr(input.parse()?)
+        },
+        _ => return Err(syn::Error::new_spanned(kind, "expected `text`, `html` or `attr = \"...\"`"))
+    };
+
+    if !input.is_empty() {
+        return Err(input.error("unexpected arguments after the selector and what to extract"));
+    }
+
+    Ok((selector, extract))
+}
@@ -0,0 +1,160 @@
+use std::io::{Error, ErrorKind};
+
+/// A type that can be extracted from a [`Dom`], like a product or an article of a page.
+/// With the `derive` feature it can be derived for structs whose fields are annotated with selectors,
+/// see [`FromDom`](derive.FromDom.html).
+///
+/// # Example
+///
+/// ```
+/// use sitescraper::{Dom, FromDom};
+/// use std::io::Error;
+///
+/// struct Product {
+///     name: String,
+///     price: f64,
+/// }
+///
+/// impl FromDom for Product {
+///     fn from_dom(dom: &Dom) -> Result<Self, Error> {
+///         Ok(Product{
+///             name: dom.filter("h1").get_text(),
+///             price: dom.filter(".price").get_text().trim().parse().unwrap_or_default(),
+///         })
+///     }
+/// }
+///
+/// let html = "<html><body><h1>Kettle</h1><span class='price'>19.99</span></body></html>";
+///
+/// let product = Product::from_dom(&sitescraper::parse_html(html).unwrap()).unwrap();
+///
+/// println!("{} {}", product.name, product.price);
+/// //Output: Kettle 19.99
+/// ```
+/// [`Dom`]: struct.Dom.html#
+pub trait FromDom: Sized {
+    /// Extracts the type from a [`Dom`] or a filtered [`Dom`].
+    /// It returns a [`Result`] that can be unwrapped to the type if everything it needs was found.
+    ///
+    /// [`Dom`]: struct.Dom.html#
+    fn from_dom(dom: &crate::Dom) -> Result<Self, Error>;
+}
+
+/// A type a single text, html or attribute-value can be converted into, which are [`String`], [`bool`], [`char`] and the number types.
+/// Numbers and the like are parsed after removing whitespace around them.
+pub trait FromValue: Sized {
+    /// Converts the value, returning [`None`] if it cannot be parsed
+    fn from_value(value: &str) -> Option<Self>;
+}
+
+impl FromValue for String {
+    fn from_value(value: &str) -> Option<Self> {
+        Some(value.to_string())
+    }
+}
+
+macro_rules! from_value {
+    ($($t:ty),*) => {
+        $(
+            impl FromValue for $t {
+                fn from_value(value: &str) -> Option<Self> {
+                    value.trim().parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+from_value!(bool, char, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+/// A type of a field annotated by `#[select("...", text)]`, `#[select("...", html)]` or `#[select("...", attr = "...")]`
+/// when deriving [`FromDom`]. It is made from the values of all tags matching the selector:
+/// a [`FromValue`] is the value of the first one and fails if there is none, an [`Option`] of it is [`None`] if there is none,
+/// and a [`Vec`] of it holds the values of all of them.
+///
+/// [`FromDom`]: trait.FromDom.html
+/// [`FromValue`]: trait.FromValue.html
+pub trait FromValues: Sized {
+    /// Converts the values found for a field by a selector
+    fn from_values(values: Vec<String>, field: &str, selector: &str) -> Result<Self, Error>;
+}
+
+impl<T: FromValue> FromValues for T {
+    fn from_values(values: Vec<String>, field: &str, selector: &str) -> Result<Self, Error> {
+        match values.first() {
+            Some(v) => parse(v, field),
+            None => Err(missing(field, selector))
+        }
+    }
+}
+
+impl<T: FromValue> FromValues for Option<T> {
+    fn from_values(values: Vec<String>, field: &str, _: &str) -> Result<Self, Error> {
+        values.first().map(|v| parse(v, field)).transpose()
+    }
+}
+
+impl<T: FromValue> FromValues for Vec<T> {
+    fn from_values(values: Vec<String>, field: &str, _: &str) -> Result<Self, Error> {
+        values.iter().map(|v| parse(v, field)).collect()
+    }
+}
+
+/// A type of a field annotated by `#[select("...")]` without saying what to extract when deriving [`FromDom`].
+/// It is extracted from the tags matching the selector, each of them as a [`Dom`] of its own:
+/// a [`FromDom`] is extracted from the first one and fails if there is none, an [`Option`] of it is [`None`] if there is none,
+/// a [`Vec`] of it is extracted from all of them, and a [`Dom`] holds all of them.
+///
+/// [`Dom`]: struct.Dom.html#
+/// [`FromDom`]: trait.FromDom.html
+pub trait FromTags: Sized {
+    /// Extracts the type from the tags found for a field by a selector
+    fn from_tags(tags: Vec<crate::Dom>, field: &str, selector: &str) -> Result<Self, Error>;
+}
+
+impl<T: FromDom> FromTags for T {
+    fn from_tags(tags: Vec<crate::Dom>, field: &str, selector: &str) -> Result<Self, Error> {
+        match tags.first() {
+            Some(v) => T::from_dom(v),
+            None => Err(missing(field, selector))
+        }
+    }
+}
+
+impl<T: FromDom> FromTags for Option<T> {
+    fn from_tags(tags: Vec<crate::Dom>, _: &str, _: &str) -> Result<Self, Error> {
+        tags.first().map(T::from_dom).transpose()
+    }
+}
+
+impl<T: FromDom> FromTags for Vec<T> {
+    fn from_tags(tags: Vec<crate::Dom>, _: &str, _: &str) -> Result<Self, Error> {
+        tags.iter().map(T::from_dom).collect()
+    }
+}
+
+impl FromDom for crate::Dom {
+    fn from_dom(dom: &crate::Dom) -> Result<Self, Error> {
+        Ok(dom.clone())
+    }
+}
+
+// The tags matching a selector inside of a Dom, each of them as a Dom of its own
+#[doc(hidden)]
+pub fn tags(dom: &crate::Dom, selector: &'static str) -> Vec<crate::Dom> {
+    dom.filter(selector).iter().map(|tag| crate::Dom::select(tag.document.clone(), vec![tag.index])).collect()
+}
+
+// The text of a tag as it is shown, with whitespace collapsed and removed around it
+#[doc(hidden)]
+pub fn text(tag: &crate::Tag) -> String {
+    tag.get_text_with(crate::TextOptions{collapse_whitespace: true, trim: true, ..Default::default()})
+}
+
+fn parse<T: FromValue>(value: &str, field: &str) -> Result<T, Error> {
+    T::from_value(value).ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("An error has occurred when trying to extract `{}`! (Cannot parse `{}`)", field, value)))
+}
+
+fn missing(field: &str, selector: &str) -> Error {
+    Error::new(ErrorKind::NotFound, format!("An error has occurred when trying to extract `{}`! (No tag matches `{}`)", field, selector))
+}
@@ -0,0 +1,269 @@
+use crate::entities;
+use crate::parse::{attributes, fetch, lexer};
+use crate::parse::lexer::{Markup, Next};
+use std::io::{Error, ErrorKind};
+use std::ops::Range;
+
+/// A [`Feed`] is a RSS or Atom feed as returned by [`parse`]
+///
+/// [`Feed`]: struct.Feed.html#
+/// [`parse`]: fn.parse.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Feed {
+    /// Whether the feed is a RSS or an Atom feed
+    pub kind: FeedKind,
+    /// The title of the feed
+    pub title: String,
+    /// The url of the website the feed belongs to
+    pub link: String,
+    /// The `description` of a RSS feed or the `subtitle` of an Atom feed
+    pub description: String,
+    /// The items of a RSS feed or the entries of an Atom feed in the order they are written
+    pub entries: Vec<Entry>,
+}
+
+/// The formats a [`Feed`] can be written in
+///
+/// [`Feed`]: struct.Feed.html#
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FeedKind {
+    /// RSS 2.0, or RSS 0.9x and 1.0, which are read the same way
+    Rss,
+    /// Atom 1.0
+    Atom,
+}
+
+/// An [`Entry`] is an item of a RSS feed or an entry of an Atom feed
+///
+/// [`Entry`]: struct.Entry.html#
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    /// The title of the entry
+    pub title: String,
+    /// The url of the entry: the `link` of a RSS item or the `href` of the alternate `<link>` of an Atom entry
+    pub link: String,
+    /// The `guid` of a RSS item or the `id` of an Atom entry
+    pub id: Option<String>,
+    /// The date the entry was published as it is written: the `pubDate` or `dc:date` of a RSS item,
+    /// e.g. `Mon, 06 Sep 2021 16:45:00 GMT`, or the `published` or `updated` date of an Atom entry, e.g. `2021-09-06T16:45:00Z`
+    pub published: Option<String>,
+    /// The content of the entry, which is often html: the `content:encoded` or `description` of a RSS item
+    /// or the `content` or `summary` of an Atom entry
+    pub content: String,
+}
+
+/// Parses a RSS or Atom document into a [`Feed`].
+/// It returns a [`Result`] that can be unwrapped to a [`Feed`] if the document is a RSS or Atom feed.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper::feeds;
+///
+/// let xml = "<?xml version='1.0'?><rss version='2.0'><channel><title>News</title><link>https://example.com/</link>
+///     <item><title>Hello</title><link>https://example.com/hello</link><pubDate>Mon, 06 Sep 2021 16:45:00 GMT</pubDate>
+///     <description><![CDATA[<p>Hello World!</p>]]></description></item></channel></rss>";
+///
+/// let feed = feeds::parse(xml).unwrap();
+///
+/// for entry in feed.entries {
+///     println!("{} {} {}", entry.title, entry.link, entry.content);
+/// }
+/// //Output: Hello https://example.com/hello <p>Hello World!</p>
+/// ```
+/// [`Feed`]: struct.Feed.html#
+pub fn parse(xml: &str) -> Result<Feed, Error> {
+
+    let root = Element::parse(xml);
+
+    if let Some(feed) = root.child("feed") {
+        return Ok(atom(xml, feed));
+    }
+
+    if let Some(rss) = root.child("rss").or_else(|| root.child("rdf:RDF")) {
+        return Ok(rss_feed(rss));
+    }
+
+    Err(Error::new(ErrorKind::InvalidData, "An error has occurred when trying to parse the feed! (Neither RSS nor Atom)"))
+}
+
+fn rss_feed(rss: &Element) -> Feed {
+
+    let channel = rss.child("channel");
+
+    // the items of RSS 1.0 follow the channel instead of being inside of it
+    let items = channel.iter().flat_map(|c| c.children("item")).chain(rss.children("item"));
+
+    let entries = items.map(|item| {
+        // a guid is the url of the item unless it is marked as no permalink
+        let guid = item.child("guid");
+        let permalink = guid.filter(|g| !g.attr("isPermaLink").is_some_and(|v| v.eq_ignore_ascii_case("false")));
+        Entry{
+            title: item.text("title"),
+            link: item.child("link").or(permalink).map(|l| l.text.trim().to_string()).unwrap_or_default(),
+            id: guid.map(|g| g.text.trim().to_string()),
+            published: item.child("pubDate").or_else(|| item.child("dc:date")).map(|d| d.text.trim().to_string()),
+            content: item.child("content:encoded").or_else(|| item.child("description")).map(|c| c.text.trim().to_string()).unwrap_or_default(),
+        }
+    }).collect();
+
+    let channel_text = |name: &str| channel.map(|c| c.text(name)).unwrap_or_default();
+
+    Feed{kind: FeedKind::Rss, title: channel_text("title"), link: channel_text("link"), description: channel_text("description"), entries}
+}
+
+fn atom(xml: &str, feed: &Element) -> Feed {
+
+    let entries = feed.children("entry").map(|entry| Entry{
+        title: entry.text("title"),
+        link: atom_link(entry),
+        id: entry.child("id").map(|i| i.text.trim().to_string()),
+        published: entry.child("published").or_else(|| entry.child("updated")).map(|d| d.text.trim().to_string()),
+        content: entry.child("content").or_else(|| entry.child("summary")).map(|c| atom_text(xml, c)).unwrap_or_default(),
+    }).collect();
+
+    Feed{kind: FeedKind::Atom, title: feed.text("title"), link: atom_link(feed), description: feed.text("subtitle"), entries}
+}
+
+// Returns the `href` of the `<link>` pointing to the website, which has no `rel` or `rel="alternate"`
+fn atom_link(element: &Element) -> String {
+    element.children("link")
+        .find(|l| l.attr("rel").map_or(true, |r| r == "alternate"))
+        .and_then(|l| l.attr("href"))
+        .map(|h| h.trim().to_string())
+        .unwrap_or_default()
+}
+
+// Returns the text of an Atom text construct, which is xhtml markup inside of the element if its type is `xhtml`
+fn atom_text(xml: &str, element: &Element) -> String {
+    match element.attr("type") {
+        Some("xhtml") => xml[element.inner.clone()].trim().to_string(),
+        _ => element.text.trim().to_string()
+    }
+}
+
+// An element of a xml-document with its text, which is put together from the text directly inside of it,
+// including CDATA sections, with character references decoded
+#[derive(Default)]
+struct Element {
+    name: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<Element>,
+    text: String,
+    // the byte range of the content in the xml
+    inner: Range<usize>,
+}
+
+impl Element {
+
+    // Reads a xml-document into an element holding its top elements
+    fn parse(xml: &str) -> Element {
+
+        let mut stack: Vec<Element> = vec![Element::default()];
+        let mut position = 0;
+
+        while let Some(v) = xml[position..].find('<') {
+
+            let start = position + v;
+            text(&mut stack, &xml[position..start]);
+
+            let rest = &xml[start..];
+
+            // CDATA sections hold text as it is written, including `<` and `>`
+            if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+                let end = cdata.find("]]>").unwrap_or(cdata.len());
+                if let Some(e) = stack.last_mut() {
+                    e.text.push_str(&cdata[..end]);
+                }
+                position = (start + 9 + end + 3).min(xml.len());
+                continue;
+            }
+
+            // declarations like `<?xml ...?>` and `<!DOCTYPE ...>`, and comments
+            let skip = match rest {
+                _ if rest.starts_with("<!--") => Some("-->"),
+                _ if rest.starts_with("<?") => Some("?>"),
+                _ if rest.starts_with("<!") => Some(">"),
+                _ => None
+            };
+
+            if let Some(end) = skip {
+                position = rest.find(end).map_or(xml.len(), |v| start + v + end.len());
+                continue;
+            }
+
+            let range = match lexer::next(xml, start, true) {
+                Next::Markup(Markup::Tag(range)) if range.start == start => range,
+                // a `<` that does not start a tag
+                _ => {
+                    text(&mut stack, "<");
+                    position = start + 1;
+                    continue;
+                }
+            };
+
+            position = range.end;
+
+            let tagcontent = &xml[range.clone()];
+            let tagname = fetch::get_tagname(tagcontent);
+
+            if let Some(name) = tagname.strip_prefix('/') {
+                // elements left open inside of the closed element are closed with it
+                if let Some(v) = stack.iter().rposition(|e| e.name == name).filter(|v| *v > 0) {
+                    while stack.len() > v {
+                        close(&mut stack, range.start);
+                    }
+                }
+                continue;
+            }
+
+            let element = Element{name: tagname, attrs: attributes::parse(tagcontent), inner: range.end..range.end, ..Default::default()};
+
+            match tagcontent.ends_with("/>") {
+                true => stack.last_mut().unwrap().children.push(element),
+                false => stack.push(element)
+            }
+        }
+
+        text(&mut stack, &xml[position..]);
+
+        while stack.len() > 1 {
+            close(&mut stack, xml.len());
+        }
+
+        stack.pop().unwrap_or_default()
+    }
+
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| c.name.eq_ignore_ascii_case(name))
+    }
+
+    fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |c| c.name.eq_ignore_ascii_case(name))
+    }
+
+    // Returns the text of the first child with the given name without whitespace around it
+    fn text(&self, name: &str) -> String {
+        self.child(name).map(|c| c.text.trim().to_string()).unwrap_or_default()
+    }
+
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+    }
+}
+
+fn text(stack: &mut [Element], text: &str) {
+    if let Some(e) = stack.last_mut() {
+        e.text.push_str(&entities::decode(text));
+    }
+}
+
+// Closes the innermost open element, whose content ends at `end`, and adds it to its parent
+fn close(stack: &mut Vec<Element>, end: usize) {
+    if let Some(mut element) = stack.pop() {
+        element.inner.end = end;
+        if let Some(parent) = stack.last_mut() {
+            parent.children.push(element);
+        }
+    }
+}
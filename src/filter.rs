@@ -0,0 +1,92 @@
+use crate::parse::selector::Selector;
+
+/// A filter that can be built step by step and passed to [`Dom::filter`] instead of a selector string.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper::Filter;
+///
+/// let html = r#"<html><body><div class="post" data-id="1">Hello World!</div><div class="post">Bye!</div></body></html>"#;
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filter = Filter::tag("div").class("post").attr("data-id").build();
+///
+/// println!("{}", dom.filter(&filter).get_text());
+/// //Output: Hello World!
+/// ```
+/// [`Dom::filter`]: struct.Dom.html#method.filter
+#[derive(Clone, Debug)]
+pub struct Filter {
+    pub (crate) selector: Selector,
+}
+
+/// Builds a [`Filter`]. Every condition that is added has to be fulfilled by a tag to match.
+///
+/// [`Filter`]: struct.Filter.html#
+#[derive(Clone, Debug, Default)]
+pub struct FilterBuilder {
+    tagname: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+    attrs: Vec<(String, Option<String>)>,
+}
+
+impl Filter {
+
+    /// Starts a [`FilterBuilder`] matching tags with the given tag-name.
+    ///
+    /// [`FilterBuilder`]: struct.FilterBuilder.html#
+    pub fn tag(tag_name: &str) -> FilterBuilder {
+        FilterBuilder::default().tag(tag_name)
+    }
+
+    /// Starts an empty [`FilterBuilder`] which matches every tag.
+    ///
+    /// [`FilterBuilder`]: struct.FilterBuilder.html#
+    pub fn builder() -> FilterBuilder {
+        FilterBuilder::default()
+    }
+}
+
+impl FilterBuilder {
+
+    /// Only matches tags with the given tag-name.
+    pub fn tag(mut self, tag_name: &str) -> FilterBuilder {
+        self.tagname = Some(tag_name.to_string());
+        self
+    }
+
+    /// Only matches tags with the given id.
+    pub fn id(mut self, id: &str) -> FilterBuilder {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    /// Only matches tags having the given class. Can be called several times.
+    pub fn class(mut self, class: &str) -> FilterBuilder {
+        self.classes.push(class.to_string());
+        self
+    }
+
+    /// Only matches tags having the given attribute, no matter what its value is.
+    pub fn attr(mut self, attr_name: &str) -> FilterBuilder {
+        self.attrs.push((attr_name.to_string(), None));
+        self
+    }
+
+    /// Only matches tags having the given attribute with exactly the given value.
+    pub fn attr_value(mut self, attr_name: &str, attr_value: &str) -> FilterBuilder {
+        self.attrs.push((attr_name.to_string(), Some(attr_value.to_string())));
+        self
+    }
+
+    /// Returns the [`Filter`] that can be passed to [`Dom::filter`].
+    ///
+    /// [`Filter`]: struct.Filter.html#
+    /// [`Dom::filter`]: struct.Dom.html#method.filter
+    pub fn build(self) -> Filter {
+        Filter{selector: Selector::from_parts(self.tagname, self.id, self.classes, self.attrs)}
+    }
+}
@@ -106,12 +106,87 @@
 
 pub (in crate) mod parse;
 pub mod http;
+pub mod tokenizer;
+pub mod entities;
+pub mod url;
+pub mod feeds;
+pub mod srcset;
+pub mod extract;
+mod filter;
+#[cfg(feature = "serde")]
+mod serialize;
+
+pub use filter::{Filter, FilterBuilder};
+pub use extract::FromDom;
+
+/// Derives [`FromDom`] for a struct whose fields are annotated by `#[select(...)]` with a selector like the ones taken by [`filter`]
+/// and what to extract from the tags matching it. This needs the `derive` feature.
+///
+/// - `#[select("h1.title", text)]` takes the text of the tags with whitespace collapsed and removed around it
+/// - `#[select("div.content", html)]` takes their InnerHTML
+/// - `#[select("a.more", attr = "href")]` takes the value of the attribute, leaving out tags without it
+/// - `#[select("div.offer")]` extracts a type implementing [`FromDom`] from the tags, each of them as a [`Dom`] of its own
+///
+/// A field of a type like [`String`] or [`f64`] gets the value of the first matching tag, and extracting fails if there is none
+/// or if the value cannot be parsed. An [`Option`] is [`None`] if there is no matching tag and a [`Vec`] gets the values of all of them.
+/// Fields without `#[select(...)]` are set to their default.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper::FromDom;
+///
+/// #[derive(FromDom)]
+/// struct Product {
+///     #[select("h1.title", text)]
+///     title: String,
+///     #[select("span.price", text)]
+///     price: f64,
+///     #[select("img", attr = "src")]
+///     images: Vec<String>,
+///     #[select("div.offer")]
+///     offers: Vec<Offer>,
+/// }
+///
+/// #[derive(FromDom)]
+/// struct Offer {
+///     #[select("span.seller", text)]
+///     seller: String,
+///     #[select("a", attr = "href")]
+///     link: Option<String>,
+/// }
+///
+/// let html = "<html><body><h1 class='title'> Kettle </h1><span class='price'>19.99</span><img src='a.jpg'><img src='b.jpg'>
+///     <div class='offer'><span class='seller'>Shop</span><a href='/buy'>Buy</a></div></body></html>";
+///
+/// let product = Product::from_dom(&sitescraper::parse_html(html).unwrap()).unwrap();
+///
+/// println!("{} {} {:?} {} {:?}", product.title, product.price, product.images, product.offers[0].seller, product.offers[0].link);
+/// //Output: Kettle 19.99 ["a.jpg", "b.jpg"] Shop Some("/buy")
+/// ```
+/// [`FromDom`]: trait.FromDom.html
+/// [`Dom`]: struct.Dom.html#
+/// [`filter`]: struct.Dom.html#method.filter
+#[cfg(feature = "derive")]
+pub use sitescraper_derive::FromDom;
+
+// lets the code generated by the derive macro refer to this crate by its name in the tests of this crate
+#[cfg(all(test, feature = "derive"))]
+extern crate self as sitescraper;
+pub use parse::selector::Selector;
 
 use std::io::{Error, ErrorKind};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, OnceLock};
 use parse::Args;
+use parse::document::Document;
 
 /// This method parses a &[`str`] to a [`Dom`].
 /// It returns a [`Result`] that can be unwrapped to a [`Dom`] if the parsing-process was successful.
+///
+/// The html is copied once into the [`Dom`] and shared by all of its [`Tag`]s, even after filtering.
+/// The tags store byte offsets into it instead of copies of their opening tags and contents,
+/// so a parsed page takes up little more memory than its html.
 /// 
 /// # Example
 /// 
@@ -123,13 +198,292 @@ use parse::Args;
 /// let dom = sitescraper::parse_html(html).unwrap();
 /// ```
 /// [`Dom`]: struct.Dom.html#
+/// [`Tag`]: struct.Tag.html#
 pub fn parse_html(html: &str) -> Result<Dom, Error> {
+    parse_html_with(html, ParseOptions::default())
+}
+
+/// This method works like [`parse_html`], but the way the html is read can be changed by passing [`ParseOptions`].
+///
+/// # Example
+///
+/// ```
+/// use sitescraper::ParseOptions;
+///
+/// let html = "<html><body><a href='/?a=1&amp;b=2'>Tom &amp; Jerry</a></body></html>";
+///
+/// let dom = sitescraper::parse_html_with(html, ParseOptions{decode_text: false, ..Default::default()}).unwrap();
+///
+/// println!("{} {}", dom.filter("a").get_text(), dom.filter("a").get_attr_value("href"));
+/// //Output: Tom &amp; Jerry /?a=1&b=2
+/// ```
+/// [`parse_html`]: fn.parse_html.html
+/// [`ParseOptions`]: struct.ParseOptions.html#
+pub fn parse_html_with(html: &str, options: ParseOptions) -> Result<Dom, Error> {
 
     if !html.contains("<") || !html.contains(">") {
         return Err(Error::new(ErrorKind::InvalidInput, "An error has occurred when trying to parse the html-string! (Invalid Input)"));
     }
 
-    Ok(parse::fetch::fetch(html.to_string()))
+    Ok(parse::fetch::fetch(html.to_string(), options))
+}
+
+/// This method parses html given as bytes, e.g. the body of a http-response, to a [`Dom`] like [`parse_html`].
+/// The encoding is taken from a byte order mark or a `<meta charset>` tag and the html is transcoded to UTF-8 before it is parsed,
+/// so pages declaring e.g. `ISO-8859-1` or `Shift_JIS` are read correctly. Without either, the html is read as UTF-8.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = b"<html><head><meta charset='ISO-8859-1'></head><body><p>Gr\xfc\xdfe</p></body></html>";
+///
+/// let dom = sitescraper::parse_html_bytes(html).unwrap();
+///
+/// println!("{}", dom.filter("p").get_text());
+/// //Output: Grüße
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`parse_html`]: fn.parse_html.html
+pub fn parse_html_bytes(html: &[u8]) -> Result<Dom, Error> {
+
+    let (html, _, _) = parse::metadata::encoding(html).decode(html);
+
+    parse_html(&html)
+}
+
+/// This method parses a fragment of html like `<li>One</li><li>Two</li>` returned by an AJAX endpoint to a [`Dom`].
+/// Unlike [`parse_html`], which takes the first [`Tag`] as the document, `get_text`, `get_inner_html` and `to_string`
+/// of the returned [`Dom`] cover the whole fragment, and a fragment without any tags gives an empty [`Dom`] instead of an error.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<li>One</li><li>Two</li>";
+///
+/// let dom = sitescraper::parse_fragment(html).unwrap();
+///
+/// println!("{} {}", dom.len(), dom.get_text());
+/// //Output: 2 OneTwo
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Tag`]: struct.Tag.html#
+/// [`parse_html`]: fn.parse_html.html
+pub fn parse_fragment(html: &str) -> Result<Dom, Error> {
+    Ok(parse::fetch::fetch_fragment(html.to_string(), ParseOptions::default()))
+}
+
+/// Options that change how [`parse_html_with`] reads the html
+///
+/// [`parse_html_with`]: fn.parse_html_with.html
+#[derive(Clone, Copy, Debug)]
+pub struct ParseOptions {
+    /// Decode character references like `&amp;` in the text returned by `get_text`. On by default.
+    pub decode_text: bool,
+    /// Decode character references like `&amp;` in the attribute-values returned by `get_attr_value`. On by default.
+    pub decode_attributes: bool,
+    /// Lowercase the tag-names of html tags, so `<IMG>` and `<img>` are both matched by `img` and returned as `img` by `get_tagname`.
+    /// The tag-names of svg and MathML tags like `linearGradient` keep their case. On by default.
+    pub lowercase_tagnames: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> ParseOptions {
+        ParseOptions{decode_text: true, decode_attributes: true, lowercase_tagnames: true}
+    }
+}
+
+/// This method parses a &[`str`] to a [`Dom`] like [`parse_html`], but fails instead of working around problems in the html.
+/// The error lists every [`Warning`] found in the html, e.g. unclosed tags, stray closing tags or broken attributes.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><div><p>Hello World!</div></body></html>";
+///
+/// match sitescraper::parse_html_strict(html) {
+///     Ok(dom) => println!("{}", dom.filter("p").get_text()),
+///     Err(e) => println!("{}", e)
+/// }
+/// //Output: An error has occurred when trying to parse the html-string! (unclosed tag <p> at line 1, column 18; mismatched closing tag </div> at line 1, column 33)
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`parse_html`]: fn.parse_html.html
+/// [`Warning`]: struct.Warning.html#
+pub fn parse_html_strict(html: &str) -> Result<Dom, Error> {
+
+    let dom = parse_html(html)?;
+
+    let warnings = dom.warnings();
+
+    if !warnings.is_empty() {
+        let warnings: Vec<String> = warnings.iter().map(|w| w.to_string()).collect();
+        return Err(Error::new(ErrorKind::InvalidData, format!("An error has occurred when trying to parse the html-string! ({})", warnings.join("; "))));
+    }
+
+    Ok(dom)
+}
+
+/// Walks through a html-[`str`] and passes every [`Event`] to the given closure in document order without building a [`Dom`].
+/// This is useful for large documents when only a few values are needed.
+///
+/// Every [`StartTag`] is followed by an [`EndTag`] at some point, even for void tags like `<br>`
+/// and tags that are not closed in the html.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper::Event;
+///
+/// let html = "<html><head><title>Hello World!</title></head><body>...</body></html>";
+///
+/// let mut in_title = false;
+///
+/// sitescraper::parse_events(html, |event| match event {
+///     Event::StartTag{name, ..} => in_title = name == "title",
+///     Event::Text(text) if in_title => println!("{}", text),
+///     _ => ()
+/// });
+/// //Output: Hello World!
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Event`]: enum.Event.html#
+/// [`StartTag`]: enum.Event.html#variant.StartTag
+/// [`EndTag`]: enum.Event.html#variant.EndTag
+pub fn parse_events<F: FnMut(Event)>(html: &str, mut f: F) {
+
+    parse::fetch::scan(html, |found| match found {
+        parse::fetch::Found::Start{name, tag, open} => {
+            f(Event::StartTag{name: &html[name.clone()], attrs: parse::attributes::parse(&html[tag])});
+            // void and self-closing tags end right away
+            if !open {
+                f(Event::EndTag{name: &html[name]});
+            }
+        },
+        parse::fetch::Found::End{name, ..} => f(Event::EndTag{name: &html[name]}),
+        parse::fetch::Found::Text(range) => f(Event::Text(&html[range])),
+        parse::fetch::Found::Comment(range) => {
+            let comment = &html[range];
+            let comment = comment.strip_prefix("<!--").unwrap_or(comment);
+            f(Event::Comment(comment.strip_suffix("-->").unwrap_or(comment)))
+        },
+        parse::fetch::Found::Doctype(_) | parse::fetch::Found::Warning(..) => (),
+    });
+}
+
+/// A [`Parser`] parses html that arrives in chunks, e.g. while it is downloaded. Every chunk is parsed as far as possible
+/// when it is passed to [`feed`], and [`finish`] returns the [`Dom`] once all chunks have arrived.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper::Parser;
+///
+/// let mut parser = Parser::new();
+///
+/// parser.feed(b"<html><body><div>Hello ");
+/// parser.feed(b"World!</div></bo");
+/// parser.feed(b"dy></html>");
+///
+/// let dom = parser.finish().unwrap();
+///
+/// println!("{}", dom.filter("div").get_text());
+/// //Output: Hello World!
+/// ```
+/// [`Parser`]: struct.Parser.html#
+/// [`Dom`]: struct.Dom.html#
+/// [`feed`]: struct.Parser.html#method.feed
+/// [`finish`]: struct.Parser.html#method.finish
+pub struct Parser {
+    html: String,
+    // the bytes of a character that was split between two chunks
+    pending: Vec<u8>,
+    builder: parse::fetch::Builder,
+}
+
+impl Parser {
+
+    /// Returns a new [`Parser`] without any html
+    ///
+    /// [`Parser`]: struct.Parser.html#
+    pub fn new() -> Parser {
+        Parser::with_options(ParseOptions::default())
+    }
+
+    /// Returns a new [`Parser`] without any html that reads the html like [`parse_html_with`]
+    ///
+    /// [`Parser`]: struct.Parser.html#
+    /// [`parse_html_with`]: fn.parse_html_with.html
+    pub fn with_options(options: ParseOptions) -> Parser {
+        Parser{html: String::new(), pending: vec![], builder: parse::fetch::Builder::new(options)}
+    }
+
+    /// Adds the next chunk of the html and parses all tags that are complete.
+    /// Characters split between two chunks are put together again, invalid UTF-8 is replaced by `U+FFFD`.
+    pub fn feed(&mut self, chunk: &[u8]) {
+
+        self.pending.extend_from_slice(chunk);
+
+        let mut rest: &[u8] = &self.pending;
+
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(v) => {
+                    self.html.push_str(v);
+                    rest = &[];
+                    break
+                },
+                Err(e) => {
+                    let (valid, invalid) = rest.split_at(e.valid_up_to());
+                    self.html.push_str(std::str::from_utf8(valid).unwrap_or_default());
+                    match e.error_len() {
+                        Some(len) => {
+                            self.html.push(char::REPLACEMENT_CHARACTER);
+                            rest = &invalid[len..];
+                        },
+                        // the character continues in the next chunk
+                        None => {
+                            rest = invalid;
+                            break
+                        }
+                    }
+                }
+            }
+        }
+
+        self.pending = rest.to_vec();
+
+        self.builder.feed(&self.html, false);
+    }
+
+    /// Parses the rest of the html and returns a [`Result`] that can be unwrapped to a [`Dom`] if the html was valid,
+    /// just like [`parse_html`].
+    ///
+    /// [`Dom`]: struct.Dom.html#
+    /// [`parse_html`]: fn.parse_html.html
+    pub fn finish(mut self) -> Result<Dom, Error> {
+
+        if !self.pending.is_empty() {
+            self.html.push(char::REPLACEMENT_CHARACTER);
+        }
+
+        if !self.html.contains("<") || !self.html.contains(">") {
+            return Err(Error::new(ErrorKind::InvalidInput, "An error has occurred when trying to parse the html-string! (Invalid Input)"));
+        }
+
+        Ok(parse::fetch::from_document(self.builder.finish(self.html)))
+    }
+}
+
+impl Default for Parser {
+    fn default() -> Parser {
+        Parser::new()
+    }
 }
 
 
@@ -138,6 +492,8 @@ pub fn parse_html(html: &str) -> Result<Dom, Error> {
 pub struct Dom {
     pub tag: Vec<Tag>,
     is_parsed: bool,
+    // a Tag for every tag of the document, so references to tags outside of this Dom can be returned
+    handles: OnceLock<Vec<Tag>>,
 }
 
 impl crate::Dom {
@@ -181,7 +537,201 @@ impl crate::Dom {
 /// let filtered_dom = dom.filter(("*", "*", "hello"));
 /// ```
 /// 
-/// A filtered [`Dom`] can be filtered again with this method, e.g.
+/// An attribute-value containing `*` is matched as a glob pattern, where `*` stands for any sequence of characters:
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><a href='/shop/product/42'>Product</a><a href='/shop/about'>About</a></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter(("a", "href", "*/product/*"));
+///
+/// println!("{}", filtered_dom.get_text());
+/// //Output: Product
+/// ```
+/// In place of the tag-name a selector like `div.post` can be passed, the tags then have to match both the selector
+/// and the attribute, e.g. `dom.filter(("div.post", "data-id", "2"))`.
+///
+/// Tags that merely have an attribute, including boolean attributes without a value like `required`,
+/// are found by passing the attribute-name only or by writing `[attr]`:
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><input name='a' required><input name='b'></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter(("input", "required"));
+///
+/// println!("{}", filtered_dom.get_attr_value("name"));
+/// //Output: a
+/// ```
+///
+/// Attribute-values can also be matched css-like by their beginning (`^=`), end (`$=`), any part (`*=`),
+/// one of their whitespace-separated words (`~=`) or their beginning up to a `-` (`|=`):
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><a href='/cat.jpg'>Cat</a><a href='/dog.png'>Dog</a></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter("a[href$='.jpg']");
+///
+/// println!("{}", filtered_dom.get_text());
+/// //Output: Cat
+/// ```
+/// To filter by a class, pass the class-name with a leading dot. Tags with multiple classes match as well:
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><span class='price sale'>5 $</span><span>Free shipping</span></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter(".price");
+///
+/// println!("{}", filtered_dom.get_text());
+/// //Output: 5 $
+/// ```
+/// A tag with a certain id can be selected by passing the id with a leading `#`:
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><div id='main-content'>Hello World!</div></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter("#main-content");
+///
+/// println!("{}", filtered_dom.get_text());
+/// //Output: Hello World!
+/// ```
+/// Tags can also be selected by their position with `:nth-child()`, `:nth-last-child()`, `:nth-of-type()`,
+/// `:nth-last-of-type()`, `:first-child`, `:last-child`, `:only-child`, `:first-of-type`, `:last-of-type` and `:only-of-type`:
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><ul><li>One</li><li>Two</li><li>Three</li></ul></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter("li:nth-child(2)");
+///
+/// println!("{}", filtered_dom.get_text());
+/// //Output: Two
+/// ```
+/// `:contains()` only selects tags whose text contains the given text:
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><a href='?page=1'>Previous</a><a href='?page=3'>Next page</a></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter("a:contains('Next')");
+///
+/// println!("{}", filtered_dom.get_attr_value("href"));
+/// //Output: ?page=3
+/// ```
+/// Tags inside of other tags can be selected by separating the selectors with a space:
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><table><tr><td>Cell</td></tr></table><td>Outside</td></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter("table td");
+///
+/// println!("{}", filtered_dom.get_text());
+/// //Output: Cell
+/// ```
+/// Use `>` to only select tags that are direct children of another tag:
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><ul><li>Item<ul><li>Nested</li></ul></li></ul></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter("body > ul > li");
+///
+/// println!("{}", filtered_dom.tag.len());
+/// //Output: 1
+/// ```
+/// Tags following other tags with the same parent can be selected with `+` (directly following) and `~` (following anywhere later):
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><dl><dt>Price</dt><dd>5 $</dd><dt>Color</dt><dd>Red</dd></dl></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter("dt:first-of-type + dd");
+///
+/// println!("{}", filtered_dom.get_text());
+/// //Output: 5 $
+/// ```
+/// Several tag-names can be passed at once, empty ones are skipped. The matching tags are kept in the order they appear in the document:
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><h2>Second</h2><h1>First</h1><p>Text</p></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter(["h1", "h2"]);
+///
+/// println!("{}", filtered_dom.get_text());
+/// //Output: SecondFirst
+/// ```
+/// Tags of inline svg or MathML content and tags with a namespace prefix like `<svg:path>` can be selected by their
+/// name with or without prefix. `svg|path` only selects tags of the given namespace and `|title` only html tags:
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><head><title>Page</title></head><body><svg><title>Icon</title></svg></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter("svg|title");
+///
+/// println!("{}", filtered_dom.get_text());
+/// //Output: Icon
+/// ```
+/// A selector that cannot be parsed matches no tags.
+///
+/// Several selectors can be separated by commas to get all tags matching any of them in document order:
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><h2>Second</h2><h1>First</h1><p class='title'>Third</p></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter("h1, h2, .title");
+///
+/// println!("{}", filtered_dom.get_text());
+/// //Output: SecondFirstThird
+/// ```
+/// Instead of a selector a [`Filter`] can be passed, which is built step by step:
+/// ```
+/// use sitescraper::Filter;
+///
+/// let html = r#"<html><body><div class="post" data-id="1">Hello World!</div><div class="post">Bye!</div></body></html>"#;
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter(Filter::tag("div").class("post").attr("data-id").build());
+///
+/// println!("{}", filtered_dom.get_text());
+/// //Output: Hello World!
+/// ```
+///
+/// A filtered [`Dom`] can be filtered again with this method. Only the filtered [`Tag`]s and the tags nested inside of them
+/// are searched then, while selectors can still refer to the tags around them, e.g.
 /// ```
 /// let html = "<html><body><div id='hello'>Hello World!</div></body></html>";
 /// 
@@ -192,528 +742,5743 @@ impl crate::Dom {
 /// let filtered_dom_2 = dom.filter("div");
 /// ```
 /// [`Dom`]: struct.Dom.html#
+/// [`Tag`]: struct.Tag.html#
+/// [`Filter`]: struct.Filter.html#
     pub fn filter(&self, args: impl Args) -> crate::Dom {
+        self.filter_with(args, FilterOptions::default())
+    }
 
-        let (tag_name, attr_name, attr_value) = args.extract();
+/// This method works like [`filter`], but the way tags are matched can be changed by passing [`FilterOptions`].
+///
+/// # Example
+///
+/// ```
+/// use sitescraper::FilterOptions;
+///
+/// let html = "<html><body><DIV ID='hello'>Hello World!</DIV></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter_with(("div", "id"), FilterOptions{case_insensitive: true, ..Default::default()});
+///
+/// println!("{}", filtered_dom.get_text());
+/// //Output: Hello World!
+/// ```
+/// Attribute-values can be matched regardless of their case as well:
+///
+/// ```
+/// use sitescraper::FilterOptions;
+///
+/// let html = "<html><head><meta charset='UTF-8'></head></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter_with("meta[charset=utf-8]", FilterOptions{case_insensitive_values: true, ..Default::default()});
+///
+/// println!("{}", filtered_dom.get_attr_value("charset"));
+/// //Output: UTF-8
+/// ```
+/// [`filter`]: struct.Dom.html#method.filter
+/// [`FilterOptions`]: struct.FilterOptions.html#
+    pub fn filter_with(&self, args: impl Args, options: FilterOptions) -> crate::Dom {
 
-        #[allow(unused_assignments)]
-        let mut new = crate::Dom::new();
+        let queries = args.extract();
 
-        if tag_name == "" && attr_name == "" && attr_value == "" {
+        if queries.iter().any(|q| q.is_empty()) {
              return self.clone();
         }
 
+        let (document, mut indices) = self.scope();
 
-        if !self.is_parsed {
-            new=crate::parse_html(&self.to_string()).unwrap();
-        } else {
-            new=self.clone();
-        }
-        
-        if tag_name != "" && tag_name != "*" {
-            new=new.tag(tag_name);
-        }
+        indices.retain(|i| queries.iter().any(|q| q.matches(&document, *i, options)));
 
-        if attr_name != "" && attr_name != "*"  {
-            new=new.attr(attr_name);
-        }
+        crate::Dom::select(document, indices)
+    }
+
+/// This method returns a reference to the first [`Tag`] matching the given arguments, which can be passed like to [`filter`].
+/// It stops searching at the first match without cloning any [`Tag`]s and returns [`None`] if there is none.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><a href='/one'>One</a><a href='/two'>Two</a></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// if let Some(tag) = dom.find("a") {
+///     println!("{}", tag.get_attr_value("href"));
+/// }
+/// //Output: /one
+/// ```
+/// [`Tag`]: struct.Tag.html#
+/// [`filter`]: struct.Dom.html#method.filter
+    pub fn find(&self, args: impl Args) -> Option<&crate::Tag> {
 
-        if attr_value != "" && attr_value != "*"  {
-            new=new.attr_value(attr_value);
+        let queries = args.extract();
+
+        if queries.iter().any(|q| q.is_empty()) {
+            return self.tag.first();
         }
 
-        new.is_parsed=false;
-        new
-    }
+        let (tags, indices) = self.scope_ref();
 
-    fn new() -> Dom {
-        let tag = crate::Tag{tagname: "".to_string(), tagcontent: "".to_string(), innerhtml: "".to_string()};
-        let tags = vec![tag];
-        crate::Dom{tag: tags, is_parsed: false}
+        indices.into_iter().find(|i| queries.iter().any(|q| q.matches(&tags[*i].document, tags[*i].index, FilterOptions::default()))).map(|i| &tags[i])
     }
 
-}
+/// This method counts the [`Tag`]s matching the given arguments, which can be passed like to [`filter`],
+/// without building a new [`Dom`].
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><table><tr><td>1</td></tr><tr><td>2</td></tr></table></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// println!("{}", dom.count("tr"));
+/// //Output: 2
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Tag`]: struct.Tag.html#
+/// [`filter`]: struct.Dom.html#method.filter
+    pub fn count(&self, args: impl Args) -> usize {
 
-/// Many [`Tag`]s are part of a [`Dom`]
-#[derive(Clone)]
-pub struct Tag {
-    tagname: String,
-    tagcontent: String,
-    innerhtml: String,
-}
+        let queries = args.extract();
 
+        if queries.iter().any(|q| q.is_empty()) {
+            return self.tag.len();
+        }
 
-impl crate::Tag {
+        let (document, indices) = self.scope();
 
-    /// Returns InnerHTML inside a [`Tag`] as a [`String`]
-    /// 
-    /// # Example
-    /// ```
-    /// use sitescraper;
-    /// 
-    /// let html = "<html><body><div>Hello World!</div></body></html>";
-    /// 
-    /// let dom = sitescraper::parse_html(html).unwrap();
-    /// 
-    /// let filtered_dom = dom.filter("body");
-    /// 
-    /// println!("{}", filtered_dom.tag[0].get_inner_html());
-    /// //Output: <div>Hello World!</div>
-    /// ```
-    /// [`Tag`]: struct.Tag.html#
-    pub fn get_inner_html(&self) -> String {
-        self.innerhtml.clone()
+        indices.into_iter().filter(|i| queries.iter().any(|q| q.matches(&document, *i, FilterOptions::default()))).count()
     }
 
-    /// Returns the name of the [`Tag`] as a [`String`]
-    /// 
-    /// # Example
-    /// ```
-    /// use sitescraper;
-    /// 
-    /// let html = "<html><body><div>Hello World!</div></body></html>";
-    /// 
-    /// let dom = sitescraper::parse_html(html).unwrap();
-    /// 
-    /// let filtered_dom = dom.filter("div");
-    /// 
-    /// println!("{}", filtered_dom.tag[0].get_tagname());
-    /// //Output: div
-    /// ```
-    /// [`Tag`]: struct.Tag.html#
-    pub fn get_tagname(&self) -> String {
-        self.tagname.clone()
+/// This method returns a reference to the first [`Tag`] matching the given css-like selector, like `querySelector` in the browser,
+/// or [`None`] if there is no such [`Tag`] or the selector is invalid.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><div class='item'><a href='/one'>One</a></div><div class='item'><a href='/two'>Two</a></div></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// if let Some(tag) = dom.query_selector("div.item > a") {
+///     println!("{}", tag.get_attr_value("href"));
+/// }
+/// //Output: /one
+/// ```
+/// [`Tag`]: struct.Tag.html#
+    pub fn query_selector(&self, selector: &str) -> Option<&crate::Tag> {
+        self.query_selector_all(selector).into_iter().next()
     }
 
-    /// Returns pure text inside a [`Tag`] as a [`String`]
-    /// 
-    /// # Example
-    /// ```
-    /// use sitescraper;
-    /// 
-    /// let html = "<html><body><div>Hello World!</div></body></html>";
-    /// 
-    /// let dom = sitescraper::parse_html(html).unwrap();
-    /// 
-    /// let filtered_dom = dom.filter("div");
-    /// 
-    /// println!("{}", filtered_dom.tag[0].get_text());
-    /// //Output: Hello World!
-    /// ```
-    /// [`Tag`]: struct.Tag.html#
-    pub fn get_text(&self) -> String {
-        parse::text::get(&self.tagname, self.innerhtml.clone())
-    }
+/// This method returns references to all [`Tag`]s matching the given css-like selector in document order,
+/// like `querySelectorAll` in the browser. If the selector is invalid, the returned [`Vec`] is empty.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><div class='item'><a href='/one'>One</a></div><div class='item'><a href='/two'>Two</a></div></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// for tag in dom.query_selector_all("div.item > a") {
+///     println!("{}", tag.get_attr_value("href"));
+/// }
+/// //Output: /one
+/// //        /two
+/// ```
+/// [`Tag`]: struct.Tag.html#
+    pub fn query_selector_all(&self, selector: &str) -> Vec<&crate::Tag> {
 
+        let selector = match parse::selector::parse(selector) {
+            Some(v) => v,
+            None => return vec![]
+        };
 
-    /// Returns the [`Tag`] and its contents as a [`String`]
-    /// 
-    /// # Example
-    /// ```
-    /// use sitescraper;
-    /// 
-    /// let html = "<html><body><div>Hello World!</div></body></html>";
-    /// 
-    /// let dom = sitescraper::parse_html(html).unwrap();
-    /// 
-    /// let filtered_dom = dom.filter("div");
-    /// 
-    /// println!("{}", filtered_dom.tag[0].to_string());
-    /// //Output: <div>Hello World!</div>
-    /// ```
-    /// [`Tag`]: struct.Tag.html#
-    pub fn to_string(&self) -> String {
-        format!("{}{}</{}>", self.tagcontent, self.innerhtml, self.tagname)
-    }
+        let (tags, indices) = self.scope_ref();
 
+        let mut out: Vec<&crate::Tag> = vec![];
 
-    /// Returns the value of the given attribute
-    /// 
-    /// # Example
-    /// ```
-    /// use sitescraper;
-    /// 
-    /// let html = "<html><body><div id='hello'>Hello World!</div></body></html>";
-    /// 
-    /// let dom = sitescraper::parse_html(html).unwrap();
-    /// 
-    /// let filtered_dom = dom.filter("div");
-    /// 
-    /// println!("{}", filtered_dom.tag[0].get_attr_value("id"));
-    /// //Output: hello
-    /// ```
-    pub fn get_attr_value(&self, attr: &str) -> String {
-
-        let mut out = String::new();
-        
-        if self.tagcontent.contains(&format!("{}=", attr)) {
-            out = self.tagcontent[self.tagcontent.find(&format!("{}=", attr)).unwrap()+format!("{}=", attr).len()..].to_string();
-
-            if out.chars().nth(0).unwrap() == '"' {
-                out=out[1..].to_string();
-                out=out[..out.find('"').unwrap()].to_string();
-            } else if out.chars().nth(0).unwrap() == '\''{
-                out=out[1..].to_string();
-                out=out[..out.find('\'').unwrap()].to_string();
-            } else {
-                match out.find(" ") {
-                    Some(v) => out=out[..v].to_string(),
-                    None => out=out[..out.len()-1].to_string()
-                }
+        for i in indices {
+            if selector.matches(&tags[i].document, tags[i].index, FilterOptions::default()) {
+                out.push(&tags[i]);
             }
         }
 
         out
     }
 
-}
+/// This method filters a [`Dom`] like [`filter`] and maps every matching [`Tag`] with the given closure in one pass.
+/// [`Tag`]s for which the closure returns [`None`] are skipped.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><a href='/one'>One</a><a>None</a><a href='/two'>Two</a></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let hrefs: Vec<String> = dom.extract("a[href]", |tag| Some(tag.get_attr_value("href")));
+///
+/// println!("{:?}", hrefs);
+/// //Output: ["/one", "/two"]
+/// ```
+/// [`Tag`]: struct.Tag.html#
+/// [`filter`]: struct.Dom.html#method.filter
+    pub fn extract<T, F: Fn(&crate::Tag) -> Option<T>>(&self, args: impl Args, f: F) -> Vec<T> {
 
+        let queries = args.extract();
+        let everything = queries.iter().any(|q| q.is_empty());
 
-impl crate::Dom {
-    #[allow(dead_code)]
+        let (document, indices) = self.scope();
 
-    fn tag(&self, tagname: &str) -> crate::Dom {
+        let mut out: Vec<T> = vec![];
 
-        if tagname.len() < 1 {
-            return self.clone();
+        for i in indices {
+            if everything || queries.iter().any(|q| q.matches(&document, i, FilterOptions::default())) {
+                if let Some(v) = f(&crate::Tag{document: document.clone(), index: i}) {
+                    out.push(v);
+                }
+            }
         }
 
-        let mut tags: Vec<crate::Tag> = vec![];
+        out
+    }
 
-        for n in &self.tag {
-            if n.tagname == tagname {
-                tags.push(n.clone());
-            }
-        }
+/// This method returns a [`Link`] for every `<a>` tag with a `href` attribute in a [`Dom`] or a filtered [`Dom`] in document order.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><a href='/home'>Home</a><a href='https://example.com' rel='nofollow' target='_blank'> Example </a></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// for link in dom.get_links() {
+///     println!("{} {} {:?} {:?}", link.href, link.text, link.rel, link.target);
+/// }
+/// //Output: /home Home None None
+/// //        https://example.com Example Some("nofollow") Some("_blank")
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Link`]: struct.Link.html#
+    pub fn get_links(&self) -> Vec<Link> {
+        self.extract("a[href]", |tag| Some(Link{
+            href: tag.get_attr_value("href"),
+            text: tag.get_text().trim().to_string(),
+            rel: tag.try_get_attr_value("rel"),
+            target: tag.try_get_attr_value("target"),
+        }))
+    }
+
+/// This method returns a [`Script`] for every `<script>` tag in a [`Dom`] or a filtered [`Dom`] in document order,
+/// holding the url of an external script given by its `src` attribute or the code of an inline script, and its `type`.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><head><script src='https://cdn.example.com/lib.js' async></script>
+///     <script type='module'>if (a < b) { run(); }</script></head></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// for script in dom.get_scripts() {
+///     println!("{:?} {:?} {}", script.src, script.kind, script.content);
+/// }
+/// //Output: Some("https://cdn.example.com/lib.js") None
+/// //        None Some("module") if (a < b) { run(); }
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Script`]: struct.Script.html#
+    pub fn get_scripts(&self) -> Vec<Script> {
+        self.extract("script", |tag| Some(Script{
+            src: tag.try_get_attr_value("src").map(|s| s.trim().to_string()).filter(|s| !s.is_empty()),
+            content: tag.get_inner_html().trim().to_string(),
+            kind: tag.try_get_attr_value("type").map(|t| t.trim().to_string()).filter(|t| !t.is_empty()),
+        }))
+    }
 
-        crate::Dom{tag: tags, is_parsed: false}
+/// This method returns a [`Stylesheet`] for every `<link rel="stylesheet">` tag with a `href` attribute and every `<style>` tag
+/// in a [`Dom`] or a filtered [`Dom`] in document order, holding the url of a linked stylesheet or the css of an inline stylesheet,
+/// and the media query given by its `media` attribute.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><head><link rel='stylesheet' href='/print.css' media='print'><style>body { margin: 0; }</style></head></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// for stylesheet in dom.get_stylesheets() {
+///     println!("{:?} {:?} {}", stylesheet.href, stylesheet.media, stylesheet.content);
+/// }
+/// //Output: Some("/print.css") Some("print")
+/// //        None None body { margin: 0; }
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Stylesheet`]: struct.Stylesheet.html#
+    pub fn get_stylesheets(&self) -> Vec<Stylesheet> {
+        self.extract(["link[href]", "style"], |tag| {
+            let media = tag.try_get_attr_value("media").map(|m| m.trim().to_string()).filter(|m| !m.is_empty());
+            match tag.matches("style") {
+                true => Some(Stylesheet{href: None, media, content: tag.get_inner_html().trim().to_string()}),
+                // `rel` may hold several types like `alternate stylesheet`
+                false => tag.get_attr_value("rel").split_whitespace().any(|r| r.eq_ignore_ascii_case("stylesheet"))
+                    .then(|| Stylesheet{href: Some(tag.get_attr_value("href").trim().to_string()), media, content: String::new()})
+            }
+        })
+    }
 
+/// This method returns a [`Table`] for every `<table>` in a [`Dom`] or a filtered [`Dom`] in document order,
+/// holding the texts of its header cells and the texts of the cells of every other row.
+/// Cells spanning several columns or rows by `colspan` or `rowspan` are repeated in every column and row they span,
+/// so the rows line up. The rows of nested tables are only part of the [`Table`]s of the nested tables.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><table><tr><th>Name</th><th>Price</th></tr><tr><td>Apple</td><td rowspan='2'>1.00</td></tr><tr><td>Pear</td></tr></table></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let table = &dom.get_tables()[0];
+///
+/// println!("{:?} {:?}", table.headers, table.rows);
+/// //Output: ["Name", "Price"] [["Apple", "1.00"], ["Pear", "1.00"]]
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Table`]: struct.Table.html#
+    pub fn get_tables(&self) -> Vec<Table> {
+        self.extract("table", |tag| Some(parse::table::read(&tag.document, tag.index)))
     }
-    #[allow(dead_code)]
-    fn attr(&self, attr: &str) -> crate::Dom {
 
-        if attr.len() < 1 {
-            return self.clone();
-        }
+/// This method returns a [`Form`] for every `<form>` in a [`Dom`] or a filtered [`Dom`] in document order,
+/// holding its action, its method and the [`FormField`]s it sends together with their default values,
+/// which can be changed before submitting the form.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><form action='/login' method='post'><input name='user'><input type='hidden' name='token' value='abc'></form></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let form = &dom.get_forms()[0];
+///
+/// for field in &form.fields {
+///     println!("{} {} {}={}", form.method, form.action, field.name, field.value);
+/// }
+/// //Output: POST /login user=
+/// //        POST /login token=abc
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Form`]: struct.Form.html#
+/// [`FormField`]: struct.FormField.html#
+    pub fn get_forms(&self) -> Vec<Form> {
+        self.extract("form", |tag| Some(parse::form::read(&tag.document, tag.index)))
+    }
 
-        let mut tags: Vec<crate::Tag> = vec![];
+/// This method returns the structured data given by microdata attributes in a [`Dom`] or a filtered [`Dom`] as trees of [`Item`]s.
+/// Every tag with an `itemscope` attribute starts an [`Item`] whose properties are the tags with an `itemprop` attribute inside of it.
+/// Items that are properties of other items are returned as their values, not on their own.
+///
+/// The value of a property is the `content` of a `<meta>`, the `src` of an `<img>` and other embedded content,
+/// the `href` of an `<a>`, `<area>` or `<link>`, the `data` of an `<object>`, the `value` of a `<data>` or `<meter>`,
+/// the `datetime` of a `<time>` or the text of any other tag.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper::ItemValue;
+///
+/// let html = "<html><body><div itemscope itemtype='https://schema.org/Product'><span itemprop='name'>Kettle</span>
+///     <div itemprop='offers' itemscope itemtype='https://schema.org/Offer'><meta itemprop='price' content='19.99'></div></div></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let product = &dom.get_microdata()[0];
+///
+/// println!("{:?} {:?}", product.kind, product.get("name"));
+/// //Output: ["https://schema.org/Product"] Some(Text("Kettle"))
+///
+/// if let Some(ItemValue::Item(offer)) = product.get("offers") {
+///     println!("{:?}", offer.get("price"));
+/// }
+/// //Output: Some(Text("19.99"))
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Item`]: struct.Item.html#
+    pub fn get_microdata(&self) -> Vec<Item> {
+        let (document, indices) = self.scope();
+        parse::items::read(&document, &indices, &parse::items::MICRODATA)
+    }
 
-        for n in &self.tag {
-            if n.tagcontent.contains(&format!(r#"{}=""#, attr)) || n.tagcontent.contains(&format!("{}=", attr)) {
-                tags.push(n.clone());
-            }
-        }
-        crate::Dom{tag: tags, is_parsed: false}
+/// This method returns the structured data given by RDFa attributes in a [`Dom`] or a filtered [`Dom`] as trees of [`Item`]s like [`get_microdata`].
+/// Every tag with a `typeof` attribute starts an [`Item`] whose properties are the tags with a `property` attribute inside of it.
+/// The identifier of an [`Item`] is taken from `resource` or `about`.
+///
+/// The value of a property is its `content`, `resource`, `href` or `src`, the `datetime` of a `<time>` or otherwise its text.
+/// Types and property-names are returned as they are written, without the `vocab` they refer to.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><div vocab='https://schema.org/' typeof='Person'><span property='name'>Ada</span>
+///     <a property='url' href='https://example.com/ada'>Home</a></div></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let person = &dom.get_rdfa()[0];
+///
+/// println!("{:?} {:?} {:?}", person.kind, person.get("name"), person.get("url"));
+/// //Output: ["Person"] Some(Text("Ada")) Some(Text("https://example.com/ada"))
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Item`]: struct.Item.html#
+/// [`get_microdata`]: struct.Dom.html#method.get_microdata
+    pub fn get_rdfa(&self) -> Vec<Item> {
+        let (document, indices) = self.scope();
+        parse::items::read(&document, &indices, &parse::items::RDFA)
     }
-    #[allow(dead_code)]
-    fn attr_value(&self, attrvalue: &str) -> crate::Dom {
 
-        if attrvalue.len() < 1 {
-            return self.clone();
-        }
+/// This method scans the text of a [`Dom`] or a filtered [`Dom`] and the targets of its `mailto:` and `tel:` links
+/// for email addresses and phone numbers and returns them as [`Contacts`] without duplicates, in the order they are found.
+/// Email addresses are lowercased. Phone numbers are reduced to their digits with a leading `+` for international numbers,
+/// and numbers in the text are only taken for phone numbers if they start with `+`, `00`, `(` or `0`,
+/// so prices, years or dates are not taken for phone numbers.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><p>Write to Info@Example.com or call +49 (0)30 123-4567.</p><a href='tel:+49301234567'>Call</a></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let contacts = dom.get_contacts();
+///
+/// println!("{:?} {:?}", contacts.emails, contacts.phones);
+/// //Output: ["info@example.com"] ["+49301234567"]
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Contacts`]: struct.Contacts.html#
+    pub fn get_contacts(&self) -> Contacts {
 
-        let mut tags: Vec<crate::Tag> = vec![];
+        // every piece of text is put on its own line, so numbers in different tags are not taken for one number
+        let text = self.get_text_with(TextOptions{separator: Some("\n".to_string()), ..Default::default()});
 
-        for n in &self.tag {
-            if n.tagcontent.contains(&format!(r#"="{}""#, attrvalue)) || n.tagcontent.contains(&format!(r#"={} "#, attrvalue)) || n.tagcontent.contains(&format!(r#"={}>"#, attrvalue)) {
-                tags.push(n.clone());
-            }
-        }
+        let links = self.extract("a[href], area[href]", |tag| tag.try_get_attr_value("href"));
 
-        crate::Dom{tag: tags, is_parsed: false}
+        parse::contacts::find(&text, &links)
     }
 
+/// This method filters a [`Dom`] by the given XPath 1.0 expression.
+/// It returns a [`Result`] that can be unwrapped to a filtered [`Dom`] if the expression is valid.
+///
+/// A [`Dom`] only holds tags, so expressions selecting attributes or text nodes return the tags they belong to:
+/// `//a/@href` returns the `<a>` tags that have a `href` attribute, not the values of the attributes.
+/// The values can be read from these tags with [`get_attr_value`] or [`get_text`].
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><ul><li>One</li><li class='active'>Two</li></ul></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.xpath("//ul/li[@class='active']").unwrap();
+///
+/// println!("{}", filtered_dom.get_text());
+/// //Output: Two
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`get_attr_value`]: struct.Dom.html#method.get_attr_value
+/// [`get_text`]: struct.Dom.html#method.get_text
+    pub fn xpath(&self, expr: &str) -> Result<crate::Dom, Error> {
 
-    /// Returns the [`Dom`] or a filtered [`Dom`] and its contents as a [`String`]
-    /// 
-    /// # Example
-    /// ```
-    /// use sitescraper;
-    /// 
-    /// let html = "<html><body><div>Hello World!</div></body></html>";
-    /// 
-    /// let dom = sitescraper::parse_html(html).unwrap();
-    /// 
-    /// let filtered_dom = dom.filter("div");
-    /// 
-    /// println!("{}", filtered_dom.to_string());
-    /// //Output: <div>Hello World!</div>
-    /// ```
-    /// [`Dom`]: struct.Dom.html#
-    pub fn to_string(&self) -> String {
+        let (document, indices) = self.scope();
 
-        if self.is_parsed {
-            let mut x = 0;
-            loop {
-                if self.tag[x].tagname != "" && self.tag[x].tagname != " " {
-                    return self.tag[x].to_string();
-                }
-            x=x+1;
-            }
-        }
+        let mut selected = parse::xpath::select(&document, expr)?;
 
-        let mut s: Vec<String> = vec![];
+        selected.retain(|i| indices.binary_search(i).is_ok());
 
-        for x in 0..self.tag.len() as usize {
+        Ok(crate::Dom::select(document, selected))
+    }
 
-        if &self.tag[x].tagname != "" && &self.tag[x].tagname != " " {
-            s.push(self.tag[x].to_string());
-        }
+/// This method filters a [`Dom`] by the given tag-name, attribute-name and a regular expression
+/// the attribute-value has to match.
+/// It returns a [`Result`] that can be unwrapped to a filtered [`Dom`] if the regular expression is valid.
+///
+/// Like with [`filter`], the tag-name and attribute-name can be left out by passing `""` or `"*"`.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><a href='https://example.com/a.pdf'>PDF</a><a href='https://example.com/'>Home</a></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter_regex("a", "href", r"^https://.*\.pdf$").unwrap();
+///
+/// println!("{}", filtered_dom.get_text());
+/// //Output: PDF
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`filter`]: struct.Dom.html#method.filter
+    pub fn filter_regex(&self, tag_name: &str, attr_name: &str, pattern: &str) -> Result<crate::Dom, Error> {
 
-    
-        }
+        let re = match regex::Regex::new(pattern) {
+            Ok(v) => v,
+            Err(e) => return Err(Error::new(ErrorKind::InvalidInput, format!("An error has occurred when trying to compile the regular expression! ({})", e)))
+        };
 
-        let mut cleared: Vec<String> = vec![];
+        let (document, indices) = self.scope();
 
-        for old in s {
-            let mut exists = false;
-            for new in &cleared {
-                if &old==new {
-                    exists=true;
-                }
-            }
+        let mut selected: Vec<usize> = vec![];
 
-            if !exists {
-                cleared.push(old);
+        for i in indices {
+            if !parse::is_wildcard(tag_name) && document.tagname(i) != tag_name {
+                continue;
+            }
+            for (name, value) in parse::attributes::parse(document.tagcontent(i)) {
+                if (parse::is_wildcard(attr_name) || name.eq_ignore_ascii_case(attr_name)) && re.is_match(&value) {
+                    selected.push(i);
+                    break
+                }
             }
         }
-       
-        cleared.concat()
+
+        Ok(crate::Dom::select(document, selected))
     }
 
+/// Removes all [`Tag`]s matching the given arguments from a [`Dom`] or a filtered [`Dom`].
+/// It takes the same arguments as [`filter`].
+///
+/// # Example
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><div>Post</div><div class='ad'>Buy!</div></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter("div").exclude(".ad");
+///
+/// println!("{}", filtered_dom.get_text());
+/// //Output: Post
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Tag`]: struct.Tag.html#
+/// [`filter`]: struct.Dom.html#method.filter
+    pub fn exclude(&self, args: impl Args) -> crate::Dom {
 
+        let excluded = self.filter(args);
 
-    /// Returns InnerHTML inside a [`Dom`] or a filtered [`Dom`] as a [`String`]
-    /// 
-    /// # Example
-    /// ```
-    /// use sitescraper;
-    /// 
-    /// let html = "<html><body><div>Hello World!</div></body></html>";
-    /// 
-    /// let dom = sitescraper::parse_html(html).unwrap();
-    /// 
-    /// let filtered_dom = dom.filter("body");
-    /// 
-    /// println!("{}", filtered_dom.get_inner_html());
-    /// //Output: <div>Hello World!</div>
-    /// ```
-    /// [`Dom`]: struct.Dom.html#
-    pub fn get_inner_html(&self) -> String {
+        let mut positions: Vec<usize> = vec![];
 
-        if self.is_parsed {
-            let mut x = 0;
-            loop {
-                if self.tag[x].tagname != "" && self.tag[x].tagname != " " {
-                    return self.tag[x].get_inner_html();
-                }
-            x=x+1;
+        for (p, n) in self.tag.iter().enumerate() {
+            if !excluded.tag.iter().any(|e| Arc::ptr_eq(&e.document, &n.document) && e.index == n.index) {
+                positions.push(p);
             }
         }
 
-        let mut s: Vec<String> = vec![];
-
-        for x in 0..self.tag.len() as usize {
+        self.keep(positions)
+    }
 
-        if &self.tag[x].tagname != "" && &self.tag[x].tagname != " " {
-            s.push(self.tag[x].get_inner_html());
-        }
+/// Returns a [`Dom`] containing the nearest [`Tag`] matching the given arguments for each [`Tag`] of a [`Dom`] or a filtered [`Dom`],
+/// starting at the [`Tag`] itself and walking up its ancestors. The arguments can be passed like to [`filter`].
+///
+/// # Example
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><div class='card' data-sku='42'><p><span class='price'>9.99</span></p></div></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter("span.price").closest("div.card");
+///
+/// println!("{}", filtered_dom.get_attr_value("data-sku"));
+/// //Output: 42
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Tag`]: struct.Tag.html#
+/// [`filter`]: struct.Dom.html#method.filter
+    pub fn closest(&self, args: impl Args) -> crate::Dom {
 
-    
-        }
+        let queries = args.extract();
 
-        let mut cleared: Vec<String> = vec![];
+        let (document, selected) = self.selection();
 
-        for old in s {
-            let mut exists = false;
-            for new in &cleared {
-                if &old==new {
-                    exists=true;
-                }
-            }
+        let mut indices: Vec<usize> = vec![];
 
-            if !exists {
-                cleared.push(old);
+        for i in selected {
+            if let Some(tag) = (crate::Tag{document: document.clone(), index: i}).closest_of(&queries) {
+                indices.push(tag.index);
             }
         }
-       
-        cleared.concat()
-    }
 
+        indices.sort_unstable();
+        indices.dedup();
 
+        crate::Dom::select(document, indices)
+    }
 
-    /// Returns pure text inside a [`Dom`] or a filtered [`Dom`] as a [`String`]
-    /// 
-    /// # Example
-    /// ```
-    /// use sitescraper;
-    /// 
-    /// let html = "<html><body><div>Hello World!</div></body></html>";
-    /// 
-    /// let dom = sitescraper::parse_html(html).unwrap();
-    /// 
-    /// let filtered_dom = dom.filter("body");
-    /// 
-    /// println!("{}", filtered_dom.get_text());
-    /// //Output: Hello World!
-    /// ```
-    /// [`Dom`]: struct.Dom.html#
-    pub fn get_text(&self) -> String {
+/// Keeps only the [`Tag`]s of a [`Dom`] or a filtered [`Dom`] whose text contains the given [`str`].
+/// Like [`filter_fn`], it does not search the [`Tag`]s nested in the ones of a filtered [`Dom`].
+///
+/// # Example
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><button>Add to cart</button><button>Buy now</button></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter("button").filter_text("cart");
+///
+/// println!("{}", filtered_dom.get_text());
+/// //Output: Add to cart
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Tag`]: struct.Tag.html#
+/// [`filter_fn`]: struct.Dom.html#method.filter_fn
+    pub fn filter_text(&self, text: &str) -> crate::Dom {
 
-        if self.is_parsed {
-            let mut x = 0;
-            loop {
-                if self.tag[x].tagname != "" && self.tag[x].tagname != " " {
-                    return self.tag[x].get_text();
-                }
-            x=x+1;
+        let mut positions: Vec<usize> = vec![];
+
+        for (p, n) in self.tag.iter().enumerate() {
+            if n.get_text().contains(text) {
+                positions.push(p);
             }
         }
 
-        let mut s: Vec<String> = vec![];
+        self.keep(positions)
+    }
 
-        for x in 0..self.tag.len() as usize {
+/// Returns a [`Dom`] that only contains the n-th [`Tag`] of a [`Dom`] or a filtered [`Dom`], starting at 0.
+/// If there is no such [`Tag`], the returned [`Dom`] is empty.
+///
+/// # Example
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><ul><li>One</li><li>Two</li><li>Three</li></ul></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter("li").nth(2);
+///
+/// println!("{}", filtered_dom.get_text());
+/// //Output: Three
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Tag`]: struct.Tag.html#
+    pub fn nth(&self, n: usize) -> crate::Dom {
 
-        if &self.tag[x].tagname != "" && &self.tag[x].tagname != " " {
-            s.push(parse::text::get(&self.tag[x].tagname, self.tag[x].innerhtml.clone()));
-        }
+        let positions: Vec<usize> = (0..self.tag.len()).nth(n).into_iter().collect();
 
-    
-        }
+        self.keep(positions)
+    }
 
-        let mut cleared: Vec<String> = vec![];
+/// Keeps only the [`Tag`]s of a [`Dom`] or a filtered [`Dom`] that are nested at the given depth in the document,
+/// the outermost tags having a depth of 0.
+///
+/// # Example
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><div class='wrapper'><div>Content</div></div></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter("div").at_depth(3);
+///
+/// println!("{}", filtered_dom.get_text());
+/// //Output: Content
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Tag`]: struct.Tag.html#
+    pub fn at_depth(&self, depth: usize) -> crate::Dom {
 
-        for old in s {
-            let mut exists = false;
-            for new in &cleared {
-                if &old==new {
-                    exists=true;
-                }
-            }
+        let mut positions: Vec<usize> = vec![];
 
-            if !exists {
-                cleared.push(old);
+        for (p, n) in self.tag.iter().enumerate() {
+            if n.get_depth() == depth {
+                positions.push(p);
             }
         }
-       
-        cleared.concat()
+
+        self.keep(positions)
     }
 
+/// This method filters a [`Dom`] by the given tag-name and several attribute-names and attribute-values at once.
+/// A [`Tag`] has to match all of the given attributes, whose names are matched regardless of their case.
+/// Like with [`filter`], the tag-name and attribute-values can be left out by passing `""` or `"*"`.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><input type='hidden' name='csrf' value='123'><input type='hidden' name='page' value='2'></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter_attrs("input", &[("type", "hidden"), ("name", "csrf")]);
+///
+/// println!("{}", filtered_dom.get_attr_value("value"));
+/// //Output: 123
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Tag`]: struct.Tag.html#
+/// [`filter`]: struct.Dom.html#method.filter
+    pub fn filter_attrs(&self, tag_name: &str, attrs: &[(&str, &str)]) -> crate::Dom {
+        self.filter_attrs_with(tag_name, attrs, FilterOptions::default())
+    }
 
+/// This method works like [`filter_attrs`], but the way the tag-name and attribute-values are matched
+/// can be changed by passing [`FilterOptions`].
+///
+/// # Example
+///
+/// ```
+/// use sitescraper::FilterOptions;
+///
+/// let html = "<html><body><META HTTP-EQUIV='Content-Type' CONTENT='text/html'></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter_attrs_with("meta", &[("http-equiv", "content-type")], FilterOptions{case_insensitive_values: true, ..Default::default()});
+///
+/// println!("{}", filtered_dom.get_attr_value("CONTENT"));
+/// //Output: text/html
+/// ```
+/// [`filter_attrs`]: struct.Dom.html#method.filter_attrs
+/// [`FilterOptions`]: struct.FilterOptions.html#
+    pub fn filter_attrs_with(&self, tag_name: &str, attrs: &[(&str, &str)], options: FilterOptions) -> crate::Dom {
 
-    /// Returns the value(s) of the given attribute as a [`String`]
-    /// 
-    /// # Example
-    /// ```
-    /// use sitescraper;
-    /// 
-    /// let html = "<html><body><div id='hello'>Hello World!</div></body></html>";
-    ///
-    /// let dom = sitescraper::parse_html(html).unwrap();
-    /// 
-    /// let filtered_dom = dom.filter("div");
-    /// 
-    /// println!("{}", filtered_dom.get_attr_value("id"));
-    /// //Output: hello
-    /// ```
-    /// [`Dom`]: struct.Dom.html#
-    pub fn get_attr_value(&self, attrname: &str) -> String {
+        let (document, mut indices) = self.scope();
 
-        let mut s: Vec<String> = vec![];
+        indices.retain(|i| parse::query::matches_attrs(&document, *i, tag_name, attrs, options));
 
-        for tag in &self.tag {
-            s.push(tag.get_attr_value(attrname).clone());
-        }
+        crate::Dom::select(document, indices)
+    }
 
-        let mut cleared: Vec<String> = vec![];
+/// This method filters a [`Dom`] by a custom condition. Every [`Tag`] for which the given closure returns `true` is kept.
+///
+/// Like [`filter_text`], [`nth`], [`at_depth`] and [`exclude`] it refines the [`Tag`]s of a filtered [`Dom`] itself,
+/// while [`filter`] searches the [`Tag`]s nested in them. To check the nested [`Tag`]s, select them with [`filter`] first.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><span data-price='5'>Cheap</span><span data-price='50'>Expensive</span></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let filtered_dom = dom.filter_fn(|tag| tag.get_attr_value("data-price").parse::<u32>().is_ok_and(|p| p < 10));
+///
+/// println!("{}", filtered_dom.get_text());
+/// //Output: Cheap
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Tag`]: struct.Tag.html#
+/// [`filter`]: struct.Dom.html#method.filter
+/// [`filter_text`]: struct.Dom.html#method.filter_text
+/// [`nth`]: struct.Dom.html#method.nth
+/// [`at_depth`]: struct.Dom.html#method.at_depth
+/// [`exclude`]: struct.Dom.html#method.exclude
+    pub fn filter_fn<F: Fn(&crate::Tag) -> bool>(&self, f: F) -> crate::Dom {
 
-        for y in s {
-            let mut exists = false;
-            for x in &cleared {
-                if &y == x {
-                    exists=true;
-                }
-            }
+        let mut positions: Vec<usize> = vec![];
 
-            if !exists {
-                cleared.push(y);
+        for (p, n) in self.tag.iter().enumerate() {
+            if f(n) {
+                positions.push(p);
             }
         }
 
-        cleared.concat()
+        self.keep(positions)
     }
 
-}
-
-// Test filter method
-#[test]
-fn test_filter_method() {
-    let html = "<html><body><div id='hello'>Hello World!</div></body></html>";
-    
-    let dom = parse_html(html).unwrap();
-    
-    let filtered_dom = dom.filter("div");
-    let filtered_dom2 = dom.filter("body");
-    let filtered_dom3 = dom.filter(("div", "id"));
-    let filtered_dom4 = dom.filter(("div", "id"));
+/// This method returns the text of all html-comments of a [`Dom`] without `<!--` and `-->` in document order.
+/// For a filtered [`Dom`] only the comments inside of its [`Tag`]s are returned.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><!-- page 2 of 5 --><div>Content</div></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// println!("{:?}", dom.comments());
+/// //Output: [" page 2 of 5 "]
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Tag`]: struct.Tag.html#
+    pub fn comments(&self) -> Vec<String> {
 
-    assert_eq!(filtered_dom.tag[0].get_attr_value("id"), "hello");
-    assert_eq!(filtered_dom.tag[0].get_inner_html(), "Hello World!");
-    assert_eq!(filtered_dom2.tag[0].get_text(), "Hello World!");
-    assert_eq!(filtered_dom3.tag[0].get_text(), "Hello World!");
-    assert_eq!(filtered_dom4.get_inner_html(), "Hello World!");
+        let (document, selected) = self.selection();
 
-    let html = r#"
-    <!doctype html>
-    <html>
-    <head>
-        <title>Example Domain</title>
-    
-        <meta charset="utf-8" />
-        <meta http-equiv="Content-type" content="text/html; charset=utf-8" />
-        <meta name="viewport" content="width=device-width, initial-scale=1" />
-        <style type="text/css">
-        body {
-            background-color: #f0f0f2;
-            margin: 0;
-            padding: 0;
-            font-family: -apple-system, system-ui, BlinkMacSystemFont, "Segoe UI", "Open Sans", "Helvetica Neue", Helvetica, Arial, sans-serif;
-            
+        let mut out: Vec<String> = vec![];
+
+        for c in &document.comments {
+            if self.is_parsed || selected.iter().any(|i| document[*i].start <= c.start && c.end <= document[*i].end) {
+                out.push(document.comment(c.clone()).to_string());
+            }
         }
-        div {
-            width: 600px;
-            margin: 5em auto;
-            padding: 2em;
-            background-color: #fdfdff;
-            border-radius: 0.5em;
-            box-shadow: 2px 3px 7px 2px rgba(0,0,0,0.02);
+
+        out
+    }
+
+/// This method returns the doctype declaration of the document a [`Dom`] was parsed from without `<!DOCTYPE` and `>`,
+/// or [`None`] if there is none.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<!DOCTYPE html><html><body><div>Hello World!</div></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// println!("{}", dom.get_doctype().unwrap());
+/// //Output: html
+/// ```
+/// [`Dom`]: struct.Dom.html#
+    pub fn get_doctype(&self) -> Option<String> {
+        let (document, _) = self.selection();
+        parse::metadata::doctype(&document).map(|d| d.to_string())
+    }
+
+/// This method returns `true` if browsers would render the document a [`Dom`] was parsed from in quirks mode,
+/// because it has no doctype declaration or a legacy one like `HTML 4.01 Transitional` without a system identifier.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = r#"<!DOCTYPE HTML PUBLIC "-//W3C//DTD HTML 4.01 Transitional//EN"><html><body></body></html>"#;
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// println!("{}", dom.is_quirks_mode());
+/// //Output: true
+/// ```
+/// [`Dom`]: struct.Dom.html#
+    pub fn is_quirks_mode(&self) -> bool {
+        let (document, _) = self.selection();
+        parse::metadata::is_quirks_mode(&document)
+    }
+
+/// This method returns the charset the document a [`Dom`] was parsed from declares in a `<meta charset>`
+/// or `<meta http-equiv="Content-Type">` tag, or [`None`] if there is none.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><head><meta http-equiv='Content-Type' content='text/html; charset=ISO-8859-1'></head></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// println!("{}", dom.get_charset().unwrap());
+/// //Output: ISO-8859-1
+/// ```
+/// [`Dom`]: struct.Dom.html#
+    pub fn get_charset(&self) -> Option<String> {
+        let (document, _) = self.selection();
+        parse::metadata::charset(&document)
+    }
+
+/// This method returns the `content` of all `<meta>` tags of the document a [`Dom`] was parsed from by their `name`, `property`
+/// or `http-equiv` attribute in lowercase, e.g. `description`, `og:title` or `content-type`. The value of a `<meta charset>` tag is returned by `charset`.
+/// If a name is given several times, the first `content` is returned.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><head><meta name='Description' content='A shop'><meta property='og:title' content='Home'></head></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let meta = dom.get_meta();
+///
+/// println!("{} {}", meta["description"], meta["og:title"]);
+/// //Output: A shop Home
+/// ```
+/// [`Dom`]: struct.Dom.html#
+    pub fn get_meta(&self) -> HashMap<String, String> {
+        let (document, _) = self.selection();
+        parse::metadata::meta(&document)
+    }
+
+/// This method returns the text of the `<title>` of the document a [`Dom`] was parsed from with its whitespace collapsed,
+/// or [`None`] if there is no title or it is empty. Titles of inline svg graphics are not taken into account.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><head><title>\n  Tom &amp; Jerry\n</title></head></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// println!("{:?}", dom.title());
+/// //Output: Some("Tom & Jerry")
+/// ```
+/// [`Dom`]: struct.Dom.html#
+    pub fn title(&self) -> Option<String> {
+
+        let (document, _) = self.selection();
+
+        let index = (0..document.len()).find(|i| document.namespace(*i).is_empty() && document.tagname(*i).eq_ignore_ascii_case("title"))?;
+
+        let title = document.text(index).split_whitespace().collect::<Vec<&str>>().join(" ");
+
+        Some(title).filter(|t| !t.is_empty())
+    }
+
+/// This method returns the `content` of the `<meta name="description">` tag of the document a [`Dom`] was parsed from
+/// without whitespace around it, or [`None`] if there is no description or it is empty.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><head><meta name='description' content='Cartoons for everyone'></head></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// println!("{:?}", dom.meta_description());
+/// //Output: Some("Cartoons for everyone")
+/// ```
+/// [`Dom`]: struct.Dom.html#
+    pub fn meta_description(&self) -> Option<String> {
+        self.get_meta().remove("description").map(|d| d.trim().to_string()).filter(|d| !d.is_empty())
+    }
+
+/// This method returns the `href` of the `<link rel="canonical">` tag of the document a [`Dom`] was parsed from as it is written,
+/// or [`None`] if there is none.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><head><link rel='canonical' href='https://example.com/page'></head></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// println!("{:?}", dom.canonical_url());
+/// //Output: Some("https://example.com/page")
+/// ```
+/// [`Dom`]: struct.Dom.html#
+    pub fn canonical_url(&self) -> Option<String> {
+        let (document, _) = self.selection();
+        parse::metadata::links(&document, "canonical").into_iter().next().map(|(href, _)| href)
+    }
+
+/// This method returns the language variants of the document a [`Dom`] was parsed from given by `<link rel="alternate" hreflang="...">` tags
+/// as pairs of language and `href` in document order, e.g. `("de-AT", "https://example.com/de-at/")` or `("x-default", "https://example.com/")`.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><head><link rel='alternate' hreflang='en' href='/en/'><link rel='alternate' hreflang='de' href='/de/'></head></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// for (lang, href) in dom.hreflang_links() {
+///     println!("{} {}", lang, href);
+/// }
+/// //Output: en /en/
+/// //        de /de/
+/// ```
+/// [`Dom`]: struct.Dom.html#
+    pub fn hreflang_links(&self) -> Vec<(String, String)> {
+
+        let (document, _) = self.selection();
+
+        parse::metadata::links(&document, "alternate").into_iter()
+            .filter_map(|(href, attrs)| {
+                let lang = attrs.into_iter().find(|(n, _)| n.eq_ignore_ascii_case("hreflang"))?.1;
+                Some((lang.trim().to_string(), href)).filter(|(l, _)| !l.is_empty())
+            })
+            .collect()
+    }
+
+/// This method returns the RSS and Atom feeds of the document a [`Dom`] was parsed from given by
+/// `<link rel="alternate" type="application/rss+xml">` or `type="application/atom+xml"` tags as [`FeedLink`]s in document order.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><head><link rel='alternate' type='application/rss+xml' title='News' href='/news.rss'></head></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// for feed in dom.feed_links() {
+///     println!("{} {:?} {}", feed.href, feed.title, feed.kind);
+/// }
+/// //Output: /news.rss Some("News") application/rss+xml
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`FeedLink`]: struct.FeedLink.html#
+    pub fn feed_links(&self) -> Vec<FeedLink> {
+
+        let (document, _) = self.selection();
+
+        parse::metadata::links(&document, "alternate").into_iter()
+            .filter_map(|(href, attrs)| {
+                let value = |name: &str| attrs.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, v)| v.trim().to_string());
+                // the type may be followed by parameters like `; charset=utf-8`
+                let kind = value("type")?.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+                match kind == "application/rss+xml" || kind == "application/atom+xml" {
+                    true => Some(FeedLink{href, title: value("title").filter(|t| !t.is_empty()), kind}),
+                    false => None
+                }
+            })
+            .collect()
+    }
+
+/// This method returns the language of the document a [`Dom`] was parsed from as it is declared by the `lang` attribute of the `<html>` tag
+/// or by a `<meta http-equiv="Content-Language">` tag, e.g. `en` or `de-AT`.
+///
+/// If the document does not declare its language, [`None`] is returned, unless the `detect-language` feature is enabled.
+/// Then the language is guessed from the text of the document and returned as an ISO 639-1 code like `en`, which works for
+/// texts of a few sentences in English, German, French, Spanish, Italian, Portuguese or Dutch and for texts in scripts
+/// like Cyrillic, Greek, Arabic, Hebrew, Chinese, Japanese or Korean.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html lang='de-AT'><body><p>Grüß Gott!</p></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// println!("{:?}", dom.language());
+/// //Output: Some("de-AT")
+/// ```
+/// [`Dom`]: struct.Dom.html#
+    pub fn language(&self) -> Option<String> {
+
+        let (document, _) = self.selection();
+
+        let declared = parse::metadata::language(&document);
+
+        #[cfg(feature = "detect-language")]
+        let declared = declared.or_else(|| parse::language::guess(&parse::render::render(&document, document.top_contents())));
+
+        declared
+    }
+
+/// This method returns the breadcrumb trail of the document a [`Dom`] was parsed from as [`Breadcrumb`]s from the start page to the current page.
+///
+/// A schema.org `BreadcrumbList` given as JSON-LD in a `<script type="application/ld+json">`, as microdata or as RDFa is preferred,
+/// with its items ordered by their `position`. Otherwise the trail is read from the first tag whose `aria-label` contains `breadcrumb`,
+/// like `<nav aria-label="Breadcrumb">`, or else whose class or id contains `breadcrumb`: every list item in it becomes a [`Breadcrumb`]
+/// or, if there are none, every link and the text after the last link. Separators like `›` or `/` are left out.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><nav aria-label='breadcrumb'><ol><li><a href='/'>Home</a></li><li><a href='/books'>Books</a></li>
+///     <li aria-current='page'>Rust</li></ol></nav></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// for crumb in dom.breadcrumbs() {
+///     println!("{} {:?}", crumb.name, crumb.url);
+/// }
+/// //Output: Home Some("/")
+/// //        Books Some("/books")
+/// //        Rust None
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Breadcrumb`]: struct.Breadcrumb.html#
+    pub fn breadcrumbs(&self) -> Vec<Breadcrumb> {
+
+        let (document, _) = self.selection();
+
+        parse::breadcrumbs::read(&document)
+    }
+
+/// This method returns the links to the next and the previous page of the paginated listing or article a [`Dom`] was parsed from as [`Pagination`].
+///
+/// Links marked by `rel="next"` or `rel="prev"` on a `<link>` or `<a>` tag are preferred. Otherwise the first link labeled like
+/// `Next`, `Next page »`, `›` or `Older posts`, or like its equivalent in a few other languages, by its text, `aria-label` or `title` is taken,
+/// or else the first link with a class like `next` or `pagination-next` on itself or its parent.
+/// The urls are returned as they are written, so relative urls are not resolved.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><div class='pagination'><a href='/page/1'>‹ Previous</a><a href='/page/1'>1</a><span>2</span>
+///     <a href='/page/3'>3</a><a href='/page/3'>Next ›</a></div></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let pagination = dom.pagination();
+///
+/// println!("{:?} {:?}", pagination.next, pagination.prev);
+/// //Output: Some("/page/3") Some("/page/1")
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Pagination`]: struct.Pagination.html#
+    pub fn pagination(&self) -> Pagination {
+
+        let (document, _) = self.selection();
+
+        Pagination{next: parse::pagination::find(&document, &parse::pagination::NEXT), prev: parse::pagination::find(&document, &parse::pagination::PREV)}
+    }
+
+/// This method returns the Open Graph properties given by `<meta property="og:...">` tags of the document a [`Dom`] was parsed from,
+/// as used for link previews. Properties that are not given are [`None`].
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><head><meta property='og:title' content='Rust'><meta property='og:image' content='https://example.com/rust.png'></head></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let og = dom.open_graph();
+///
+/// println!("{:?} {:?} {:?}", og.title, og.image, og.description);
+/// //Output: Some("Rust") Some("https://example.com/rust.png") None
+/// ```
+/// [`Dom`]: struct.Dom.html#
+    pub fn open_graph(&self) -> OpenGraph {
+
+        let mut meta = self.get_meta();
+        let mut take = |name: &str| meta.remove(name).filter(|v| !v.is_empty());
+
+        OpenGraph{
+            title: take("og:title"),
+            description: take("og:description"),
+            image: take("og:image").or_else(|| take("og:image:url")),
+            url: take("og:url"),
+            kind: take("og:type"),
+            site_name: take("og:site_name"),
+            locale: take("og:locale"),
         }
-        a:link, a:visited {
-            color: #38488f;
-            text-decoration: none;
+    }
+
+/// This method returns the Twitter Card properties given by `<meta name="twitter:...">` tags of the document a [`Dom`] was parsed from,
+/// as used for link previews. Properties that are not given are [`None`].
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><head><meta name='twitter:card' content='summary'><meta name='twitter:site' content='@rustlang'></head></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let card = dom.twitter_card();
+///
+/// println!("{:?} {:?} {:?}", card.card, card.site, card.title);
+/// //Output: Some("summary") Some("@rustlang") None
+/// ```
+/// [`Dom`]: struct.Dom.html#
+    pub fn twitter_card(&self) -> TwitterCard {
+
+        let mut meta = self.get_meta();
+        let mut take = |name: &str| meta.remove(name).filter(|v| !v.is_empty());
+
+        TwitterCard{
+            card: take("twitter:card"),
+            site: take("twitter:site"),
+            creator: take("twitter:creator"),
+            title: take("twitter:title"),
+            description: take("twitter:description"),
+            image: take("twitter:image").or_else(|| take("twitter:image:src")),
         }
-        @media (max-width: 700px) {
-            div {
-                margin: 0 auto;
-                width: auto;
-            }
+    }
+
+/// This method returns the problems in the html a [`Dom`] was parsed from that the parser worked around, in document order:
+/// tags that are not closed, closing tags that close a tag other than the innermost open one,
+/// closing tags without an opening tag and opening tags with broken attributes.
+/// For a filtered [`Dom`] only the problems inside of its [`Tag`]s are returned.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><div><p>Hello World!</div></span></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// for warning in dom.warnings() {
+///     println!("{}", warning);
+/// }
+/// //Output: unclosed tag <p> at line 1, column 18
+/// //        mismatched closing tag </div> at line 1, column 33
+/// //        stray closing tag </span> at line 1, column 39
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Tag`]: struct.Tag.html#
+    pub fn warnings(&self) -> Vec<Warning> {
+
+        let (document, selected) = self.selection();
+
+        let mut warnings: Vec<&(WarningKind, std::ops::Range<usize>)> = document.warnings.iter()
+            .filter(|(_, span)| self.is_parsed || selected.iter().any(|i| document[*i].start <= span.start && span.end <= document[*i].end))
+            .collect();
+
+        warnings.sort_by_key(|(_, span)| span.start);
+
+        warnings.into_iter().map(|(kind, span)| Warning{
+            kind: *kind,
+            tagname: parse::fetch::get_tagname(&document.html[span.clone()]).trim_start_matches('/').to_string(),
+            span: span.clone(),
+            position: document.position(span.start),
+        }).collect()
+    }
+
+/// This method returns the [`Tag`] with the given [`NodeId`] from the document a [`Dom`] was parsed from,
+/// or [`None`] if there is no such [`Tag`] or the [`NodeId`] belongs to another document.
+/// Any [`Dom`] filtered from the same document can be used to look it up, except for an empty one.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper;
+///
+/// let html = "<html><body><h1>Title</h1><p>Text</p></body></html>";
+///
+/// let dom = sitescraper::parse_html(html).unwrap();
+///
+/// let id = dom.find("h1").unwrap().node_id();
+///
+/// let paragraphs = dom.filter("p");
+///
+/// println!("{}", paragraphs.node(id).unwrap().get_text());
+/// //Output: Title
+/// ```
+/// [`Dom`]: struct.Dom.html#
+/// [`Tag`]: struct.Tag.html#
+/// [`NodeId`]: struct.NodeId.html#
+    pub fn node(&self, id: NodeId) -> Option<crate::Tag> {
+
+        let (document, _) = self.selection();
+
+        if id.0 != document.id || id.1 >= document.len() {
+            return None;
         }
-        </style>    
-    </head>
-    
-    <body>
-    <div>
-        <h1>Example Domain</h1>
-        <p>This domain is for use in illustrative examples in documents. You may use this
-        domain in literature without prior coordination or asking for permission.</p>
-        <p><a href="https://www.iana.org/domains/example">More information...</a></p>
-    </div>
-    </body>
-    </html>
-    "#;
 
-    let dom = parse_html(html).unwrap();
-    let filtered_dom = dom.filter("h1");
-        assert_eq!(filtered_dom.tag[0].get_text(), "Example Domain");
+        Some(crate::Tag{document, index: id.1})
+    }
+
+}
+
+/// Options that change how [`filter_with`] and [`filter_attrs_with`] match tags
+///
+/// [`filter_with`]: struct.Dom.html#method.filter_with
+/// [`filter_attrs_with`]: struct.Dom.html#method.filter_attrs_with
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FilterOptions {
+    /// Match tag-names and attribute-names regardless of their case, e.g. `<DIV>` and `<div>`
+    pub case_insensitive: bool,
+    /// Match attribute-values regardless of their case, e.g. `UTF-8` and `utf-8`.
+    /// Single attributes of a selector can also be matched this way by writing `[attr=value i]`.
+    pub case_insensitive_values: bool,
+}
+
+/// Options that change how [`get_text_with`] puts the text together
+///
+/// [`get_text_with`]: struct.Tag.html#method.get_text_with
+#[derive(Clone, Debug, Default)]
+pub struct TextOptions {
+    /// Replace every run of whitespace like spaces, tabs and line breaks by a single space
+    pub collapse_whitespace: bool,
+    /// Remove whitespace at the beginning and end of the text
+    pub trim: bool,
+    /// Put this between the pieces of text between tags, after removing whitespace around them and leaving out pieces that are only whitespace
+    pub separator: Option<String>,
+    /// Use the `alt`, `aria-label` or `title` attribute of tags without text, like images, icons and image buttons, as their text
+    pub alt_text: bool,
+    /// Leave out the text of tags that browsers do not show: `<script>`, `<style>` and `<template>`, tags with a `hidden` attribute
+    /// and tags hidden by `display: none` or `visibility: hidden` in their `style` attribute, together with the tags inside of them.
+    /// The text of a [`Tag`] inside of a hidden tag is empty.
+    ///
+    /// [`Tag`]: struct.Tag.html#
+    pub visible_only: bool,
+}
+
+/// Options that change which attributes [`fingerprint_with`] leaves out
+///
+/// [`fingerprint_with`]: struct.Dom.html#method.fingerprint_with
+#[derive(Clone, Debug, Default)]
+pub struct FingerprintOptions {
+    /// Leave out attributes whose values change on every request, which are those whose names contain `nonce`, `csrf` or `token`
+    pub ignore_dynamic_attributes: bool,
+    /// Leave out the attributes with these names, e.g. `data-timestamp`
+    pub ignored_attributes: Vec<String>,
+}
+
+/// Many [`Tag`]s are part of a [`Dom`]. A [`Tag`] refers to a tag of the parsed document,
+/// so it keeps its place in the document together with its parent and children.
+///
+/// With the `serde` feature, a [`Tag`] can be serialized as its tag-name, attributes, text and contents and deserialized again,
+/// and so can a [`Dom`].
+#[derive(Clone)]
+pub struct Tag {
+    document: Arc<Document>,
+    index: usize,
+}
+
+/// The content of a [`Tag`] is made up of [`Node`]s: the [`Tag`]s nested in it, the text between them and comments.
+/// They are returned by [`nodes`].
+///
+/// [`Tag`]: struct.Tag.html#
+/// [`Node`]: enum.Node.html#
+/// [`nodes`]: struct.Tag.html#method.nodes
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Node {
+    /// A [`Tag`] nested inside of another one
+    Element(Tag),
+    /// Text between tags as it is written in the html
+    Text(String),
+    /// The text of a comment like `<!-- text -->` without `<!--` and `-->`
+    Comment(String),
+}
+
+/// The [`Event`]s passed to the closure of [`parse_events`]
+///
+/// [`Event`]: enum.Event.html#
+/// [`parse_events`]: fn.parse_events.html
+#[derive(Clone, Debug)]
+pub enum Event<'a> {
+    /// An opening tag with its tag-name and its attribute-names and attribute-values
+    StartTag{name: &'a str, attrs: Vec<(String, String)>},
+    /// The end of a tag
+    EndTag{name: &'a str},
+    /// Text between tags as it is written in the html
+    Text(&'a str),
+    /// The text of a comment without `<!--` and `-->`
+    Comment(&'a str),
+}
+
+/// A [`NodeId`] identifies a [`Tag`] of a parsed document. It stays the same for all [`Dom`]s filtered from that document,
+/// so it can be stored and turned back into the [`Tag`] with [`node`] later on. It does not find a [`Tag`] in any other document,
+/// even one parsed from the same html.
+///
+/// [`NodeId`]: struct.NodeId.html#
+/// [`Tag`]: struct.Tag.html#
+/// [`Dom`]: struct.Dom.html#
+/// [`node`]: struct.Dom.html#method.node
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(parse::document::DocumentId, usize);
+
+/// A [`Visitor`] is passed to [`walk`] to be called for every [`Tag`] it walks through.
+/// Both methods do nothing by default, so only the needed one has to be implemented.
+///
+/// [`Visitor`]: trait.Visitor.html#
+/// [`Tag`]: struct.Tag.html#
+/// [`walk`]: struct.Dom.html#method.walk
+pub trait Visitor {
+    /// Called when a [`Tag`] is entered, before its contents are walked through
+    ///
+    /// [`Tag`]: struct.Tag.html#
+    fn enter(&mut self, _tag: &Tag) -> Walk {
+        Walk::Continue
+    }
+
+    /// Called when a [`Tag`] is left, after its contents were walked through or skipped
+    ///
+    /// [`Tag`]: struct.Tag.html#
+    fn exit(&mut self, _tag: &Tag) -> Walk {
+        Walk::Continue
+    }
+}
 
-}
\ No newline at end of file
+/// Tells [`walk`] how to go on after a [`Visitor`] was called
+///
+/// [`walk`]: struct.Dom.html#method.walk
+/// [`Visitor`]: trait.Visitor.html#
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Walk {
+    /// Go on with the walk
+    Continue,
+    /// Do not walk through the contents of the [`Tag`] that was entered. Works like `Continue` when a [`Tag`] is left.
+    ///
+    /// [`Tag`]: struct.Tag.html#
+    Skip,
+    /// Stop the walk right away
+    Stop,
+}
+
+/// A [`Link`] is a `<a>` tag as returned by [`get_links`]
+///
+/// [`Link`]: struct.Link.html#
+/// [`get_links`]: struct.Dom.html#method.get_links
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Link {
+    /// The value of the `href` attribute as it is written, so relative links are not resolved
+    pub href: String,
+    /// The text of the link without leading and trailing whitespace
+    pub text: String,
+    /// The value of the `rel` attribute, e.g. `nofollow`
+    pub rel: Option<String>,
+    /// The value of the `target` attribute, e.g. `_blank`
+    pub target: Option<String>,
+}
+
+/// A [`Script`] is a `<script>` tag as returned by [`get_scripts`]
+///
+/// [`Script`]: struct.Script.html#
+/// [`get_scripts`]: struct.Dom.html#method.get_scripts
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Script {
+    /// The url of an external script as it is written in the `src` attribute, so relative urls are not resolved
+    pub src: Option<String>,
+    /// The code of an inline script as it is written, without leading and trailing whitespace,
+    /// which is empty for external scripts
+    pub content: String,
+    /// The value of the `type` attribute, e.g. `module` or `application/ld+json`, which is [`None`] for classic JavaScript
+    /// without a `type`
+    pub kind: Option<String>,
+}
+
+/// A [`Stylesheet`] is a `<link rel="stylesheet">` or a `<style>` tag as returned by [`get_stylesheets`]
+///
+/// [`Stylesheet`]: struct.Stylesheet.html#
+/// [`get_stylesheets`]: struct.Dom.html#method.get_stylesheets
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stylesheet {
+    /// The url of a linked stylesheet as it is written in the `href` attribute, so relative urls are not resolved
+    pub href: Option<String>,
+    /// The value of the `media` attribute, e.g. `print` or `(max-width: 600px)`
+    pub media: Option<String>,
+    /// The css of an inline stylesheet as it is written, without leading and trailing whitespace,
+    /// which is empty for linked stylesheets
+    pub content: String,
+}
+
+/// A [`Breadcrumb`] is a step of the breadcrumb trail of a page as returned by [`breadcrumbs`]
+///
+/// [`Breadcrumb`]: struct.Breadcrumb.html#
+/// [`breadcrumbs`]: struct.Dom.html#method.breadcrumbs
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Breadcrumb {
+    /// The name of the page, e.g. `Books`
+    pub name: String,
+    /// The url of the page as it is written, so relative urls are not resolved. The current page often has no url.
+    pub url: Option<String>,
+}
+
+/// The links to the neighbouring pages of a paginated page as returned by [`pagination`]
+///
+/// [`pagination`]: struct.Dom.html#method.pagination
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Pagination {
+    /// The url of the next page, or [`None`] on the last page
+    pub next: Option<String>,
+    /// The url of the previous page, or [`None`] on the first page
+    pub prev: Option<String>,
+}
+
+/// A change between two [`Dom`]s as returned by [`diff`]
+///
+/// [`Dom`]: struct.Dom.html#
+/// [`diff`]: struct.Dom.html#method.diff
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DomChange {
+    /// A [`Tag`] of the other [`Dom`] without a counterpart, together with its descendants
+    Added(Tag),
+    /// A [`Tag`] of the [`Dom`] without a counterpart in the other one, together with its descendants
+    Removed(Tag),
+    /// The text directly inside of a [`Tag`] has changed, not counting the text of its children
+    TextChanged{
+        /// The [`Tag`] of the [`Dom`]
+        old: Tag,
+        /// The [`Tag`] of the other [`Dom`]
+        new: Tag,
+    },
+    /// Attributes of a [`Tag`] have been added, removed or changed
+    AttributesChanged{
+        /// The [`Tag`] of the [`Dom`]
+        old: Tag,
+        /// The [`Tag`] of the other [`Dom`]
+        new: Tag,
+    },
+}
+
+/// A [`FeedLink`] is a RSS or Atom feed of a document as returned by [`feed_links`]
+///
+/// [`FeedLink`]: struct.FeedLink.html#
+/// [`feed_links`]: struct.Dom.html#method.feed_links
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeedLink {
+    /// The value of the `href` attribute as it is written, so relative links are not resolved
+    pub href: String,
+    /// The value of the `title` attribute
+    pub title: Option<String>,
+    /// The type of the feed in lowercase, `application/rss+xml` or `application/atom+xml`
+    pub kind: String,
+}
+
+/// Statistics about the text of a [`Dom`] as returned by [`text_stats`]
+///
+/// [`Dom`]: struct.Dom.html#
+/// [`text_stats`]: struct.Dom.html#method.text_stats
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextStats {
+    /// The number of words, which are pieces of text between whitespace that contain a letter or digit
+    pub words: usize,
+    /// The number of characters that are not whitespace
+    pub characters: usize,
+    /// An estimate of the number of sentences, counting the pieces of text ending with `.`, `!` or `?`
+    /// and lines without such an ending like headings or list items
+    pub sentences: usize,
+    /// The time it takes to read the text at 200 words per minute
+    pub reading_time: std::time::Duration,
+}
+
+/// A [`Table`] holds the texts of the cells of a `<table>` as returned by [`get_tables`]
+///
+/// [`Table`]: struct.Table.html#
+/// [`get_tables`]: struct.Dom.html#method.get_tables
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Table {
+    /// The texts of the header cells. These are taken from the last of the leading rows that are part of `<thead>`
+    /// or only contain `<th>` cells, so it is empty for tables without a header.
+    pub headers: Vec<String>,
+    /// The texts of the cells of the rows that are not header rows
+    pub rows: Vec<Vec<String>>,
+}
+
+/// A [`Form`] describes a `<form>` as returned by [`get_forms`]
+///
+/// [`Form`]: struct.Form.html#
+/// [`get_forms`]: struct.Dom.html#method.get_forms
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Form {
+    /// The value of the `action` attribute as it is written, which is empty if the form is sent to the page itself
+    pub action: String,
+    /// `GET` or `POST`
+    pub method: String,
+    /// The `<input>`, `<select>` and `<textarea>` tags of the form in document order. Fields without a name are left out, as they are not sent.
+    pub fields: Vec<FormField>,
+}
+
+/// A [`FormField`] is a field of a [`Form`]
+///
+/// [`Form`]: struct.Form.html#
+/// [`FormField`]: struct.FormField.html#
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormField {
+    /// The value of the `name` attribute
+    pub name: String,
+    /// The `type` of an `<input>` like `text`, `hidden` or `checkbox`, or `select` or `textarea`
+    pub kind: String,
+    /// The default value: the `value` attribute of an `<input>`, the value of the selected option of a `<select>`
+    /// or the text of a `<textarea>`
+    pub value: String,
+    /// Whether a checkbox or radio button is checked by default, so it is sent
+    pub checked: bool,
+    /// The values of the options of a `<select>`
+    pub options: Vec<String>,
+}
+
+/// The Open Graph properties of a document as returned by [`open_graph`]
+///
+/// [`open_graph`]: struct.Dom.html#method.open_graph
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OpenGraph {
+    /// `og:title`
+    pub title: Option<String>,
+    /// `og:description`
+    pub description: Option<String>,
+    /// `og:image`, or `og:image:url` if it is not given
+    pub image: Option<String>,
+    /// `og:url`
+    pub url: Option<String>,
+    /// `og:type`, e.g. `website` or `article`
+    pub kind: Option<String>,
+    /// `og:site_name`
+    pub site_name: Option<String>,
+    /// `og:locale`, e.g. `en_US`
+    pub locale: Option<String>,
+}
+
+/// The Twitter Card properties of a document as returned by [`twitter_card`]
+///
+/// [`twitter_card`]: struct.Dom.html#method.twitter_card
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TwitterCard {
+    /// `twitter:card`, e.g. `summary` or `summary_large_image`
+    pub card: Option<String>,
+    /// `twitter:site`, the account of the website
+    pub site: Option<String>,
+    /// `twitter:creator`, the account of the author
+    pub creator: Option<String>,
+    /// `twitter:title`
+    pub title: Option<String>,
+    /// `twitter:description`
+    pub description: Option<String>,
+    /// `twitter:image`, or `twitter:image:src` if it is not given
+    pub image: Option<String>,
+}
+
+/// An [`Item`] of structured data as returned by [`get_microdata`] or [`get_rdfa`]
+///
+/// [`Item`]: struct.Item.html#
+/// [`get_microdata`]: struct.Dom.html#method.get_microdata
+/// [`get_rdfa`]: struct.Dom.html#method.get_rdfa
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Item {
+    /// The types of the item given by `itemtype` or `typeof`, e.g. `https://schema.org/Product`
+    pub kind: Vec<String>,
+    /// The identifier of the item given by `itemid`, `resource` or `about`
+    pub id: Option<String>,
+    /// The properties of the item as pairs of property-name and value in document order.
+    /// A property can be given several times, e.g. for several images.
+    pub properties: Vec<(String, ItemValue)>,
+}
+
+impl Item {
+
+    /// Returns the value of the first property with the given name, or [`None`] if the [`Item`] has no such property
+    ///
+    /// [`Item`]: struct.Item.html#
+    pub fn get(&self, name: &str) -> Option<&ItemValue> {
+        self.properties.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+    }
+}
+
+/// The value of a property of an [`Item`]
+///
+/// [`Item`]: struct.Item.html#
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ItemValue {
+    /// A text, number, date or url
+    Text(String),
+    /// An item nested in the item
+    Item(Item),
+}
+
+/// The email addresses and phone numbers found by [`get_contacts`]
+///
+/// [`get_contacts`]: struct.Dom.html#method.get_contacts
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Contacts {
+    /// Lowercased email addresses like `info@example.com`
+    pub emails: Vec<String>,
+    /// Phone numbers as digits, with a leading `+` for international numbers, like `+49301234567`
+    pub phones: Vec<String>,
+}
+
+/// A [`Warning`] describes a problem in the html that the parser worked around, as returned by [`warnings`]
+///
+/// [`Warning`]: struct.Warning.html#
+/// [`warnings`]: struct.Dom.html#method.warnings
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Warning {
+    /// The kind of the problem
+    pub kind: WarningKind,
+    /// The name of the tag the problem was found at
+    pub tagname: String,
+    /// The byte range of the opening or closing tag in the html
+    pub span: std::ops::Range<usize>,
+    /// The line and column at which the tag starts, both starting at 1
+    pub position: (usize, usize),
+}
+
+/// The kinds of problems a [`Warning`] can describe
+///
+/// [`Warning`]: struct.Warning.html#
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WarningKind {
+    /// A tag without a closing tag, which ends with the tag it is nested in or with the html
+    UnclosedTag,
+    /// A closing tag that closes a tag other than the innermost open one, which leaves the tags in between unclosed
+    MismatchedClosingTag,
+    /// A closing tag without an opening tag, which is ignored
+    StrayClosingTag,
+    /// An opening tag with broken attributes, like a missing quote or an attribute given twice
+    InvalidAttributes,
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let (kind, tag) = match self.kind {
+            WarningKind::UnclosedTag => ("unclosed tag", format!("<{}>", self.tagname)),
+            WarningKind::MismatchedClosingTag => ("mismatched closing tag", format!("</{}>", self.tagname)),
+            WarningKind::StrayClosingTag => ("stray closing tag", format!("</{}>", self.tagname)),
+            WarningKind::InvalidAttributes => ("invalid attributes in", format!("<{}>", self.tagname)),
+        };
+        write!(f, "{} {} at line {}, column {}", kind, tag, self.position.0, self.position.1)
+    }
+}
+
+
+impl crate::Tag {
+
+    /// Returns InnerHTML inside a [`Tag`] as a [`String`]
+    /// 
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    /// 
+    /// let html = "<html><body><div>Hello World!</div></body></html>";
+    /// 
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    /// 
+    /// let filtered_dom = dom.filter("body");
+    /// 
+    /// println!("{}", filtered_dom.tag[0].get_inner_html());
+    /// //Output: <div>Hello World!</div>
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    pub fn get_inner_html(&self) -> String {
+        self.document.inner_html(self.index).to_string()
+    }
+
+    /// Returns the name of the [`Tag`] as a [`String`]
+    /// 
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    /// 
+    /// let html = "<html><body><div>Hello World!</div></body></html>";
+    /// 
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    /// 
+    /// let filtered_dom = dom.filter("div");
+    /// 
+    /// println!("{}", filtered_dom.tag[0].get_tagname());
+    /// //Output: div
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    pub fn get_tagname(&self) -> String {
+        self.document.tagname(self.index).to_string()
+    }
+
+    /// Returns the namespace of the [`Tag`] as a [`String`]. This is the prefix of a tag-name like `svg:path`,
+    /// `svg` or `math` for tags of inline svg or MathML content, or an empty [`String`] for html.
+    /// 
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    /// 
+    /// let html = "<html><body><svg><path d='M0 0'/></svg></body></html>";
+    /// 
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    /// 
+    /// let filtered_dom = dom.filter("path");
+    /// 
+    /// println!("{}", filtered_dom.tag[0].get_namespace());
+    /// //Output: svg
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    pub fn get_namespace(&self) -> String {
+        self.document.namespace(self.index).to_string()
+    }
+
+    /// Returns the name of the [`Tag`] without its namespace prefix as a [`String`]
+    /// 
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    /// 
+    /// let html = "<html><body><svg:svg><svg:circle r='1'/></svg:svg></body></html>";
+    /// 
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    /// 
+    /// let filtered_dom = dom.filter("svg:circle");
+    /// 
+    /// println!("{}", filtered_dom.tag[0].get_local_name());
+    /// //Output: circle
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    pub fn get_local_name(&self) -> String {
+        parse::local_name(self.document.tagname(self.index)).to_string()
+    }
+
+    /// Returns the nesting depth of the [`Tag`] in the document, the outermost tags having a depth of 0.
+    /// 
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    /// 
+    /// let html = "<html><body><div>Hello World!</div></body></html>";
+    /// 
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    /// 
+    /// let filtered_dom = dom.filter("div");
+    /// 
+    /// println!("{}", filtered_dom.tag[0].get_depth());
+    /// //Output: 2
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    pub fn get_depth(&self) -> usize {
+        self.node().depth
+    }
+
+    /// Returns the nesting depth of the [`Tag`], a shorthand for [`get_depth`]. The depth is stored while parsing,
+    /// so it can be used to group the [`Tag`]s of a filtered [`Dom`] by their containers.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><ul><li>One</li><li><ul><li>Two</li></ul></li></ul></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// for tag in &dom.filter("li").tag {
+    ///     println!("{} {}", tag.depth(), tag.get_text());
+    /// }
+    /// //Output: 3 One
+    /// //        3 Two
+    /// //        5 Two
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    /// [`Dom`]: struct.Dom.html#
+    /// [`get_depth`]: struct.Tag.html#method.get_depth
+    pub fn depth(&self) -> usize {
+        self.get_depth()
+    }
+
+    /// Returns the byte range the [`Tag`] and its contents take up in the html it was parsed from,
+    /// from the `<` of its opening tag to the `>` of its closing tag.
+    /// 
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    /// 
+    /// let html = "<html><body><div>Hello World!</div></body></html>";
+    /// 
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    /// 
+    /// let filtered_dom = dom.filter("div");
+    /// 
+    /// println!("{:?}", filtered_dom.tag[0].span());
+    /// //Output: 12..35
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    pub fn span(&self) -> std::ops::Range<usize> {
+        self.node().start..self.node().end
+    }
+
+    /// Returns the line and column at which the [`Tag`] starts in the html it was parsed from, both starting at 1.
+    /// Columns are counted in characters.
+    /// 
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    /// 
+    /// let html = "<html>\n<body>\n  <div>Hello World!</div>\n</body>\n</html>";
+    /// 
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    /// 
+    /// let filtered_dom = dom.filter("div");
+    /// 
+    /// println!("{:?}", filtered_dom.tag[0].position());
+    /// //Output: (3, 3)
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    pub fn position(&self) -> (usize, usize) {
+        self.document.position(self.node().start)
+    }
+
+    /// Returns the [`NodeId`] of the [`Tag`], which can be used to look it up again with [`node`].
+    /// 
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    /// 
+    /// let html = "<html><body><div>Hello World!</div></body></html>";
+    /// 
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    /// 
+    /// let id = dom.filter("div").tag[0].node_id();
+    /// 
+    /// println!("{}", dom.node(id).unwrap().to_string());
+    /// //Output: <div>Hello World!</div>
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    /// [`NodeId`]: struct.NodeId.html#
+    /// [`node`]: struct.Dom.html#method.node
+    pub fn node_id(&self) -> crate::NodeId {
+        crate::NodeId(self.document.id, self.index)
+    }
+
+    /// Returns pure text inside a [`Tag`] as a [`String`]
+    /// Character references like `&amp;` or `&#39;` are decoded unless this is turned off by [`ParseOptions`].
+    /// 
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    /// 
+    /// let html = "<html><body><div>Hello World!</div></body></html>";
+    /// 
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    /// 
+    /// let filtered_dom = dom.filter("div");
+    /// 
+    /// println!("{}", filtered_dom.tag[0].get_text());
+    /// //Output: Hello World!
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    /// [`ParseOptions`]: struct.ParseOptions.html#
+    pub fn get_text(&self) -> String {
+        self.document.text(self.index)
+    }
+
+
+    /// Returns the text inside a [`Tag`] like [`get_text`], but put together from the pieces of text between its child [`Tag`]s
+    /// as given by the [`TextOptions`]. With the default [`TextOptions`] the text is returned exactly as it is written.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper::TextOptions;
+    ///
+    /// let html = "<html><body><div>\n  <b>Name:</b>   Tom\n  <i>Age:</i> 42\n</div></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// let tag = &dom.filter("div").tag[0];
+    ///
+    /// println!("{}", tag.get_text_with(TextOptions{collapse_whitespace: true, trim: true, ..Default::default()}));
+    /// //Output: Name: Tom Age: 42
+    ///
+    /// println!("{}", tag.get_text_with(TextOptions{separator: Some(" | ".to_string()), ..Default::default()}));
+    /// //Output: Name: | Tom | Age: | 42
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    /// [`get_text`]: struct.Tag.html#method.get_text
+    /// [`TextOptions`]: struct.TextOptions.html#
+    pub fn get_text_with(&self, options: TextOptions) -> String {
+        let mut segments: Vec<String> = vec![];
+        if !options.visible_only || self.document.is_visible(self.index) {
+            self.document.text_segments_with(self.document.contents(self.index), parse::text::is_verbatim(self.document.tagname(self.index)), &options, &mut segments);
+        }
+        parse::text::join(segments, &options)
+    }
+
+    /// Returns the [`Tag`] and its contents as normalized html like [`Dom::to_html`]
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><p class=intro>Hello<br>World!</p></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// println!("{}", dom.filter("p").tag[0].to_html());
+    /// //Output: <p class="intro">Hello<br/>World!</p>
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    /// [`Dom::to_html`]: struct.Dom.html#method.to_html
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        self.document.write_html(self.index, &mut out);
+        out
+    }
+
+    /// Returns the [`Tag`] and its contents as Markdown like [`Dom::to_markdown`]
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><p>See <a href='/docs'>the <b>docs</b></a>!</p></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// println!("{}", dom.filter("p").tag[0].to_markdown());
+    /// //Output: See [the **docs**](/docs)!
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    /// [`Dom::to_markdown`]: struct.Dom.html#method.to_markdown
+    pub fn to_markdown(&self) -> String {
+        parse::markdown::write(&self.document, vec![parse::document::Content::Element(self.index)])
+    }
+
+    /// Returns the text inside a [`Tag`] with line breaks like [`Dom::render_text`]
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><div><p>Hello</p><p>World!</p></div></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// println!("{}", dom.filter("div").tag[0].render_text());
+    /// //Output: Hello
+    /// //
+    /// //        World!
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    /// [`Dom::render_text`]: struct.Dom.html#method.render_text
+    pub fn render_text(&self) -> String {
+        parse::render::render(&self.document, vec![parse::document::Content::Element(self.index)])
+    }
+
+    /// Returns the n-th child [`Tag`] of a [`Tag`], starting at 0, or [`None`] if there is no such child.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><tr><td>A</td><td>B</td></tr></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// let filtered_dom = dom.filter("tr");
+    ///
+    /// println!("{}", filtered_dom.tag[0].nth_child(1).unwrap().get_text());
+    /// //Output: B
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    pub fn nth_child(&self, n: usize) -> Option<crate::Tag> {
+        self.children().nth(n)
+    }
+
+    /// Returns an iterator over the direct child [`Tag`]s of a [`Tag`] in document order.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><table><tr><td>A</td><td>B</td></tr><tr><td>C</td><td>D</td></tr></table></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// for row in &dom.filter("tr").tag {
+    ///     let cells: Vec<String> = row.children().map(|cell| cell.get_text()).collect();
+    ///     println!("{:?}", cells);
+    /// }
+    /// //Output: ["A", "B"]
+    /// //        ["C", "D"]
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    pub fn children(&self) -> impl Iterator<Item = crate::Tag> + '_ {
+        parse::tree::children(&self.document, self.index).into_iter().map(move |index| crate::Tag{document: self.document.clone(), index})
+    }
+
+    /// Returns the content of a [`Tag`] as a [`Vec`] of [`Node`]s in document order:
+    /// the [`Tag`]s nested directly inside of it, the text between them and comments.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper::Node;
+    ///
+    /// let html = "<html><body><p>Price: <b>$5</b> only today</p></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// for node in dom.find("p").unwrap().nodes() {
+    ///     match node {
+    ///         Node::Element(tag) => println!("{:?}", tag.get_text()),
+    ///         Node::Text(text) => println!("{:?}", text),
+    ///         Node::Comment(_) => ()
+    ///     }
+    /// }
+    /// //Output: "Price: "
+    /// //        "$5"
+    /// //        " only today"
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    /// [`Node`]: enum.Node.html#
+    pub fn nodes(&self) -> Vec<crate::Node> {
+        self.document.contents(self.index).into_iter().map(|c| match c {
+            parse::document::Content::Element(index) => crate::Node::Element(crate::Tag{document: self.document.clone(), index}),
+            parse::document::Content::Text(range) => crate::Node::Text(self.document.html[range].to_string()),
+            parse::document::Content::Comment(range) => crate::Node::Comment(self.document.comment(range).to_string()),
+        }).collect()
+    }
+
+    /// Returns the [`Tag`] a [`Tag`] is nested in, or [`None`] if it is one of the outermost tags of the document.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><div data-sku='42'><span class='price'>9.99</span></div></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// let filtered_dom = dom.filter("span.price");
+    ///
+    /// println!("{}", filtered_dom.tag[0].parent().unwrap().get_attr_value("data-sku"));
+    /// //Output: 42
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    pub fn parent(&self) -> Option<crate::Tag> {
+        self.node().parent.map(|index| crate::Tag{document: self.document.clone(), index})
+    }
+
+    /// Returns the nearest [`Tag`] matching the given arguments, starting at the [`Tag`] itself and walking up its ancestors,
+    /// or [`None`] if there is no such [`Tag`]. The arguments can be passed like to [`filter`].
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><div class='card' data-sku='42'><p><span class='price'>9.99</span></p></div></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// let price = dom.find("span.price").unwrap();
+    ///
+    /// println!("{}", price.closest("div.card").unwrap().get_attr_value("data-sku"));
+    /// //Output: 42
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    /// [`filter`]: struct.Dom.html#method.filter
+    pub fn closest(&self, args: impl Args) -> Option<crate::Tag> {
+        self.closest_of(&args.extract())
+    }
+
+    fn closest_of(&self, queries: &[parse::query::Query]) -> Option<crate::Tag> {
+
+        let everything = queries.iter().any(|q| q.is_empty());
+
+        let mut current = Some(self.index);
+
+        while let Some(c) = current {
+            if everything || queries.iter().any(|q| q.matches(&self.document, c, FilterOptions::default())) {
+                return Some(crate::Tag{document: self.document.clone(), index: c});
+            }
+            current = parse::tree::parent(&self.document, c);
+        }
+
+        None
+    }
+
+    /// Returns the [`Tag`] following a [`Tag`] inside of the same parent, or [`None`] if it is the last one.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><table><tr><th>Price</th><td>9.99</td></tr></table></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// let label = dom.find("th:contains('Price')").unwrap();
+    ///
+    /// println!("{}", label.next_sibling().unwrap().get_text());
+    /// //Output: 9.99
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    pub fn next_sibling(&self) -> Option<crate::Tag> {
+        let siblings = parse::tree::siblings(&self.document, self.index);
+        let position = siblings.iter().position(|i| *i == self.index)?;
+        siblings.get(position + 1).map(|i| crate::Tag{document: self.document.clone(), index: *i})
+    }
+
+    /// Returns the [`Tag`] preceding a [`Tag`] inside of the same parent, or [`None`] if it is the first one.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><table><tr><th>Price</th><td>9.99</td></tr></table></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// let value = dom.find("td").unwrap();
+    ///
+    /// println!("{}", value.prev_sibling().unwrap().get_text());
+    /// //Output: Price
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    pub fn prev_sibling(&self) -> Option<crate::Tag> {
+        let siblings = parse::tree::siblings(&self.document, self.index);
+        let position = siblings.iter().position(|i| *i == self.index)?;
+        position.checked_sub(1).map(|p| crate::Tag{document: self.document.clone(), index: siblings[p]})
+    }
+
+    /// Filters the [`Tag`]s nested inside of a [`Tag`]. The arguments can be passed like to [`filter`],
+    /// but only the subtree of the [`Tag`] is searched and the [`Tag`] itself is not part of the result.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><div class='post'><a>First</a></div><div class='post'><a>Second</a></div></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// let posts = dom.filter("div.post");
+    ///
+    /// println!("{}", posts.tag[1].filter("a").get_text());
+    /// //Output: Second
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    /// [`filter`]: struct.Dom.html#method.filter
+    pub fn filter(&self, args: impl Args) -> crate::Dom {
+
+        let queries = args.extract();
+        let everything = queries.iter().any(|q| q.is_empty());
+
+        let mut indices: Vec<usize> = vec![];
+
+        for i in parse::tree::descendants(&self.document, self.index) {
+            if everything || queries.iter().any(|q| q.matches(&self.document, i, FilterOptions::default())) {
+                indices.push(i);
+            }
+        }
+
+        crate::Dom::select(self.document.clone(), indices)
+    }
+
+    /// Returns the value of the given attribute. Character references like `&amp;` are decoded unless this is turned off by [`ParseOptions`].
+    /// 
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    /// 
+    /// let html = "<html><body><div id='hello'>Hello World!</div></body></html>";
+    /// 
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    /// 
+    /// let filtered_dom = dom.filter("div");
+    /// 
+    /// println!("{}", filtered_dom.tag[0].get_attr_value("id"));
+    /// //Output: hello
+    /// ```
+    /// [`ParseOptions`]: struct.ParseOptions.html#
+    pub fn get_attr_value(&self, attr: &str) -> String {
+        self.try_get_attr_value(attr).unwrap_or_default()
+    }
+
+    /// Returns the value of the given attribute, or [`None`] if the [`Tag`] does not have it.
+    /// Unlike [`get_attr_value`], this tells an attribute with an empty value like `alt=""` apart from a missing one.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><img src='a.png' alt=''><img src='b.png'></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// let filtered_dom = dom.filter("img");
+    ///
+    /// println!("{:?} {:?}", filtered_dom.tag[0].try_get_attr_value("alt"), filtered_dom.tag[1].try_get_attr_value("alt"));
+    /// //Output: Some("") None
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    /// [`get_attr_value`]: struct.Tag.html#method.get_attr_value
+    pub fn try_get_attr_value(&self, attr: &str) -> Option<String> {
+        self.document.attributes(self.index).into_iter().find(|(name, _)| name.eq_ignore_ascii_case(attr)).map(|(_, value)| value)
+    }
+
+    /// Returns all attributes of a [`Tag`] as pairs of attribute-name and attribute-value in the order they are written in the html.
+    /// Attributes without a value like `hidden` get an empty value. If an attribute is given twice, only the first one is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><div id='product' data-id='42' data-price='9.99' hidden>Hello World!</div></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// for (name, value) in dom.filter("div").tag[0].get_attributes() {
+    ///     println!("{}={}", name, value);
+    /// }
+    /// //Output: id=product
+    /// //        data-id=42
+    /// //        data-price=9.99
+    /// //        hidden=
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    pub fn get_attributes(&self) -> Vec<(String, String)> {
+        self.document.attributes(self.index)
+    }
+
+    /// Returns `true` if the [`Tag`] matches the given arguments, which can be passed like to [`filter`].
+    /// Selectors can refer to the tags around it, like its ancestors, just like when filtering the whole document.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><a class='next' href='/2'>Next</a><a href='/1'>Back</a></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// for tag in &dom.filter("a").tag {
+    ///     if tag.matches("a.next") {
+    ///         println!("{}", tag.get_attr_value("href"));
+    ///     }
+    /// }
+    /// //Output: /2
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    /// [`filter`]: struct.Dom.html#method.filter
+    pub fn matches(&self, args: impl Args) -> bool {
+
+        let queries = args.extract();
+
+        if queries.iter().any(|q| q.is_empty()) {
+            return true;
+        }
+
+        queries.iter().any(|q| q.matches(&self.document, self.index, FilterOptions::default()))
+    }
+
+    /// Returns `true` if the `class` attribute of the [`Tag`] contains the given class.
+    /// The attribute is split at whitespace, so `inactive` does not count as `active`.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><li class='item active'>One</li><li class='inactive'>Two</li></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// let filtered_dom = dom.filter("li");
+    ///
+    /// println!("{} {}", filtered_dom.tag[0].has_class("active"), filtered_dom.tag[1].has_class("active"));
+    /// //Output: true false
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    pub fn has_class(&self, class: &str) -> bool {
+        self.try_get_attr_value("class").is_some_and(|value| value.split_whitespace().any(|c| c == class))
+    }
+
+    /// Returns all `data-*` attributes of a [`Tag`] as a map from their names without the `data-` prefix to their values,
+    /// e.g. `data-product-id="42"` becomes `product-id` and `42`. The names are lowercased like browsers do.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><div class='product' data-product-id='42' data-price='9.99'>Coffee</div></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// let data = dom.filter("div").tag[0].data_attrs();
+    ///
+    /// println!("{} {}", data["product-id"], data["price"]);
+    /// //Output: 42 9.99
+    /// ```
+    /// [`Tag`]: struct.Tag.html#
+    pub fn data_attrs(&self) -> HashMap<String, String> {
+
+        let mut out: HashMap<String, String> = HashMap::new();
+
+        for (name, value) in self.document.attributes(self.index) {
+            let name = name.to_ascii_lowercase();
+            if let Some(v) = name.strip_prefix("data-").filter(|v| !v.is_empty()) {
+                out.entry(v.to_string()).or_insert(value);
+            }
+        }
+
+        out
+    }
+
+    fn node(&self) -> &parse::document::Node {
+        &self.document[self.index]
+    }
+
+}
+
+
+impl crate::Dom {
+
+    // Returns the document together with the indices of the tags that are searched when filtering,
+    // which are the tags of this Dom and all of their descendants
+    fn scope(&self) -> (Arc<Document>, Vec<usize>) {
+
+        let (document, selected) = self.selection();
+
+        let indices = crate::Dom::subtrees(&document, selected);
+
+        (document, indices)
+    }
+
+    // Returns the document together with the indices of the tags of this Dom in it
+    fn selection(&self) -> (Arc<Document>, Vec<usize>) {
+
+        if let Some(document) = self.document() {
+            return (document.clone(), self.tag.iter().map(|t| t.index).collect());
+        }
+
+        if self.tag.is_empty() {
+            return (Arc::new(Document::default()), vec![]);
+        }
+
+        // the tags were taken from different documents, so they are parsed again as one
+        let document = parse::fetch::parse(self.to_string());
+        let indices = parse::tree::roots(&document);
+
+        (Arc::new(document), indices)
+    }
+
+    // Works like scope, but returns references to the tags instead of their indices in the document
+    fn scope_ref(&self) -> (&[crate::Tag], Vec<usize>) {
+
+        if let Some(document) = self.document() {
+            // a parsed Dom holds every tag of its document in order, so its own tags can be handed out
+            if self.tag.len() == document.len() && self.tag.iter().enumerate().all(|(i, t)| t.index == i) {
+                return (&self.tag, (0..self.tag.len()).collect());
+            }
+            let tags = self.handles.get_or_init(|| (0..document.len()).map(|index| crate::Tag{document: document.clone(), index}).collect());
+            if tags.first().is_some_and(|t| Arc::ptr_eq(&t.document, document)) {
+                return (tags, crate::Dom::subtrees(document, self.tag.iter().map(|t| t.index).collect()));
+            }
+        }
+
+        // the tags of the Dom were changed or belong to different documents, so they are searched on their own
+        (&self.tag, (0..self.tag.len()).collect())
+    }
+
+    // Returns the document all tags of this Dom belong to
+    fn document(&self) -> Option<&Arc<Document>> {
+
+        let document = &self.tag.first()?.document;
+
+        if self.tag.iter().all(|t| Arc::ptr_eq(&t.document, document)) {
+            return Some(document);
+        }
+
+        None
+    }
+
+    // Returns the document of a Dom returned by parse_fragment
+    fn fragment(&self) -> Option<&Document> {
+        self.document().filter(|d| self.is_parsed && d.fragment).map(|d| d.as_ref())
+    }
+
+    // Returns the given indices together with the indices of their descendants
+    fn subtrees(document: &Document, mut selected: Vec<usize>) -> Vec<usize> {
+
+        selected.sort_unstable();
+
+        let mut indices: Vec<usize> = vec![];
+        let mut end = 0;
+
+        // the descendants of a tag that is already covered by a selected ancestor are skipped
+        for i in selected {
+            if i < end {
+                continue;
+            }
+            let descendants = parse::tree::descendants(document, i);
+            indices.push(i);
+            end = descendants.end;
+            indices.extend(descendants);
+        }
+
+        indices
+    }
+
+    // Builds a filtered Dom from the tags of the document at the given indices
+    fn select(document: Arc<Document>, indices: Vec<usize>) -> crate::Dom {
+        let tags = indices.into_iter().map(|index| crate::Tag{document: document.clone(), index}).collect();
+        crate::Dom{tag: tags, is_parsed: false, handles: OnceLock::new()}
+    }
+
+    // Builds a Dom from the tags at the given positions
+    fn keep(&self, positions: Vec<usize>) -> crate::Dom {
+        let tags = positions.iter().map(|p| self.tag[*p].clone()).collect();
+        crate::Dom{tag: tags, is_parsed: false, handles: OnceLock::new()}
+    }
+
+    /// Returns the [`Dom`] or a filtered [`Dom`] as normalized html. Unlike [`to_string`], which returns the html as it is written,
+    /// attribute-values are put in double quotes, void tags like `<br>` are closed by `/>` and closing tags that were left out are added.
+    /// The attributes keep their order, text and comments are returned as they are.
+    /// For a filtered [`Dom`] the html of its [`Tag`]s is returned one after another, leaving out [`Tag`]s nested in one of the others.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><p class=intro id='a'>Hello<br>World!<p>Bye</body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// println!("{}", dom.filter("body").to_html());
+    /// //Output: <body><p class="intro" id="a">Hello<br/>World!<p>Bye</p></p></body>
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    /// [`Tag`]: struct.Tag.html#
+    /// [`to_string`]: struct.Dom.html#method.to_string
+    pub fn to_html(&self) -> String {
+
+        let (document, selected) = self.selection();
+
+        let mut out = String::new();
+
+        if self.is_parsed {
+            document.write_contents(document.top_contents(), &mut out);
+            return out;
+        }
+
+        for i in crate::Dom::outermost(&document, &selected) {
+            document.write_html(i, &mut out);
+        }
+
+        out
+    }
+
+    /// Returns a [`Dom`] in which the relative urls of all `href` and `src` attributes are resolved against the given base url,
+    /// so e.g. `/about` becomes `https://example.com/about`. A `<base href>` tag in the html takes precedence over the base url
+    /// like in browsers. Values that cannot be resolved stay as they are.
+    /// For a filtered [`Dom`] the returned [`Dom`] holds its [`Tag`]s, leaving out [`Tag`]s nested in one of the others.
+    /// It returns a [`Result`] that can be unwrapped to a [`Dom`] if the base url is absolute.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><a href='../about'>About</a><img src='//cdn.example.com/logo.png'></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// let dom = dom.resolve_urls("https://example.com/shop/items/").unwrap();
+    ///
+    /// println!("{} {}", dom.filter("a").get_attr_value("href"), dom.filter("img").get_attr_value("src"));
+    /// //Output: https://example.com/shop/about https://cdn.example.com/logo.png
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    /// [`Tag`]: struct.Tag.html#
+    pub fn resolve_urls(&self, base: &str) -> Result<crate::Dom, Error> {
+
+        let (document, selected) = self.selection();
+
+        let mut base = url::parse(base)?;
+
+        let base_tag = (0..document.len()).filter(|i| document.tagname(*i).eq_ignore_ascii_case("base"))
+            .find_map(|i| document.attributes(i).into_iter().find(|(name, _)| name.eq_ignore_ascii_case("href")));
+
+        if let Some(v) = base_tag.and_then(|(_, href)| base.join(href.trim()).ok()) {
+            base = v;
+        }
+
+        let resolve = |index: usize, name: &str, value: String| {
+            if !(name.eq_ignore_ascii_case("href") || name.eq_ignore_ascii_case("src")) || document.tagname(index).eq_ignore_ascii_case("base") {
+                return value;
+            }
+            match url::join(&base, &entities::decode(&value)) {
+                Ok(v) => entities::encode(&v),
+                Err(_) => value
+            }
+        };
+
+        let mut out = String::new();
+
+        if self.is_parsed {
+            document.write_contents_with(document.top_contents(), &mut out, &resolve);
+            return match document.fragment {
+                true => Ok(parse::fetch::fetch_fragment(out, document.options)),
+                false => Ok(parse::fetch::fetch(out, document.options))
+            };
+        }
+
+        for i in crate::Dom::outermost(&document, &selected) {
+            document.write_html_with(i, &mut out, &resolve);
+        }
+
+        let resolved = parse::fetch::parse_with(out, document.options);
+        let indices = resolved.roots();
+
+        Ok(crate::Dom::select(Arc::new(resolved), indices))
+    }
+
+    /// Returns a new [`Dom`] without the parts of the page around its content: navigation, headers and footers, sidebars,
+    /// cookie banners and share buttons. They are found by their tag-names `<nav>`, `<header>`, `<footer>` and `<aside>`,
+    /// by the ARIA roles `navigation`, `banner`, `contentinfo`, `complementary` and `search`, or by words of their classes and ids
+    /// like `cookie`, `consent`, `share`, `social` or `sidebar`, as in `class="cookie-banner"`.
+    /// Headers and footers inside of `<article>` or `<main>` are kept, as they belong to the content,
+    /// and so are tags holding the content, like a `<div class="has-sidebar">` around an `<article>`.
+    /// The rest of the html is kept as it is written. For a filtered [`Dom`] the returned [`Dom`] holds its [`Tag`]s
+    /// that are not nested in one of the others with the boilerplate inside of them removed.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><header><nav><a href='/'>Home</a></nav></header><main><article><header><h1>Title</h1></header>
+    ///     <p>Text</p><div class='share-buttons'>Share</div></article></main><aside>Related</aside>
+    ///     <div id='cookie-consent'>We use cookies</div><footer>© 2021</footer></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// println!("{}", dom.strip_boilerplate().filter("body").get_text_with(sitescraper::TextOptions{separator: Some(" ".to_string()), ..Default::default()}));
+    /// //Output: Title Text
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    /// [`Tag`]: struct.Tag.html#
+    pub fn strip_boilerplate(&self) -> crate::Dom {
+
+        let (document, selected) = self.selection();
+
+        if self.is_parsed {
+            let html = parse::boilerplate::strip(&document, 0..document.html.len(), 0..document.len());
+            return match document.fragment {
+                true => parse::fetch::fetch_fragment(html, document.options),
+                false => parse::fetch::fetch(html, document.options)
+            };
+        }
+
+        let mut html = String::new();
+
+        for i in crate::Dom::outermost(&document, &selected) {
+            html.push_str(&parse::boilerplate::strip(&document, document[i].start..document[i].end, i..document[i].subtree_end));
+        }
+
+        let stripped = parse::fetch::parse_with(html, document.options);
+        let indices = stripped.roots();
+
+        crate::Dom::select(Arc::new(stripped), indices)
+    }
+
+    /// Returns the [`Dom`] or a filtered [`Dom`] as Markdown. Headings, paragraphs, emphasis, links, images, lists, quotes,
+    /// code and tables are converted into their Markdown equivalents, other tags only contribute their text.
+    /// The contents of `<head>`, `<script>` and `<style>` are left out.
+    /// For a filtered [`Dom`] the Markdown of its [`Tag`]s is separated by blank lines, leaving out [`Tag`]s nested in one of the others.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><h1>Fruits</h1><ul><li>Apple</li><li><i>Pear</i></li></ul></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// println!("{}", dom.to_markdown());
+    /// //Output: # Fruits
+    /// //
+    /// //        - Apple
+    /// //        - *Pear*
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    /// [`Tag`]: struct.Tag.html#
+    pub fn to_markdown(&self) -> String {
+
+        let (document, selected) = self.selection();
+
+        if self.is_parsed {
+            return parse::markdown::write(&document, document.top_contents());
+        }
+
+        let parts: Vec<String> = crate::Dom::outermost(&document, &selected).into_iter()
+            .map(|i| parse::markdown::write(&document, vec![parse::document::Content::Element(i)]))
+            .filter(|m| !m.is_empty())
+            .collect();
+
+        parts.join("\n\n")
+    }
+
+    /// Returns the text inside a [`Dom`] or a filtered [`Dom`] the way a text browser shows it, unlike [`get_text`],
+    /// which puts all text on one line. Block tags like `<div>`, `<li>` and `<tr>` as well as `<br>` start new lines,
+    /// paragraphs and headings are separated by blank lines, list items get bullets or numbers and the cells of a table row
+    /// are separated by tabs. The contents of `<head>`, `<script>` and `<style>` are left out.
+    /// For a filtered [`Dom`] the text of its [`Tag`]s is separated by line breaks, leaving out [`Tag`]s nested in one of the others.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><h1>Fruits</h1><ul><li>Apple</li><li>Pear</li></ul></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// println!("{}", dom.render_text());
+    /// //Output: Fruits
+    /// //
+    /// //        • Apple
+    /// //        • Pear
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    /// [`Tag`]: struct.Tag.html#
+    /// [`get_text`]: struct.Dom.html#method.get_text
+    pub fn render_text(&self) -> String {
+
+        let (document, selected) = self.selection();
+
+        if self.is_parsed {
+            return parse::render::render(&document, document.top_contents());
+        }
+
+        let parts: Vec<String> = crate::Dom::outermost(&document, &selected).into_iter()
+            .map(|i| parse::render::render(&document, vec![parse::document::Content::Element(i)]))
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        parts.join("\n")
+    }
+
+    /// Returns [`TextStats`] about the text of a [`Dom`] or a filtered [`Dom`] as it is returned by [`render_text`]:
+    /// the number of words, characters and sentences and the time it takes to read the text.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><h1>Hello</h1><p>This is a short text. It has two sentences!</p></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// let stats = dom.text_stats();
+    ///
+    /// println!("{} {} {:?}", stats.words, stats.sentences, stats.reading_time);
+    /// //Output: 10 3 3s
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    /// [`TextStats`]: struct.TextStats.html#
+    /// [`render_text`]: struct.Dom.html#method.render_text
+    pub fn text_stats(&self) -> TextStats {
+
+        let text = self.render_text();
+
+        // the bullets and numbers of list items are not part of the text
+        let lines: Vec<&str> = text.lines().map(|line| {
+            let line = line.trim_start();
+            let number = line.trim_start_matches(|c: char| c.is_ascii_digit());
+            match (line.strip_prefix("• "), number.strip_prefix(". ")) {
+                (Some(rest), _) => rest,
+                (None, Some(rest)) if number.len() < line.len() => rest,
+                _ => line
+            }
+        }).collect();
+
+        let words = lines.iter().flat_map(|l| l.split_whitespace()).filter(|w| w.chars().any(|c| c.is_alphanumeric())).count();
+
+        let characters = lines.iter().flat_map(|l| l.chars()).filter(|c| !c.is_whitespace()).count();
+
+        // a sentence ends with `.`, `!` or `?` followed by whitespace, or with its line like a heading or a list item
+        let mut sentences = 0;
+        for line in &lines {
+            let mut words = false;
+            let mut chars = line.chars().peekable();
+            while let Some(c) = chars.next() {
+                words |= c.is_alphanumeric();
+                let end = chars.peek().map_or(true, |n| n.is_whitespace());
+                if words && end && (matches!(c, '.' | '!' | '?') || chars.peek().is_none()) {
+                    sentences += 1;
+                    words = false;
+                }
+            }
+        }
+
+        // reading 200 words per minute takes 300 milliseconds per word
+        let reading_time = std::time::Duration::from_millis(words as u64 * 300);
+
+        TextStats{words, characters, sentences, reading_time}
+    }
+
+    /// Returns the [`Tag`]s of a [`Dom`] as a JSON array. Every [`Tag`] becomes an object with its tag-name, its attributes,
+    /// its text and its child [`Tag`]s. For a parsed [`Dom`] the array holds the outermost [`Tag`]s of the document,
+    /// for a filtered [`Dom`] all of its [`Tag`]s.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><ul class='menu'><li>Home</li></ul></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// println!("{}", dom.filter("ul").to_json());
+    /// //Output: [{"name":"ul","attributes":{"class":"menu"},"text":"Home","children":[{"name":"li","attributes":{},"text":"Home","children":[]}]}]
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    /// [`Tag`]: struct.Tag.html#
+    pub fn to_json(&self) -> String {
+
+        let (document, selected) = self.selection();
+
+        let indices = match self.is_parsed {
+            true => document.roots(),
+            false => selected
+        };
+
+        let mut out = String::new();
+        parse::json::write_tags(&document, &indices, &mut out);
+        out
+    }
+
+    /// Compares a [`Dom`] with another one, e.g. an older and a newer version of a page, and returns what has changed
+    /// as [`DomChange`]s in document order. For a parsed [`Dom`] the whole documents are compared, for a filtered [`Dom`]
+    /// its [`Tag`]s that are not nested in one of the others.
+    ///
+    /// The children of [`Tag`]s are lined up like the lines of two files in a diff, so a [`Tag`] inserted in a list only shows up
+    /// as [`Added`]. [`Tag`]s with the same tag-name and `id` are taken as the same [`Tag`] and compared by their attributes,
+    /// no matter their order, and by the text directly inside of them, no matter its whitespace. The [`Tag`]s of added and removed
+    /// subtrees are not reported on their own.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper::DomChange;
+    ///
+    /// let old = sitescraper::parse_html("<html><body><h1>Prices</h1><ul><li>Tea 2.50</li><li>Coffee 3.00</li></ul></body></html>").unwrap();
+    /// let new = sitescraper::parse_html("<html><body><h1 class='new'>Prices</h1><ul><li>Tea 2.50</li><li>Coffee 3.20</li><li>Juice 4.00</li></ul></body></html>").unwrap();
+    ///
+    /// for change in old.diff(&new) {
+    ///     match change {
+    ///         DomChange::Added(tag) => println!("added {}", tag),
+    ///         DomChange::Removed(tag) => println!("removed {}", tag),
+    ///         DomChange::TextChanged{old, new} => println!("text {} -> {}", old.get_text(), new.get_text()),
+    ///         DomChange::AttributesChanged{old, new} => println!("attributes {:?} -> {:?}", old.get_attributes(), new.get_attributes()),
+    ///     }
+    /// }
+    /// //Output: attributes [] -> [("class", "new")]
+    /// //        text Coffee 3.00 -> Coffee 3.20
+    /// //        added <li>Juice 4.00</li>
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    /// [`Tag`]: struct.Tag.html#
+    /// [`DomChange`]: enum.DomChange.html#
+    /// [`Added`]: enum.DomChange.html#variant.Added
+    pub fn diff(&self, other: &crate::Dom) -> Vec<DomChange> {
+
+        let tags = |dom: &crate::Dom| {
+            let (document, selected) = dom.selection();
+            let indices = match dom.is_parsed {
+                true => document.roots(),
+                false => crate::Dom::outermost(&document, &selected)
+            };
+            (document, indices)
+        };
+
+        let (old, old_tags) = tags(self);
+        let (new, new_tags) = tags(other);
+
+        parse::diff::diff(&old, &old_tags, &new, &new_tags)
+    }
+
+    /// Returns a hash of the normalized contents of a [`Dom`] or a filtered [`Dom`], which stays the same as long as the content does,
+    /// so a page only has to be looked at again when its fingerprint has changed. Whitespace, the order of attributes,
+    /// the way the html is written, like quotes or the case of tag-names, and comments do not change the fingerprint.
+    /// The hash is the same in every run of a program and every version of Rust, so it can be stored.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let old = sitescraper::parse_html("<html><body><p class='price' id='a'>3.00</p></body></html>").unwrap();
+    /// let new = sitescraper::parse_html("<HTML>\n<body>\n  <p id=\"a\" class=\"price\"> 3.00 </p>\n</body>\n</HTML>").unwrap();
+    ///
+    /// println!("{}", old.fingerprint() == new.fingerprint());
+    /// //Output: true
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint_with(FingerprintOptions::default())
+    }
+
+    /// Returns a hash of the normalized contents of a [`Dom`] or a filtered [`Dom`] like [`fingerprint`],
+    /// leaving out the attributes given by the [`FingerprintOptions`], like nonces that change on every request.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper::FingerprintOptions;
+    ///
+    /// let old = sitescraper::parse_html("<html><body><script nonce='r4nd0m'>run();</script><p data-rendered='12:00'>Hello</p></body></html>").unwrap();
+    /// let new = sitescraper::parse_html("<html><body><script nonce='0th3r'>run();</script><p data-rendered='12:05'>Hello</p></body></html>").unwrap();
+    ///
+    /// let options = FingerprintOptions{ignore_dynamic_attributes: true, ignored_attributes: vec!["data-rendered".to_string()]};
+    ///
+    /// println!("{}", old.fingerprint_with(options.clone()) == new.fingerprint_with(options));
+    /// //Output: true
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    /// [`fingerprint`]: struct.Dom.html#method.fingerprint
+    /// [`FingerprintOptions`]: struct.FingerprintOptions.html#
+    pub fn fingerprint_with(&self, options: FingerprintOptions) -> u64 {
+
+        let (document, selected) = self.selection();
+
+        let contents = match self.is_parsed {
+            true => document.top_contents(),
+            false => crate::Dom::outermost(&document, &selected).into_iter().map(parse::document::Content::Element).collect()
+        };
+
+        parse::fingerprint::hash(&document, contents, &options)
+    }
+
+    /// Walks through the [`Tag`]s of a [`Dom`] and their descendants in document order, calling [`enter`] of the [`Visitor`]
+    /// before and [`exit`] after the contents of every [`Tag`]. For a filtered [`Dom`] the walk starts at each of its [`Tag`]s
+    /// that is not nested in one of the others. The [`Walk`] returned by the [`Visitor`] can skip the contents of a [`Tag`] or stop the walk.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper::{Tag, Visitor, Walk};
+    ///
+    /// // Finds the deepest nesting of lists
+    /// struct Lists {
+    ///     depth: usize,
+    ///     max: usize,
+    /// }
+    ///
+    /// impl Visitor for Lists {
+    ///     fn enter(&mut self, tag: &Tag) -> Walk {
+    ///         if tag.get_tagname() == "ul" {
+    ///             self.depth += 1;
+    ///             self.max = self.max.max(self.depth);
+    ///         }
+    ///         Walk::Continue
+    ///     }
+    ///
+    ///     fn exit(&mut self, tag: &Tag) -> Walk {
+    ///         if tag.get_tagname() == "ul" {
+    ///             self.depth -= 1;
+    ///         }
+    ///         Walk::Continue
+    ///     }
+    /// }
+    ///
+    /// let html = "<html><body><ul><li>One<ul><li>Two</li></ul></li></ul><ul><li>Three</li></ul></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// let mut lists = Lists{depth: 0, max: 0};
+    ///
+    /// dom.walk(&mut lists);
+    ///
+    /// println!("{}", lists.max);
+    /// //Output: 2
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    /// [`Tag`]: struct.Tag.html#
+    /// [`Visitor`]: trait.Visitor.html#
+    /// [`Walk`]: enum.Walk.html#
+    /// [`enter`]: trait.Visitor.html#method.enter
+    /// [`exit`]: trait.Visitor.html#method.exit
+    pub fn walk(&self, visitor: &mut impl Visitor) {
+
+        let (document, selected) = self.selection();
+
+        let tops = match self.is_parsed {
+            true => document.roots(),
+            false => crate::Dom::outermost(&document, &selected)
+        };
+
+        for i in tops {
+            if !crate::Dom::walk_tag(&document, i, visitor) {
+                return;
+            }
+        }
+    }
+
+    // Walks through a tag and its descendants, returning false if the walk was stopped
+    fn walk_tag(document: &Arc<Document>, index: usize, visitor: &mut impl Visitor) -> bool {
+
+        let tag = crate::Tag{document: document.clone(), index};
+
+        match visitor.enter(&tag) {
+            Walk::Stop => return false,
+            Walk::Skip => (),
+            Walk::Continue => {
+                for c in document.children(index) {
+                    if !crate::Dom::walk_tag(document, c, visitor) {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        visitor.exit(&tag) != Walk::Stop
+    }
+
+    // Concatenates the strings, leaving out the ones that came up before
+    fn concat_unique(strings: impl Iterator<Item = String>) -> String {
+        let mut seen: HashSet<String> = HashSet::new();
+        strings.filter(|s| seen.insert(s.clone())).collect::<Vec<String>>().concat()
+    }
+
+    // Returns the selected tags that are not nested in another selected tag
+    fn outermost(document: &Document, selected: &[usize]) -> Vec<usize> {
+
+        let mut out: Vec<usize> = vec![];
+
+        for (n, i) in selected.iter().enumerate() {
+            if !selected[..n].iter().any(|s| document[*s].start <= document[*i].start && document[*i].end <= document[*s].end) {
+                out.push(*i);
+            }
+        }
+
+        out
+    }
+
+    /// Returns InnerHTML inside a [`Dom`] or a filtered [`Dom`] as a [`String`]
+    /// 
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    /// 
+    /// let html = "<html><body><div>Hello World!</div></body></html>";
+    /// 
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    /// 
+    /// let filtered_dom = dom.filter("body");
+    /// 
+    /// println!("{}", filtered_dom.get_inner_html());
+    /// //Output: <div>Hello World!</div>
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    pub fn get_inner_html(&self) -> String {
+
+        if let Some(document) = self.fragment() {
+            return document.html.clone();
+        }
+
+        if self.is_parsed {
+            if let Some(tag) = self.tag.first() {
+                return tag.get_inner_html();
+            }
+        }
+
+        crate::Dom::concat_unique(self.tag.iter().map(|t| t.get_inner_html()))
+    }
+
+
+
+    /// Returns pure text inside a [`Dom`] or a filtered [`Dom`] as a [`String`]
+    /// Character references like `&amp;` or `&#39;` are decoded.
+    /// 
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    /// 
+    /// let html = "<html><body><div>Hello World!</div></body></html>";
+    /// 
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    /// 
+    /// let filtered_dom = dom.filter("body");
+    /// 
+    /// println!("{}", filtered_dom.get_text());
+    /// //Output: Hello World!
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    pub fn get_text(&self) -> String {
+
+        if let Some(document) = self.fragment() {
+            return document.html_text();
+        }
+
+        if self.is_parsed {
+            if let Some(tag) = self.tag.first() {
+                return tag.get_text();
+            }
+        }
+
+        crate::Dom::concat_unique(self.tag.iter().map(|t| t.get_text()))
+    }
+
+    /// Returns the text inside a [`Dom`] or a filtered [`Dom`] like [`get_text`], but put together as given by the [`TextOptions`]
+    /// like [`Tag::get_text_with`]. The text of the [`Tag`]s of a filtered [`Dom`] is joined by the separator, if there is one.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper::TextOptions;
+    ///
+    /// let html = "<html><body><ul>\n  <li>One</li>\n  <li>Two</li>\n</ul></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// println!("{}", dom.filter("ul").get_text_with(TextOptions{separator: Some(", ".to_string()), ..Default::default()}));
+    /// //Output: One, Two
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    /// [`Tag`]: struct.Tag.html#
+    /// [`get_text`]: struct.Dom.html#method.get_text
+    /// [`TextOptions`]: struct.TextOptions.html#
+    /// [`Tag::get_text_with`]: struct.Tag.html#method.get_text_with
+    pub fn get_text_with(&self, options: TextOptions) -> String {
+
+        let mut segments: Vec<String> = vec![];
+
+        if let Some(document) = self.fragment() {
+            document.text_segments_with(document.top_contents(), false, &options, &mut segments);
+            return parse::text::join(segments, &options);
+        }
+
+        if self.is_parsed {
+            if let Some(tag) = self.tag.first() {
+                return tag.get_text_with(options);
+            }
+        }
+
+        let (document, selected) = self.selection();
+
+        for i in crate::Dom::outermost(&document, &selected) {
+            if !options.visible_only || document.is_visible(i) {
+                document.text_segments_with(document.contents(i), parse::text::is_verbatim(document.tagname(i)), &options, &mut segments);
+            }
+        }
+
+        parse::text::join(segments, &options)
+    }
+
+
+
+    /// Returns the value(s) of the given attribute as a [`String`]
+    /// 
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    /// 
+    /// let html = "<html><body><div id='hello'>Hello World!</div></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    /// 
+    /// let filtered_dom = dom.filter("div");
+    /// 
+    /// println!("{}", filtered_dom.get_attr_value("id"));
+    /// //Output: hello
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    pub fn get_attr_value(&self, attrname: &str) -> String {
+
+        crate::Dom::concat_unique(self.tag.iter().map(|t| t.get_attr_value(attrname)))
+    }
+
+    /// Returns an iterator over the [`Tag`]s of a [`Dom`], so iterator adapters like `map`, `filter` or `take` can be used on them
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><a href='/one'>One</a><a>Two</a><a href='/three'>Three</a></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// let links: Vec<String> = dom.filter("a").iter().filter_map(|t| t.try_get_attr_value("href")).collect();
+    ///
+    /// println!("{:?}", links);
+    /// //Output: ["/one", "/three"]
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    /// [`Tag`]: struct.Tag.html#
+    pub fn iter(&self) -> std::slice::Iter<'_, crate::Tag> {
+        self.tag.iter()
+    }
+
+    /// Returns the [`Tag`] at the given position of a [`Dom`], or [`None`] if there are not as many [`Tag`]s.
+    /// Unlike `dom.tag[i]`, this does not panic if nothing matched a filter.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><div>Hello</div><div>World!</div></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// let filtered_dom = dom.filter("div");
+    ///
+    /// println!("{:?} {:?}", filtered_dom.get(1).map(|t| t.get_text()), filtered_dom.get(2).map(|t| t.get_text()));
+    /// //Output: Some("World!") None
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    /// [`Tag`]: struct.Tag.html#
+    pub fn get(&self, i: usize) -> Option<&crate::Tag> {
+        self.tag.get(i)
+    }
+
+    /// Returns the first [`Tag`] of a [`Dom`], or [`None`] if it is empty
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><div>Hello</div><div>World!</div></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// println!("{}", dom.filter("div").first().unwrap().get_text());
+    /// //Output: Hello
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    /// [`Tag`]: struct.Tag.html#
+    pub fn first(&self) -> Option<&crate::Tag> {
+        self.tag.first()
+    }
+
+    /// Returns the last [`Tag`] of a [`Dom`], or [`None`] if it is empty
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><div>Hello</div><div>World!</div></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// println!("{}", dom.filter("div").last().unwrap().get_text());
+    /// //Output: World!
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    /// [`Tag`]: struct.Tag.html#
+    pub fn last(&self) -> Option<&crate::Tag> {
+        self.tag.last()
+    }
+
+    /// Returns the number of [`Tag`]s in a [`Dom`]. For a filtered [`Dom`] this is the number of [`Tag`]s that matched.
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><div>Hello</div><div>World!</div></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// println!("{} {}", dom.filter("div").len(), dom.filter("span").len());
+    /// //Output: 2 0
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    /// [`Tag`]: struct.Tag.html#
+    pub fn len(&self) -> usize {
+        self.tag.len()
+    }
+
+    /// Returns `true` if a [`Dom`] has no [`Tag`]s, e.g. because nothing matched a filter
+    ///
+    /// # Example
+    /// ```
+    /// use sitescraper;
+    ///
+    /// let html = "<html><body><div>Hello World!</div></body></html>";
+    ///
+    /// let dom = sitescraper::parse_html(html).unwrap();
+    ///
+    /// println!("{} {}", dom.filter("div").is_empty(), dom.filter("span").is_empty());
+    /// //Output: false true
+    /// ```
+    /// [`Dom`]: struct.Dom.html#
+    /// [`Tag`]: struct.Tag.html#
+    pub fn is_empty(&self) -> bool {
+        self.tag.is_empty()
+    }
+
+}
+
+impl<'a> IntoIterator for &'a crate::Dom {
+    type Item = &'a crate::Tag;
+    type IntoIter = std::slice::Iter<'a, crate::Tag>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tag.iter()
+    }
+}
+
+impl IntoIterator for crate::Dom {
+    type Item = crate::Tag;
+    type IntoIter = std::vec::IntoIter<crate::Tag>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tag.into_iter()
+    }
+}
+
+/// Writes the [`Tag`] and its contents as they are written in the html, so `to_string` returns them as a [`String`]
+/// 
+/// # Example
+/// ```
+/// use sitescraper;
+/// 
+/// let html = "<html><body><div>Hello World!</div></body></html>";
+/// 
+/// let dom = sitescraper::parse_html(html).unwrap();
+/// 
+/// let filtered_dom = dom.filter("div");
+/// 
+/// println!("{}", filtered_dom.tag[0].to_string());
+/// //Output: <div>Hello World!</div>
+/// ```
+/// [`Tag`]: struct.Tag.html#
+impl std::fmt::Display for crate::Tag {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.document.outer_html(self.index))
+    }
+}
+
+// Shows the tag-name, the attributes and the beginning of the text
+impl std::fmt::Debug for crate::Tag {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+
+        struct Attributes(Vec<(String, String)>);
+
+        impl std::fmt::Debug for Attributes {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.debug_map().entries(self.0.iter().map(|(n, v)| (n, v))).finish()
+            }
+        }
+
+        let mut text = self.get_text_with(TextOptions::default());
+        if let Some((i, _)) = text.char_indices().nth(40) {
+            text.truncate(i);
+            text.push_str("...");
+        }
+
+        f.debug_struct("Tag")
+            .field("name", &self.document.tagname(self.index))
+            .field("attributes", &Attributes(self.get_attributes()))
+            .field("text", &text)
+            .finish()
+    }
+}
+
+// Tags are equal if their normalized html is equal, no matter where they are in the html,
+// so tags written with other quotes or a different case of the tag-name are equal as well
+impl PartialEq for crate::Tag {
+    fn eq(&self, other: &crate::Tag) -> bool {
+        (Arc::ptr_eq(&self.document, &other.document) && self.index == other.index) || self.to_html() == other.to_html()
+    }
+}
+
+impl Eq for crate::Tag {}
+
+impl std::hash::Hash for crate::Tag {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.to_html().hash(state);
+    }
+}
+
+/// Writes the [`Dom`] or a filtered [`Dom`] and its contents as they are written in the html, so `to_string` returns them as a [`String`]
+/// 
+/// # Example
+/// ```
+/// use sitescraper;
+/// 
+/// let html = "<html><body><div>Hello World!</div></body></html>";
+/// 
+/// let dom = sitescraper::parse_html(html).unwrap();
+/// 
+/// let filtered_dom = dom.filter("div");
+/// 
+/// println!("{}", filtered_dom.to_string());
+/// //Output: <div>Hello World!</div>
+/// ```
+/// [`Dom`]: struct.Dom.html#
+impl std::fmt::Display for crate::Dom {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+
+        if let Some(document) = self.fragment() {
+            return f.write_str(&document.html);
+        }
+
+        if self.is_parsed {
+            if let Some(tag) = self.tag.first() {
+                return write!(f, "{}", tag);
+            }
+        }
+
+        f.write_str(&crate::Dom::concat_unique(self.tag.iter().map(|t| t.to_string())))
+    }
+}
+
+impl std::fmt::Debug for crate::Dom {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Dom").field("tag", &self.tag).finish()
+    }
+}
+
+// Test filter method
+#[test]
+fn test_filter_method() {
+    let html = "<html><body><div id='hello'>Hello World!</div></body></html>";
+    
+    let dom = parse_html(html).unwrap();
+    
+    let filtered_dom = dom.filter("div");
+    let filtered_dom2 = dom.filter("body");
+    let filtered_dom3 = dom.filter(("div", "id"));
+    let filtered_dom4 = dom.filter(("div", "id"));
+
+    assert_eq!(filtered_dom.tag[0].get_attr_value("id"), "hello");
+    assert_eq!(filtered_dom.tag[0].get_inner_html(), "Hello World!");
+    assert_eq!(filtered_dom2.tag[0].get_text(), "Hello World!");
+    assert_eq!(filtered_dom3.tag[0].get_text(), "Hello World!");
+    assert_eq!(filtered_dom4.get_inner_html(), "Hello World!");
+
+    let html = r#"
+    <!doctype html>
+    <html>
+    <head>
+        <title>Example Domain</title>
+    
+        <meta charset="utf-8" />
+        <meta http-equiv="Content-type" content="text/html; charset=utf-8" />
+        <meta name="viewport" content="width=device-width, initial-scale=1" />
+        <style type="text/css">
+        body {
+            background-color: #f0f0f2;
+            margin: 0;
+            padding: 0;
+            font-family: -apple-system, system-ui, BlinkMacSystemFont, "Segoe UI", "Open Sans", "Helvetica Neue", Helvetica, Arial, sans-serif;
+            
+        }
+        div {
+            width: 600px;
+            margin: 5em auto;
+            padding: 2em;
+            background-color: #fdfdff;
+            border-radius: 0.5em;
+            box-shadow: 2px 3px 7px 2px rgba(0,0,0,0.02);
+        }
+        a:link, a:visited {
+            color: #38488f;
+            text-decoration: none;
+        }
+        @media (max-width: 700px) {
+            div {
+                margin: 0 auto;
+                width: auto;
+            }
+        }
+        </style>    
+    </head>
+    
+    <body>
+    <div>
+        <h1>Example Domain</h1>
+        <p>This domain is for use in illustrative examples in documents. You may use this
+        domain in literature without prior coordination or asking for permission.</p>
+        <p><a href="https://www.iana.org/domains/example">More information...</a></p>
+    </div>
+    </body>
+    </html>
+    "#;
+
+    let dom = parse_html(html).unwrap();
+    let filtered_dom = dom.filter("h1");
+        assert_eq!(filtered_dom.tag[0].get_text(), "Example Domain");
+
+}
+// Test xpath method
+#[test]
+fn test_xpath_method() {
+    let html = "<html><body><div id='main'><ul><li>One</li><li class='active'>Two</li><li>Three</li></ul><p>Text <a href='/more'>More</a></p></div><img src='x.png'><p>Last</p></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.xpath("//li[@class='active']").unwrap().get_text(), "Two");
+    assert_eq!(dom.xpath("/html/body/div/ul/li[last()]").unwrap().get_text(), "Three");
+    assert_eq!(dom.xpath("//li[position() < 3]").unwrap().tag.len(), 2);
+    assert_eq!(dom.xpath("//a[contains(@href, 'more')]/..").unwrap().tag[0].get_tagname(), "p");
+    assert_eq!(dom.xpath("//li[text()='One']/following-sibling::li[1]").unwrap().get_text(), "Two");
+    assert_eq!(dom.xpath("//a/@href").unwrap().get_attr_value("href"), "/more");
+    assert_eq!(dom.xpath("//body/p").unwrap().get_text(), "Last");
+    assert_eq!(dom.xpath("//div[@id='main']//p | //ul").unwrap().tag[0].get_tagname(), "ul");
+    assert_eq!(dom.filter("div").xpath("//li").unwrap().tag.len(), 3);
+    assert!(dom.xpath("//li[").is_err());
+
+    assert_eq!(dom.xpath("//li[position() mod 2 = 0]").unwrap().get_text(), "Two");
+    assert_eq!(dom.xpath("//li[position() * 2 = last() + 1]").unwrap().get_text(), "Two");
+    assert_eq!(dom.xpath("//li[6 div position() = 3]").unwrap().get_text(), "Two");
+    assert_eq!(dom.xpath("//li[1 div 0 > 1000]").unwrap().tag.len(), 3);
+    assert_eq!(dom.xpath("//li[string(1 div 0) = 'Infinity']").unwrap().tag.len(), 3);
+    assert_eq!(dom.xpath("//li[2 - 1 * 2 = 0]").unwrap().tag.len(), 3);
+    assert_eq!(dom.xpath("//ul/*[2]").unwrap().get_text(), "Two");
+    assert_eq!(dom.xpath("(//li)[1]").unwrap().get_text(), "One");
+    assert_eq!(dom.xpath("(//li)[last()]").unwrap().get_text(), "Three");
+    assert_eq!(dom.xpath("(//li | //a)[@href]/..").unwrap().tag[0].get_tagname(), "p");
+    assert_eq!(dom.xpath("//li[(//li)[2] = 'Two' and position() = 1]").unwrap().get_text(), "One");
+    assert_eq!(dom.xpath("//a/@href").unwrap().tag[0].get_tagname(), "a");
+}
+
+// Test filter_regex method
+#[test]
+fn test_filter_regex_method() {
+    let html = r#"<html><body><a href="https://example.com/report.pdf">Report</a><a href='https://example.com/'>Home</a><a href=/local.pdf>Local</a></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter_regex("a", "href", r"^https://.*\.pdf$").unwrap().get_text(), "Report");
+    assert_eq!(dom.filter_regex("*", "", r"\.pdf$").unwrap().tag.len(), 2);
+    assert_eq!(dom.filter("body").filter_regex("a", "href", r"example\.com/$").unwrap().get_text(), "Home");
+    assert!(dom.filter_regex("a", "href", r"(").is_err());
+    assert_eq!(dom.filter_regex("a", "HREF", r"^/local").unwrap().get_text(), "Local");
+}
+
+// Test attribute operators
+#[test]
+fn test_attribute_operators() {
+    let html = r#"<html><body><a href="https://example.com/cat.jpg">Cat</a><a href="http://example.com/dog.png">Dog</a><a name="top">Top</a></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter("a[href$='.jpg']").get_text(), "Cat");
+    assert_eq!(dom.filter("a[href^=http://]").get_text(), "Dog");
+    assert_eq!(dom.filter("[href*=\"example.com\"]").tag.len(), 2);
+    assert_eq!(dom.filter("a[name=top]").get_text(), "Top");
+    assert_eq!(dom.filter("a[href][href*=dog]").get_text(), "Dog");
+    assert_eq!(dom.filter("a[href^='']").tag.len(), 0);
+    assert_eq!(dom.filter("a[").tag.len(), 0);
+
+    let html = r#"<html><body><p class="note big">A</p><p class="notes">B</p><p lang="en-US">C</p><p lang="en">D</p><p lang="eng">E</p></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter("p[class~=note]").get_text(), "A");
+    assert_eq!(dom.filter("p[class~='big']").get_text(), "A");
+    assert_eq!(dom.filter("p[class~='note big']").tag.len(), 0);
+    assert_eq!(dom.filter("p[lang|=en]").get_text(), "CD");
+    assert_eq!(dom.filter("p[lang|=\"en-US\"]").get_text(), "C");
+    assert_eq!(dom.filter("p[class!=note]").tag.len(), 0);
+    assert_eq!(dom.filter("p[class&=note]").tag.len(), 0);
+    assert_eq!(dom.filter("p[cl ass]").tag.len(), 0);
+
+    let html = r#"<html><body><div class="post" data-id="1">A</div><div class="post" data-id="2">B</div><div data-id="2">C</div></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter(("div.post", "data-id", "2")).get_text(), "B");
+    assert_eq!(dom.filter(("div.post", "data-id")).tag.len(), 2);
+    assert_eq!(dom.filter(("div.post", "title")).tag.len(), 0);
+    assert_eq!(dom.filter(("div[data-id^=1], div.post", "data-id", "1")).get_text(), "A");
+    assert_eq!(dom.filter_with((".post", "DATA-ID", "2"), FilterOptions{case_insensitive: true, ..Default::default()}).get_text(), "B");
+}
+
+// Test class shorthand
+#[test]
+fn test_class_selector() {
+    let html = r#"<html><body><div class="item price">10</div><div class="pricey">20</div><span class=" price ">30</span></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter(".price").tag.len(), 2);
+    assert_eq!(dom.filter("div.price").get_text(), "10");
+    assert_eq!(dom.filter("div.item.price").get_text(), "10");
+    assert_eq!(dom.filter(".item.pricey").tag.len(), 0);
+    assert_eq!(dom.filter("span.price[class*=pri]").get_text(), "30");
+}
+
+// Test id shorthand
+#[test]
+fn test_id_selector() {
+    let html = r#"<html><body><div id="main-content" class="box">Main</div><div id="main">Other</div></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter("#main-content").get_text(), "Main");
+    assert_eq!(dom.filter("div#main").get_text(), "Other");
+    assert_eq!(dom.filter("#main-content.box").get_text(), "Main");
+    assert_eq!(dom.filter("span#main").tag.len(), 0);
+    assert_eq!(dom.filter("#a#b").tag.len(), 0);
+}
+
+// Test positional selection
+#[test]
+fn test_positional_selection() {
+    let html = "<html><body><table><tr><th>H</th><td>A</td><td>B</td><td>C</td><td>D</td></tr></table></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter("td:nth-child(3)").get_text(), "B");
+    assert_eq!(dom.filter("td:nth-of-type(3)").get_text(), "C");
+    assert_eq!(dom.filter("td:nth-child(odd)").get_text(), "BD");
+    assert_eq!(dom.filter("td:nth-child(2n+4)").get_text(), "C");
+    assert_eq!(dom.filter("td:nth-last-child(1)").get_text(), "D");
+    assert_eq!(dom.filter("td:first-of-type").get_text(), "A");
+    assert_eq!(dom.filter("td:last-of-type").get_text(), "D");
+    assert_eq!(dom.filter("td:nth-child(-n+2)").get_text(), "A");
+    assert_eq!(dom.filter("td").nth(1).get_text(), "B");
+    assert_eq!(dom.filter("td").nth(10).tag.len(), 0);
+    assert_eq!(dom.filter("tr").tag[0].nth_child(0).unwrap().get_text(), "H");
+    assert!(dom.filter("tr").tag[0].nth_child(5).is_none());
+    assert_eq!(dom.filter("td:unknown").tag.len(), 0);
+    assert_eq!(dom.filter("td:nth-child(n-9223372036854775808)").tag.len(), 0);
+    assert_eq!(dom.filter("td:nth-child(-n+9223372036854775807)").tag.len(), 4);
+    assert_eq!(dom.filter("td:nth-last-child(-9223372036854775808n-9223372036854775808)").tag.len(), 0);
+    assert_eq!(dom.filter("td:nth-child(9223372036854775807n+3)").get_text(), "B");
+    assert_eq!(dom.filter("td:nth-child(n+99999999999999999999)").tag.len(), 0);
+}
+
+// Test filter_text method
+#[test]
+fn test_filter_text_method() {
+    let html = "<html><body><div><button>Add to cart</button><button>Buy now</button></div></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter("button").filter_text("Add to cart").tag.len(), 1);
+    assert_eq!(dom.filter("button").filter_text("now").get_text(), "Buy now");
+    assert_eq!(dom.filter_text("cart").tag.len(), 4);
+    assert_eq!(dom.filter("button").filter_text("checkout").tag.len(), 0);
+
+    let dom = parse_html("<html><body><p>é<b>ü</b></p><p>Grüße</p></body></html>").unwrap();
+
+    assert_eq!(dom.filter("p").filter_text("ü").tag.len(), 2);
+    assert_eq!(dom.filter("p").filter_text("éü").get_text(), "éü");
+    assert_eq!(dom.filter("b").filter_text("é").tag.len(), 0);
+}
+
+// Test exclude method
+#[test]
+fn test_exclude_method() {
+    let html = r#"<html><body><div>First</div><div class="ad">Ad</div><div id="x">Second</div></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter("div").exclude(("div", "class", "ad")).get_text(), "FirstSecond");
+    assert_eq!(dom.filter("div").exclude(".ad").exclude("#x").get_text(), "First");
+    assert_eq!(dom.filter("div").exclude("span").tag.len(), 3);
+    assert_eq!(dom.filter("div").exclude("div").tag.len(), 0);
+}
+
+// Test filter_attrs method
+#[test]
+fn test_filter_attrs_method() {
+    let html = r#"<html><body><form><input type="hidden" name="csrf" value="abc"><input type="hidden" name="page" value="2"><input type="text" name="csrf" value="no"></form></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter_attrs("input", &[("type", "hidden"), ("name", "csrf")]).get_attr_value("value"), "abc");
+    assert_eq!(dom.filter_attrs("input", &[("type", "hidden")]).tag.len(), 2);
+    assert_eq!(dom.filter_attrs("*", &[("name", "csrf"), ("value", "*")]).tag.len(), 2);
+    assert_eq!(dom.filter_attrs("input", &[("type", "hidden"), ("name", "missing")]).tag.len(), 0);
+
+    let html = r#"<html><body><INPUT TYPE="Hidden" Name="csrf" value="abc"><input type="hidden" name="page"></body></html>"#;
+
+    let dom = parse_html_with(html, ParseOptions{lowercase_tagnames: false, ..Default::default()}).unwrap();
+    let options = FilterOptions{case_insensitive: true, case_insensitive_values: true};
+
+    assert_eq!(dom.filter_attrs("*", &[("type", "*"), ("NAME", "csrf")]).get_attr_value("value"), "abc");
+    assert_eq!(dom.filter_attrs("input", &[("type", "hidden")]).tag.len(), 1);
+    assert_eq!(dom.filter_attrs_with("input", &[("type", "hidden")], options).tag.len(), 2);
+    assert_eq!(dom.filter_attrs_with("input", &[("type", "hidden"), ("name", "CSRF")], options).get_attr_value("value"), "abc");
+    assert_eq!(dom.filter_attrs_with("input", &[("type", "hidden")], FilterOptions{case_insensitive: true, ..Default::default()}).tag.len(), 1);
+}
+
+// Test case insensitive filtering
+#[test]
+fn test_case_insensitive_filter() {
+    let html = r#"<html><body><DIV ID="a">Upper</DIV><Div Class="x">Mixed</Div><div id="b">Lower</div></body></html>"#;
+
+    let dom = parse_html_with(html, ParseOptions{lowercase_tagnames: false, ..Default::default()}).unwrap();
+    let options = FilterOptions{case_insensitive: true, ..Default::default()};
+
+    assert_eq!(dom.filter("div").tag.len(), 1);
+    assert_eq!(dom.filter_with("div", options).tag.len(), 3);
+    assert_eq!(dom.filter_with(("div", "id"), options).get_text(), "UpperLower");
+    assert_eq!(dom.filter_with("div.x", options).get_text(), "Mixed");
+    assert_eq!(dom.filter_with("DIV[id=b]", options).get_text(), "Lower");
+    assert_eq!(dom.filter_with("div[id=B]", options).tag.len(), 0);
+}
+
+// Test filtering by multiple tag-names
+#[test]
+fn test_multiple_tag_names() {
+    let html = "<html><body><h2>B</h2><p>x</p><h1>A</h1><h3>C</h3><h2>D</h2></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter(["h1", "h2", "h3"]).get_text(), "BACD");
+    assert_eq!(dom.filter(["h3", "p"]).get_text(), "xC");
+    let names: Vec<&'static str> = vec!["h1", "h3"];
+    assert_eq!(dom.filter(&names[..]).get_text(), "AC");
+    assert_eq!(dom.filter(["h1", "h2"]).filter("h1").get_text(), "A");
+    assert_eq!(dom.filter(["h1", ""]).get_text(), "A");
+    let names: Vec<&'static str> = vec!["", "h3", ""];
+    assert_eq!(dom.filter(&names[..]).get_text(), "C");
+    assert_eq!(dom.count(["p", ""]), 1);
+    assert_eq!(dom.filter([""]).len(), dom.len());
+}
+
+// Test descendant combinator
+#[test]
+fn test_descendant_combinator() {
+    let html = r#"<html><body><table class="prices"><tr><td>1</td><td><span>2</span></td></tr></table><div><td>3</td><span>4</span></div></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter("table td").get_text(), "12");
+    assert_eq!(dom.filter("table.prices span").get_text(), "2");
+    assert_eq!(dom.filter("body  div   span").get_text(), "4");
+    assert_eq!(dom.filter("html table tr td:nth-child(2) span").get_text(), "2");
+    assert_eq!(dom.filter("div table").tag.len(), 0);
+}
+
+// Test child combinator
+#[test]
+fn test_child_combinator() {
+    let html = "<html><body><ul id='list'><li><b>One</b></li><li><span><b>Two</b></span></li></ul></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter("li > b").get_text(), "One");
+    assert_eq!(dom.filter("li b").tag.len(), 2);
+    assert_eq!(dom.filter("#list>li>span>b").get_text(), "Two");
+    assert_eq!(dom.filter("body > ul li > span b").get_text(), "Two");
+    assert_eq!(dom.filter("html > ul").tag.len(), 0);
+    assert_eq!(dom.filter("> li").tag.len(), 0);
+    assert_eq!(dom.filter("ul > > li").tag.len(), 0);
+}
+
+// Test sibling combinators
+#[test]
+fn test_sibling_combinators() {
+    let html = "<html><body><h2>Title</h2><p>First</p><p>Second</p><div><p>Inner</p></div><p>Third</p></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter("h2 + p").get_text(), "First");
+    assert_eq!(dom.filter("h2 ~ p").get_text(), "FirstSecondThird");
+    assert_eq!(dom.filter("h2~p+p").get_text(), "Second");
+    assert_eq!(dom.filter("div + p").get_text(), "Third");
+    assert_eq!(dom.filter("p + h2").tag.len(), 0);
+    assert_eq!(dom.filter("p:nth-child(2n+2)").get_text(), "First");
+}
+
+// Test filter builder
+#[test]
+fn test_filter_builder() {
+    let html = r#"<html><body><div class="post featured" data-id="1">One</div><div class="post">Two</div><div id="x" class="post" data-id="3">Three</div></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    let filter = Filter::tag("div").class("post").attr("data-id").build();
+
+    assert_eq!(dom.filter(&filter).get_text(), "OneThree");
+    assert_eq!(dom.filter(Filter::builder().class("featured").build()).get_text(), "One");
+    assert_eq!(dom.filter(Filter::tag("div").id("x").build()).get_text(), "Three");
+    assert_eq!(dom.filter(Filter::tag("div").attr_value("data-id", "3").build()).get_text(), "Three");
+    assert_eq!(dom.filter(Filter::tag("span").build()).tag.len(), 0);
+}
+
+// Test precompiled selector
+#[test]
+fn test_precompiled_selector() {
+    let selector = Selector::parse("div.item a").unwrap();
+
+    let first = parse_html("<html><body><div class='item'><a>One</a></div><a>No</a></body></html>").unwrap();
+    let second = parse_html("<html><body><div class='item'><p><a>Two</a></p></div></body></html>").unwrap();
+
+    assert_eq!(first.filter(&selector).get_text(), "One");
+    assert_eq!(second.filter(&selector).get_text(), "Two");
+    assert_eq!(first.filter(Selector::parse("div").unwrap()).tag.len(), 1);
+
+    assert!(Selector::parse("div >").is_err());
+    assert!(Selector::parse("a[href").is_err());
+}
+
+// Test first and last child
+#[test]
+fn test_first_last_child() {
+    let html = "<html><body><article><h1>Title</h1><p>First</p><p>Second</p></article><ul><li>Only</li></ul></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter("article p:first-child").get_text(), "");
+    assert_eq!(dom.filter("article p:first-of-type").get_text(), "First");
+    assert_eq!(dom.filter("article :first-child").get_text(), "Title");
+    assert_eq!(dom.filter("article p:last-child").get_text(), "Second");
+    assert_eq!(dom.filter("li:only-child").get_text(), "Only");
+    assert_eq!(dom.filter("p:only-of-type").tag.len(), 0);
+    assert_eq!(dom.filter("h1:only-of-type").get_text(), "Title");
+}
+
+// Test boolean attributes
+#[test]
+fn test_boolean_attributes() {
+    let html = r#"<html><body><input name="a" required><input name="b" disabled/><input name="c" value="x"><script async src="x.js"></script></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter(("input", "required")).tag.len(), 1);
+    assert_eq!(dom.filter(("input", "disabled")).tag.len(), 1);
+    assert_eq!(dom.filter(("", "async")).tag.len(), 1);
+    assert_eq!(dom.filter("input[required]").tag.len(), 1);
+    assert_eq!(dom.filter(("input", "hidden")).tag.len(), 0);
+    assert_eq!(dom.filter_with(("input", "REQUIRED"), FilterOptions{case_insensitive: true, ..Default::default()}).tag.len(), 1);
+}
+
+// Test filter_fn method
+#[test]
+fn test_filter_fn_method() {
+    let html = r#"<html><body><div><span data-price="5">Cheap</span><span data-price="50">Expensive</span><span>Free</span></div></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    let filtered_dom = dom.filter_fn(|tag| tag.get_attr_value("data-price").parse::<u32>().is_ok_and(|p| p > 10));
+    assert_eq!(filtered_dom.get_text(), "Expensive");
+
+    let filtered_dom = dom.filter("div span").filter_fn(|tag| tag.get_text().starts_with("Fr"));
+    assert_eq!(filtered_dom.get_text(), "Free");
+
+    assert_eq!(dom.filter("div").filter_fn(|tag| tag.get_tagname() == "span").tag.len(), 0);
+    assert_eq!(dom.filter("div").filter_fn(|tag| tag.get_text().contains("Free")).tag.len(), 1);
+    assert_eq!(dom.filter("span").filter_fn(|tag| tag.get_text().len() > 4).nth(1).get_text(), "Expensive");
+
+    assert_eq!(dom.filter_fn(|_| false).tag.len(), 0);
+}
+
+// Test find method
+#[test]
+fn test_find_method() {
+    let html = r#"<html><body><div><a href="/one">One</a><a href="/two">Two</a></div></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.find("a").unwrap().get_attr_value("href"), "/one");
+    assert_eq!(dom.find(("a", "href", "/two")).unwrap().get_text(), "Two");
+    assert_eq!(dom.filter("div").find("a:last-child").unwrap().get_text(), "Two");
+    assert!(dom.find("span").is_none());
+    assert!(parse_html("<html></html>").unwrap().filter("p").find("a").is_none());
+
+    assert!(std::ptr::eq(dom.find("a:last-child").unwrap(), &dom.tag[4]));
+    assert!(std::ptr::eq(dom.find("").unwrap(), &dom.tag[0]));
+}
+
+// Test extract method
+#[test]
+fn test_extract_method() {
+    let html = r#"<html><body><a href="/one">One</a><a>None</a><a href="/two">Two</a><p data-n="3">x</p></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    let hrefs: Vec<String> = dom.extract(("a", "href"), |tag| Some(tag.get_attr_value("href")));
+    assert_eq!(hrefs, vec!["/one", "/two"]);
+
+    let numbers: Vec<u32> = dom.extract("*", |tag| tag.get_attr_value("data-n").parse().ok());
+    assert_eq!(numbers, vec![3]);
+
+    let texts: Vec<String> = dom.extract("span", |tag| Some(tag.get_text()));
+    assert!(texts.is_empty());
+}
+
+// Test glob patterns for attribute-values
+#[test]
+fn test_glob_attribute_values() {
+    let html = r#"<html><body><a href="/shop/product/42" title="x">A</a><a href="/shop/about">B</a><a href="/product">C</a><img src="/img/cat.jpg"></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter(("a", "href", "*/product/*")).get_text(), "A");
+    assert_eq!(dom.filter(("a", "href", "/shop/*")).get_text(), "AB");
+    assert_eq!(dom.filter(("a", "href", "*product*")).get_text(), "AC");
+    assert_eq!(dom.filter(("a", "title", "*product*")).tag.len(), 0);
+    assert_eq!(dom.filter(("", "", "*.jpg")).tag.len(), 1);
+    assert_eq!(dom.filter(("a", "href", "/*/*/*")).get_text(), "A");
+}
+
+// Test depth
+#[test]
+fn test_depth() {
+    let html = "<html><body><div class='wrapper'><div>Content<br><div>Inner</div></div></div></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let divs = dom.filter("div");
+    assert_eq!(divs.tag.iter().map(|t| t.get_depth()).collect::<Vec<usize>>(), vec![2, 3, 4]);
+    assert_eq!(divs.at_depth(4).get_text(), "Inner");
+    assert_eq!(divs.at_depth(2).tag.len(), 1);
+    assert_eq!(divs.at_depth(7).tag.len(), 0);
+    assert_eq!(dom.filter("html").tag[0].get_depth(), 0);
+    assert_eq!(divs.tag.iter().map(|t| t.depth()).collect::<Vec<usize>>(), vec![2, 3, 4]);
+    assert!(divs.tag.iter().all(|t| t.depth() == t.get_depth()));
+}
+
+// Test Tag::filter method
+#[test]
+fn test_tag_filter_method() {
+    let html = "<html><body><div class='post'><a>First</a><p><a>Nested</a></p></div><div class='post'><a>Second</a></div></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let posts = dom.filter("div.post");
+
+    assert_eq!(posts.tag[0].filter("a").get_text(), "FirstNested");
+    assert_eq!(posts.tag[1].filter("a").get_text(), "Second");
+    assert_eq!(posts.tag[0].filter("p a").get_text(), "Nested");
+    assert_eq!(posts.tag[0].filter("div").tag.len(), 0);
+    assert_eq!(posts.tag[0].filter("a").tag[0].get_depth(), 3);
+}
+
+// Test chained filters
+#[test]
+fn test_chained_filters() {
+    let html = "<html><body><article><a>One</a><article><a>Two</a></article></article><a>Outside</a></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let articles = dom.filter("article");
+    assert_eq!(articles.tag.len(), 2);
+    assert_eq!(articles.filter("a").get_text(), "OneTwo");
+    assert_eq!(articles.filter("body > article > a").get_text(), "One");
+    assert_eq!(articles.filter_text("Two").filter("a").get_text(), "OneTwo");
+    assert_eq!(articles.nth(1).filter("a").get_text(), "Two");
+    assert_eq!(articles.filter("a").xpath("//a").unwrap().get_text(), "OneTwo");
+    assert_eq!(dom.filter("body").filter("a").filter("a").get_text(), "OneTwoOutside");
+}
+
+// Test namespaces
+#[test]
+fn test_namespaces() {
+    let html = r#"<html><head><title>Page</title></head><body><svg viewBox="0 0 1 1"><title>Icon</title><path d="M0 0"/><g><circle r="1"/></g></svg><svg:svg><svg:path d="M1 1"/></svg:svg><p>Text</p></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter("path").tag.len(), 2);
+    assert_eq!(dom.filter("svg:path").tag.len(), 1);
+    assert_eq!(dom.filter("svg|path").tag.len(), 2);
+    assert_eq!(dom.filter("svg|*").tag.len(), 7);
+    assert_eq!(dom.filter("title").get_text(), "PageIcon");
+    assert_eq!(dom.filter("|title").get_text(), "Page");
+    assert_eq!(dom.filter("svg|title").get_text(), "Icon");
+    assert_eq!(dom.filter("g > circle").tag[0].get_namespace(), "svg");
+    assert_eq!(dom.filter("svg:path").tag[0].get_local_name(), "path");
+    assert_eq!(dom.filter("p").tag[0].get_namespace(), "");
+    assert_eq!(dom.filter("p").get_text(), "Text");
+    assert_eq!(dom.filter(Filter::tag("svg:path").build()).tag.len(), 1);
+}
+
+// Test count method
+#[test]
+fn test_count_method() {
+    let html = "<html><body><table><tr><td>1</td><td>2</td></tr><tr><td>3</td></tr></table><td>4</td></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.count("tr"), 2);
+    assert_eq!(dom.count("td"), 4);
+    assert_eq!(dom.count("table td"), 3);
+    assert_eq!(dom.filter("tr").count("td"), 3);
+    assert_eq!(dom.count("span"), 0);
+    assert_eq!(dom.filter("tr").count(""), 2);
+}
+
+// Test class tokens
+#[test]
+fn test_class_tokens() {
+    let html = r#"<html><body><li class="item active">One</li><li class="inactive">Two</li><li class="active">Three</li></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    let items = dom.filter("li");
+    assert!(items.tag[0].has_class("active"));
+    assert!(items.tag[0].has_class("item"));
+    assert!(!items.tag[1].has_class("active"));
+
+    assert_eq!(dom.filter(("li", "class", "active")).get_text(), "OneThree");
+    assert_eq!(dom.filter(("li", "class", "item active")).get_text(), "One");
+    assert_eq!(dom.filter(".active").get_text(), "OneThree");
+
+    let dom = parse_html(r#"<html><body><p class="a&#32;b&#9;c" class="d">Text</p></body></html>"#).unwrap();
+
+    let p = dom.find("p").unwrap();
+    assert!(p.has_class("b"));
+    assert!(p.has_class("c"));
+    assert!(!p.has_class("d"));
+    assert_eq!(p.has_class("d"), p.get_attr_value("class").split_whitespace().any(|c| c == "d"));
+}
+
+// Test contains pseudo-class
+#[test]
+fn test_contains_selector() {
+    let html = r#"<html><body><nav><button>Previous</button><button>Next (2)</button></nav><button>Next</button></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter(r#"button:contains("Next")"#).tag.len(), 2);
+    assert_eq!(dom.filter("nav button:contains('Next')").get_text(), "Next (2)");
+    assert_eq!(dom.filter("button:contains('Next (2)')").get_text(), "Next (2)");
+    assert_eq!(dom.filter("nav:contains(Previous) > button:first-child").get_text(), "Previous");
+    assert_eq!(dom.filter("button:contains('Last')").tag.len(), 0);
+
+    let dom = parse_html("<html><body><p>é<b>ü</b></p><p>Grüße</p></body></html>").unwrap();
+
+    assert_eq!(dom.filter("p:contains(ü)").tag.len(), 2);
+    assert_eq!(dom.filter("p:contains('éü')").get_text(), "éü");
+    assert_eq!(dom.xpath("//p[contains(., 'ü')]").unwrap().tag.len(), 2);
+    assert_eq!(dom.xpath("//p[contains(., 'Grü')]").unwrap().get_text(), "Grüße");
+    assert_eq!(dom.xpath("//p[string-length(.) = 2]").unwrap().get_text(), "éü");
+}
+
+// Test selector lists
+#[test]
+fn test_selector_list() {
+    let html = r#"<html><body><h2>B</h2><h1>A</h1><p class="title">C</p><div><h1>D</h1></div><a title="x,y">E</a></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter("h1, h2, .title").get_text(), "BACD");
+    assert_eq!(dom.filter("div h1,h2").get_text(), "BD");
+    assert_eq!(dom.filter("h1, h1").tag.len(), 2);
+    assert_eq!(dom.filter(r#"a[title="x,y"], p"#).get_text(), "CE");
+    assert_eq!(dom.filter("h1,").tag.len(), 0);
+    assert!(Selector::parse("h1, , h2").is_err());
+}
+
+// Test case-insensitive attribute-values
+#[test]
+fn test_case_insensitive_values() {
+    let html = r#"<html><body><meta charset="UTF-8"><input type="TEXT" class="Big"><input type="text"><a data-flag="True">A</a></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    let options = FilterOptions{case_insensitive_values: true, ..Default::default()};
+
+    assert_eq!(dom.filter("meta[charset=utf-8]").tag.len(), 0);
+    assert_eq!(dom.filter_with("meta[charset=utf-8]", options).tag.len(), 1);
+    assert_eq!(dom.filter(r#"input[type="text" i]"#).tag.len(), 2);
+    assert_eq!(dom.filter(r#"input[type="text"]"#).tag.len(), 1);
+    assert_eq!(dom.filter_with(("a", "data-flag", "true"), options).get_text(), "A");
+    assert_eq!(dom.filter(("a", "data-flag", "true")).tag.len(), 0);
+    assert_eq!(dom.filter_with(".big", options).tag.len(), 1);
+    assert_eq!(dom.filter_with(("input", "class", "big"), options).tag.len(), 1);
+}
+
+// Test query_selector methods
+#[test]
+fn test_query_selector() {
+    let html = "<html><body><div class='item'><a href='/one'>One</a></div><div class='item'><a href='/two'>Two</a></div><a>Three</a></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.query_selector("div.item > a").unwrap().get_text(), "One");
+    assert_eq!(dom.query_selector_all("a").len(), 3);
+    assert_eq!(dom.query_selector_all("div.item a").iter().map(|t| t.get_attr_value("href")).collect::<Vec<String>>(), vec!["/one", "/two"]);
+    assert!(dom.query_selector("span").is_none());
+    assert!(dom.query_selector("a[").is_none());
+
+    let items = dom.filter(".item");
+    assert_eq!(items.query_selector_all("a").len(), 2);
+    assert_eq!(items.query_selector("body > div:last-of-type a").unwrap().get_text(), "Two");
+}
+
+// Test Tag::matches method
+#[test]
+fn test_tag_matches_method() {
+    let html = r#"<html><body><a class="next" href="/2">Next</a><a href="/1">Back</a></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    let links = dom.filter("a");
+
+    assert!(links.tag[0].matches("a.next"));
+    assert!(links.tag[0].matches(("a", "href", "/2")));
+    assert!(!links.tag[1].matches("a.next"));
+    assert!(links.tag[1].matches(["span", "a"]));
+    assert!(links.tag[1].matches(Filter::tag("a").attr("href").build()));
+    assert!(links.tag[0].matches("body > a"));
+    assert!(!links.tag[0].matches("div a"));
+}
+
+// Test closest method
+#[test]
+fn test_closest_method() {
+    let html = r#"<html><body><div class="card" data-sku="1"><p><span class="price">1.00</span></p><span class="price">1.50</span></div><div class="card" data-sku="2"><span class="price">2.00</span></div><span class="price">0</span></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    let cards = dom.filter("span.price").closest("div.card");
+    assert_eq!(cards.tag.len(), 2);
+    assert_eq!(cards.tag[0].get_attr_value("data-sku"), "1");
+    assert_eq!(cards.tag[1].get_attr_value("data-sku"), "2");
+
+    assert_eq!(dom.filter("span.price").closest("span").tag.len(), 4);
+    assert_eq!(dom.filter("span.price").closest("p").tag.len(), 1);
+    assert_eq!(dom.filter("span.price").closest("table").tag.len(), 0);
+
+    let price = dom.find("p span.price").unwrap();
+    assert_eq!(price.closest("div.card").unwrap().get_attr_value("data-sku"), "1");
+    assert_eq!(price.closest("p").unwrap().get_tagname(), "p");
+    assert_eq!(price.closest(".price").unwrap(), *price);
+    assert_eq!(price.closest(("div", "data-sku", "1")).unwrap().get_tagname(), "div");
+    assert!(price.closest("table").is_none());
+    assert!(dom.filter("span.price").tag[3].closest("div").is_none());
+}
+
+// Test document tree
+#[test]
+fn test_document_tree() {
+    let html = "<html><body><div id='a'><div id='b'>Inner</div><p>One<br>Two</p></div><div id='c'></div></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let outer = dom.filter("div#a");
+
+    assert_eq!(outer.tag[0].to_string(), "<div id='a'><div id='b'>Inner</div><p>One<br>Two</p></div>");
+    assert_eq!(outer.tag[0].get_inner_html(), "<div id='b'>Inner</div><p>One<br>Two</p>");
+    assert_eq!(outer.tag[0].nth_child(1).unwrap().get_tagname(), "p");
+    assert_eq!(outer.filter("div").tag.len(), 2);
+    assert_eq!(outer.filter("br").tag[0].to_string(), "<br>");
+    assert!(outer.filter("p").tag[0].matches("div#a > p"));
+
+    let dom = parse_html("<div><p>Unclosed</div><span>After</span>").unwrap();
+
+    assert_eq!(dom.filter("p").get_inner_html(), "Unclosed");
+    assert_eq!(dom.filter("span").tag[0].get_depth(), 0);
+}
+
+// Test children method
+#[test]
+fn test_children_method() {
+    let html = "<html><body><table><tr><th>Name</th><td>Value <b>bold</b></td></tr><tr></tr></table></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let rows = dom.filter("tr");
+
+    assert_eq!(rows.tag[0].children().map(|t| t.get_tagname()).collect::<Vec<String>>(), vec!["th", "td"]);
+    assert_eq!(rows.tag[0].children().nth(1).unwrap().children().count(), 1);
+    assert_eq!(rows.tag[1].children().count(), 0);
+    assert_eq!(dom.filter("table").tag[0].children().count(), 2);
+}
+
+// Test parent method
+#[test]
+fn test_parent_method() {
+    let html = "<html><body><div data-sku='42'><p><span class='price'>9.99</span></p></div></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let price = dom.find("span.price").unwrap();
+
+    assert_eq!(price.parent().unwrap().get_tagname(), "p");
+    assert_eq!(price.parent().unwrap().parent().unwrap().get_attr_value("data-sku"), "42");
+    assert!(dom.find("html").unwrap().parent().is_none());
+}
+
+// Test sibling navigation
+#[test]
+fn test_siblings() {
+    let html = "<html><body><table><tr><th>Name</th><td>Widget</td></tr><tr><th>Price</th><td>9.99</td></tr></table></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let labels = dom.filter("th");
+
+    assert_eq!(labels.tag[1].next_sibling().unwrap().get_text(), "9.99");
+    assert!(labels.tag[1].prev_sibling().is_none());
+    assert!(labels.tag[1].next_sibling().unwrap().next_sibling().is_none());
+    assert_eq!(dom.find("td").unwrap().prev_sibling().unwrap().get_text(), "Name");
+    assert_eq!(dom.filter("tr").tag[0].next_sibling().unwrap().children().count(), 2);
+}
+
+// Test nodes method
+#[test]
+fn test_nodes_method() {
+    let html = "<html><body><p>Price: <b>$5</b><!-- <b>hidden</b> --> only today</p><div></div></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let nodes = dom.find("p").unwrap().nodes();
+
+    assert_eq!(nodes.len(), 4);
+    assert!(matches!(&nodes[0], Node::Text(t) if t == "Price: "));
+    assert!(matches!(&nodes[1], Node::Element(t) if t.get_text() == "$5"));
+    assert!(matches!(&nodes[2], Node::Comment(c) if c == " <b>hidden</b> "));
+    assert!(matches!(&nodes[3], Node::Text(t) if t == " only today"));
+    assert_eq!(dom.filter("b").tag.len(), 1);
+    assert!(dom.find("div").unwrap().nodes().is_empty());
+}
+
+// Test comments method
+#[test]
+fn test_comments_method() {
+    let html = "<!-- before --><html><body><p>Hello <!-- <b>hidden</b> -->World</p><div><!--{\"page\": 2}--></div></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.comments(), vec![" before ", " <b>hidden</b> ", "{\"page\": 2}"]);
+    assert_eq!(dom.filter("div").comments(), vec!["{\"page\": 2}"]);
+    assert_eq!(dom.filter("b").tag.len(), 0);
+    assert_eq!(dom.filter("p").get_text(), "Hello World");
+}
+
+// Test document metadata
+#[test]
+fn test_document_metadata() {
+    let dom = parse_html("<!doctype html><html><head><meta charset='utf-8'></head><body><p>Hi</p></body></html>").unwrap();
+
+    assert_eq!(dom.get_doctype(), Some("html".to_string()));
+    assert!(!dom.is_quirks_mode());
+    assert_eq!(dom.get_charset(), Some("utf-8".to_string()));
+    assert_eq!(dom.filter("p").get_charset(), Some("utf-8".to_string()));
+
+    let dom = parse_html(r#"<!DOCTYPE HTML PUBLIC "-//W3C//DTD HTML 4.01 Transitional//EN" "http://www.w3.org/TR/html4/loose.dtd"><html></html>"#).unwrap();
+
+    assert!(!dom.is_quirks_mode());
+    assert_eq!(dom.get_charset(), None);
+
+    let dom = parse_html(r#"<!DOCTYPE html PUBLIC "-//W3C//DTD HTML 3.2 Final//EN"><html></html>"#).unwrap();
+
+    assert!(dom.is_quirks_mode());
+
+    let dom = parse_html("<html><body></body></html>").unwrap();
+
+    assert_eq!(dom.get_doctype(), None);
+    assert!(dom.is_quirks_mode());
+}
+
+// Test span method
+#[test]
+fn test_span_method() {
+    let html = "<html><body><p>One <b>two</b></p><br><i>three</body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    for tag in &dom.tag {
+        assert_eq!(&html[tag.span()], tag.to_string());
+    }
+
+    assert_eq!(&html[dom.find("b").unwrap().span()], "<b>two</b>");
+    assert_eq!(&html[dom.find("br").unwrap().span()], "<br>");
+    assert_eq!(&html[dom.find("i").unwrap().span()], "<i>three");
+}
+
+// Test position method
+#[test]
+fn test_position_method() {
+    let html = "<html><body>\n<p>Grüße</p><b>x</b>\r\n\t<i>y</i>\n</body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.find("html").unwrap().position(), (1, 1));
+    assert_eq!(dom.find("p").unwrap().position(), (2, 1));
+    assert_eq!(dom.find("b").unwrap().position(), (2, 13));
+    assert_eq!(dom.find("i").unwrap().position(), (3, 2));
+}
+
+// Test node ids
+#[test]
+fn test_node_ids() {
+    let html = "<html><body><ul><li>One</li><li>Two</li></ul><p>Text</p></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let ids: Vec<NodeId> = dom.filter("li").tag.iter().map(|t| t.node_id()).collect();
+
+    assert_eq!(ids, dom.filter("ul").filter("li").tag.iter().map(|t| t.node_id()).collect::<Vec<NodeId>>());
+    assert_eq!(dom.filter("p").node(ids[1]).unwrap().get_text(), "Two");
+    assert_ne!(ids[0], ids[1]);
+    assert!(dom.node(NodeId(ids[0].0, 100)).is_none());
+    assert!(dom.filter("table").node(ids[0]).is_none());
+
+    let other = parse_html(html).unwrap();
+    assert!(other.node(ids[0]).is_none());
+    assert!(other.filter("li").node(ids[1]).is_none());
+    assert_ne!(other.filter("li").tag[0].node_id(), ids[0]);
+    assert_eq!(other.node(other.filter("li").tag[1].node_id()).unwrap().get_text(), "Two");
+}
+
+// Test parse_events function
+#[test]
+fn test_parse_events() {
+    let html = "<html><body><p class='a'>One<br>Two</p><!-- note --><ul><li>Open</ul></body></html>";
+
+    let mut events: Vec<String> = vec![];
+
+    parse_events(html, |event| events.push(match event {
+        Event::StartTag{name, attrs} => format!("<{}{}>", name, attrs.iter().map(|(n, v)| format!(" {}={}", n, v)).collect::<String>()),
+        Event::EndTag{name} => format!("</{}>", name),
+        Event::Text(text) => text.to_string(),
+        Event::Comment(comment) => format!("#{}", comment.trim()),
+    }));
+
+    assert_eq!(events.concat(), "<html><body><p class=a>One<br></br>Two</p>#note<ul><li>Open</li></ul></body></html>");
+}
+
+// Test parsing html in chunks
+#[test]
+fn test_chunked_parser() {
+    let html = "<!DOCTYPE html><html><body><!-- a <b>comment</b> --><p class='x'>Grüße <b>one</b></p><ul><li>Two</ul><br/></body></html>";
+
+    for size in 1..html.len() {
+        let mut parser = Parser::new();
+        for chunk in html.as_bytes().chunks(size) {
+            parser.feed(chunk);
+        }
+        let dom = parser.finish().unwrap();
+        let expected = parse_html(html).unwrap();
+        assert_eq!(dom.tag.iter().map(|t| t.span()).collect::<Vec<std::ops::Range<usize>>>(), expected.tag.iter().map(|t| t.span()).collect::<Vec<std::ops::Range<usize>>>());
+        assert_eq!(dom.comments(), expected.comments());
+        assert_eq!(dom.get_doctype(), Some("html".to_string()));
+        assert_eq!(dom.filter("p").get_inner_html(), "Grüße <b>one</b>");
+    }
+
+    let mut parser = Parser::new();
+    parser.feed(b"<p>\xff</p>");
+    assert_eq!(parser.finish().unwrap().filter("p").get_text(), "\u{fffd}");
+
+    assert!(Parser::new().finish().is_err());
+}
+
+// Test tokenizer
+#[test]
+fn test_tokenizer() {
+    use tokenizer::Token;
+
+    let html = "<!DOCTYPE html><p id=a>1 < 2<custom-tag x/></p><!--c--></div>";
+
+    let tokens: Vec<Token> = tokenizer::tokenize(html).collect();
+
+    assert_eq!(parse_html(html).unwrap().find("p").unwrap().get_inner_html(), "1 < 2<custom-tag x/>");
+
+    assert_eq!(tokens, vec![
+        Token::Doctype{text: "html", span: 0..15},
+        Token::StartTag{name: "p", attrs: vec![("id".to_string(), "a".to_string())], self_closing: false, span: 15..23},
+        Token::Text{text: "1 < 2", span: 23..28},
+        Token::StartTag{name: "custom-tag", attrs: vec![("x".to_string(), "".to_string())], self_closing: true, span: 28..43},
+        Token::EndTag{name: "p", span: 43..47},
+        Token::Comment{text: "c", span: 47..55},
+        Token::EndTag{name: "div", span: 55..61},
+    ]);
+
+    assert_eq!(tokenizer::tokenize("no tags").collect::<Vec<Token>>(), vec![Token::Text{text: "no tags", span: 0..7}]);
+    assert_eq!(tokenizer::tokenize("").count(), 0);
+}
+
+// Test warnings
+#[test]
+fn test_warnings() {
+
+    let html = "<html><body>\n<div><p>Hello World!</div></span>\n<a href=\"/x\"class='y'>Link</a><br title=\"a\" title=\"b\"></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let warnings = dom.warnings();
+
+    let kinds: Vec<WarningKind> = warnings.iter().map(|w| w.kind).collect();
+    assert_eq!(kinds, vec![WarningKind::UnclosedTag, WarningKind::MismatchedClosingTag, WarningKind::StrayClosingTag, WarningKind::InvalidAttributes, WarningKind::InvalidAttributes]);
+
+    assert_eq!(warnings[0].tagname, "p");
+    assert_eq!(warnings[0].position, (2, 6));
+    assert_eq!(&html[warnings[0].span.clone()], "<p>");
+    assert_eq!(warnings[2].to_string(), "stray closing tag </span> at line 2, column 27");
+    assert_eq!(warnings[4].tagname, "br");
+
+    assert_eq!(dom.filter("div").warnings().len(), 2);
+
+    assert!(parse_html("<html><body><a href='/' class=\"x\">Link</a><br/><img src=a.png /></body></html>").unwrap().warnings().is_empty());
+
+    let unclosed = parse_html("<div><span>Text").unwrap().warnings();
+    assert_eq!(unclosed.iter().map(|w| w.tagname.as_str()).collect::<Vec<&str>>(), vec!["div", "span"]);
+}
+
+// Test strict parsing
+#[test]
+fn test_parse_html_strict() {
+
+    let dom = parse_html_strict("<html><body><div class='a'>Hello World!</div><br></body></html>").unwrap();
+    assert_eq!(dom.filter("div").get_text(), "Hello World!");
+
+    let e = parse_html_strict("<html><body><div><p>Hello World!</div></body></html>").err().unwrap();
+    assert_eq!(e.kind(), ErrorKind::InvalidData);
+    assert_eq!(e.to_string(), "An error has occurred when trying to parse the html-string! (unclosed tag <p> at line 1, column 18; mismatched closing tag </div> at line 1, column 33)");
+
+    assert_eq!(parse_html_strict("Hello World!").err().unwrap().kind(), ErrorKind::InvalidInput);
+}
+
+// Test void tags
+#[test]
+fn test_void_tags() {
+
+    let html = "<html><body><p>One<img src='a.png'>Two</p><div><input type=text><hr/><span>Three</span></div><p>Four</img>Five</p></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter("img").tag[0].get_inner_html(), "");
+    assert_eq!(dom.filter("img").tag[0].to_string(), "<img src='a.png'>");
+    assert_eq!(dom.filter("div").tag[0].get_inner_html(), "<input type=text><hr/><span>Three</span>");
+    assert_eq!(dom.filter("span").tag[0].get_inner_html(), "Three");
+    assert_eq!(dom.filter("input").tag[0].children().count(), 0);
+    assert_eq!(dom.filter("p").tag.len(), 2);
+    assert!(!dom.filter("p").tag[1].get_text().contains("</img>"));
+}
+
+// Test raw text
+#[test]
+fn test_raw_text() {
+
+    let script = "if (a < b && c > d) { document.write(\"<div><p>\"); } // </scripts>";
+    let html = format!("<html><head><style>ul > li {{ color: red }}</style><script>{}</script></head><body><div>Content</div></body></html>", script);
+
+    let dom = parse_html(&html).unwrap();
+
+    assert_eq!(dom.filter("div").tag.len(), 1);
+    assert_eq!(dom.filter("p").tag.len(), 0);
+    assert_eq!(dom.filter("script").get_inner_html(), script);
+    assert_eq!(dom.filter("script").get_text(), script);
+    assert_eq!(dom.filter("style").get_text(), "ul > li { color: red }");
+    assert_eq!(dom.filter("body").get_text(), "Content");
+    assert!(dom.warnings().is_empty());
+
+    let mut parser = Parser::new();
+    for chunk in html.as_bytes().chunks(7) {
+        parser.feed(chunk);
+    }
+    assert_eq!(parser.finish().unwrap().filter("script").get_inner_html(), script);
+
+    let tokens: Vec<tokenizer::Token> = tokenizer::tokenize("<script>a<b</SCRIPT>").collect();
+    assert_eq!(tokens[1], tokenizer::Token::Text{text: "a<b", span: 8..11});
+    assert_eq!(tokens.len(), 3);
+}
+
+// Test entity decoding
+#[test]
+fn test_entity_decoding() {
+
+    let html = "<html><head><title>Tom &amp; Jerry</title></head><body><h1>Ben &amp; Jerry&#8217;s</h1><p>It&#39;s &lt;b&gt; &euro;5&nbsp;only &#x1F600; &#146; &unknown; &amp</p><script>a &amp;&amp; b</script></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter("h1").get_text(), "Ben & Jerry\u{2019}s");
+    assert_eq!(dom.filter("p").tag[0].get_text(), "It's <b> \u{20ac}5\u{a0}only \u{1F600} \u{2019} &unknown; &amp");
+    assert_eq!(dom.filter("title").get_text(), "Tom & Jerry");
+    assert_eq!(dom.filter("script").get_text(), "a &amp;&amp; b");
+    assert_eq!(dom.filter("h1").get_inner_html(), "Ben &amp; Jerry&#8217;s");
+}
+
+// Test entity options
+#[test]
+fn test_entity_options() {
+
+    let html = "<html><body><a href='/?a=1&amp;b=2' title=\"Tom &amp; Jerry\">Tom &amp; Jerry</a></body></html>";
+
+    let dom = parse_html(html).unwrap();
+    assert_eq!(dom.filter("a").get_text(), "Tom & Jerry");
+    assert_eq!(dom.filter("a").tag[0].get_attr_value("href"), "/?a=1&b=2");
+    assert_eq!(dom.xpath("//a[@title='Tom & Jerry']").unwrap().tag.len(), 1);
+
+    let raw = parse_html_with(html, ParseOptions{decode_text: false, decode_attributes: false, ..Default::default()}).unwrap();
+    assert_eq!(raw.filter("a").get_text(), "Tom &amp; Jerry");
+    assert_eq!(raw.filter("a").tag[0].get_attr_value("href"), "/?a=1&amp;b=2");
+
+    let mut parser = Parser::with_options(ParseOptions{decode_text: false, ..Default::default()});
+    parser.feed(html.as_bytes());
+    let dom = parser.finish().unwrap();
+    assert_eq!(dom.filter("a").get_text(), "Tom &amp; Jerry");
+    assert_eq!(dom.filter("a").get_attr_value("href"), "/?a=1&b=2");
+
+    assert_eq!(entities::encode("<p class=\"a\">Tom & Jerry's</p>"), "&lt;p class=&quot;a&quot;&gt;Tom &amp; Jerry&#39;s&lt;/p&gt;");
+    assert_eq!(entities::decode(&entities::encode("<p>Tom & Jerry</p>")), "<p>Tom & Jerry</p>");
+}
+
+// Test get_attributes method
+#[test]
+fn test_get_attributes_method() {
+
+    let html = "<html><body><a href='/?a=1&amp;b=2' data-id = \"42\" data-tags=x,y download href='/other'>Link</a><p>Text</p></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let attrs = dom.filter("a").tag[0].get_attributes();
+    assert_eq!(attrs, vec![
+        ("href".to_string(), "/?a=1&b=2".to_string()),
+        ("data-id".to_string(), "42".to_string()),
+        ("data-tags".to_string(), "x,y".to_string()),
+        ("download".to_string(), "".to_string()),
+    ]);
+
+    let data: Vec<&str> = attrs.iter().filter(|(n, _)| n.starts_with("data-")).map(|(_, v)| v.as_str()).collect();
+    assert_eq!(data, vec!["42", "x,y"]);
+
+    assert!(dom.filter("p").tag[0].get_attributes().is_empty());
+}
+
+// Test try_get_attr_value method
+#[test]
+fn test_try_get_attr_value_method() {
+
+    let html = "<html><body><img src='a.png' alt=''><img src='b.png' ALT='Logo'><input disabled><img src='c.png'></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let images = dom.filter("img");
+
+    assert_eq!(images.tag[0].try_get_attr_value("alt"), Some("".to_string()));
+    assert_eq!(images.tag[1].try_get_attr_value("alt"), Some("Logo".to_string()));
+    assert_eq!(images.tag[2].try_get_attr_value("alt"), None);
+    assert_eq!(images.tag[2].get_attr_value("alt"), "");
+    assert_eq!(dom.filter("input").tag[0].try_get_attr_value("disabled"), Some("".to_string()));
+}
+
+// Test attribute values
+#[test]
+fn test_attribute_values() {
+
+    let html = r#"<html><body><a href=/foo?a=b class = 'link main' title="a > b" data-x = "1=2">Link</a><div style="width:10px" hidden>Box</div></body></html>"#;
+
+    let dom = parse_html(html).unwrap();
+
+    let link = &dom.filter("a").tag[0];
+    assert_eq!(link.get_attr_value("href"), "/foo?a=b");
+    assert_eq!(link.get_attr_value("class"), "link main");
+    assert_eq!(link.get_attr_value("title"), "a > b");
+    assert_eq!(link.get_attr_value("data-x"), "1=2");
+    assert_eq!(link.get_inner_html(), "Link");
+    assert_eq!(dom.filter("div").tag[0].get_attr_value("style"), "width:10px");
+
+    assert_eq!(dom.filter(("a", "href", "/foo?a=b")).tag.len(), 1);
+    assert_eq!(dom.filter(("a", "class", "main")).tag.len(), 1);
+    assert_eq!(dom.filter(("a", "title", "a > b")).tag.len(), 1);
+    assert_eq!(dom.filter(("div", "hidden", "")).tag.len(), 1);
+    assert_eq!(dom.filter(("div", "style", "width")).tag.len(), 0);
+    assert_eq!(dom.filter("a[data-x='1=2']").tag.len(), 1);
+    assert!(dom.warnings().iter().all(|w| w.tagname == "a"));
+}
+
+// Test tag-name normalization
+#[test]
+fn test_lowercase_tagnames() {
+
+    let html = "<HTML><Body><IMG src='a.png'><img src='b.png'><svg><linearGradient id='g'></linearGradient></svg><P>Text</p></Body></HTML>";
+
+    let dom = parse_html(html).unwrap();
+    assert_eq!(dom.filter("img").tag.len(), 2);
+    assert_eq!(dom.filter("img").tag[0].get_tagname(), "img");
+    assert_eq!(dom.filter("p").get_text(), "Text");
+    assert_eq!(dom.filter("linearGradient").tag.len(), 1);
+    assert_eq!(dom.filter("body").tag[0].to_string().len(), html.len() - "<HTML></HTML>".len());
+
+    let dom = parse_html_with(html, ParseOptions{lowercase_tagnames: false, ..Default::default()}).unwrap();
+    assert_eq!(dom.filter("img").tag.len(), 1);
+    assert_eq!(dom.filter("IMG").tag[0].get_tagname(), "IMG");
+}
+
+// Test to_html method
+#[test]
+fn test_to_html_method() {
+
+    let html = "<!DOCTYPE html>\n<HTML><body class=main data-x='say \"hi\"'><!-- note --><p id=a title='x'>One<br>Two<IMG src=a.png><p>Three</body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.to_html(), "<!DOCTYPE html>\n<html><body class=\"main\" data-x=\"say &quot;hi&quot;\"><!-- note --><p id=\"a\" title=\"x\">One<br/>Two<img src=\"a.png\"/><p>Three</p></p></body></html>");
+    assert_eq!(dom.filter("p").to_html(), "<p id=\"a\" title=\"x\">One<br/>Two<img src=\"a.png\"/><p>Three</p></p>");
+    assert_eq!(dom.filter("img").to_html(), "<img src=\"a.png\"/>");
+
+    let svg = parse_html("<div hidden><svg><path d='M0'/><g></g></svg></div>").unwrap();
+    assert_eq!(svg.to_html(), "<div hidden><svg><path d=\"M0\"/><g/></svg></div>");
+
+    assert_eq!(parse_html(&dom.to_html()).unwrap().to_html(), dom.to_html());
+}
+
+// Test walk method
+#[test]
+fn test_walk_method() {
+
+    struct Recorder {
+        events: Vec<String>,
+    }
+
+    impl Visitor for Recorder {
+        fn enter(&mut self, tag: &Tag) -> Walk {
+            self.events.push(format!("<{}>", tag.get_tagname()));
+            match tag.get_tagname().as_str() {
+                "ul" => Walk::Skip,
+                "i" => Walk::Stop,
+                _ => Walk::Continue
+            }
+        }
+
+        fn exit(&mut self, tag: &Tag) -> Walk {
+            self.events.push(format!("</{}>", tag.get_tagname()));
+            Walk::Continue
+        }
+    }
+
+    let html = "<html><body><div><p>One</p><ul><li>Two</li></ul></div><b>Three</b><i>Four</i><p>Five</p></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let mut recorder = Recorder{events: vec![]};
+    dom.walk(&mut recorder);
+    assert_eq!(recorder.events.concat(), "<html><body><div><p></p><ul></ul></div><b></b><i>");
+
+    let mut recorder = Recorder{events: vec![]};
+    dom.filter("div, p").walk(&mut recorder);
+    assert_eq!(recorder.events.concat(), "<div><p></p><ul></ul></div><p></p>");
+}
+
+// Test iterating over a Dom
+#[test]
+fn test_dom_iterator() {
+
+    let html = "<html><body><a href='/one'>One</a><a>Two</a><a href='/three'>Three</a></body></html>";
+
+    let dom = parse_html(html).unwrap();
+    let links = dom.filter("a");
+
+    let hrefs: Vec<String> = links.iter().filter_map(|t| t.try_get_attr_value("href")).collect();
+    assert_eq!(hrefs, vec!["/one", "/three"]);
+
+    let mut texts: Vec<String> = vec![];
+    for tag in &links {
+        texts.push(tag.get_text());
+    }
+    assert_eq!(texts, vec!["One", "Two", "Three"]);
+
+    assert_eq!(links.iter().take(2).count(), 2);
+    assert_eq!(links.into_iter().map(|t| t.get_text()).last(), Some("Three".to_string()));
+}
+
+// Test indexed access
+#[test]
+fn test_indexed_access() {
+
+    let html = "<html><body><div>One</div><div>Two</div><div>Three</div></body></html>";
+
+    let dom = parse_html(html).unwrap();
+    let divs = dom.filter("div");
+
+    assert_eq!(divs.get(1).unwrap().get_text(), "Two");
+    assert!(divs.get(3).is_none());
+    assert_eq!(divs.first().unwrap().get_text(), "One");
+    assert_eq!(divs.last().unwrap().get_text(), "Three");
+
+    let none = dom.filter("span");
+    assert!(none.get(0).is_none());
+    assert!(none.first().is_none());
+    assert!(none.last().is_none());
+}
+
+// Test len and is_empty methods
+#[test]
+fn test_len_method() {
+
+    let html = "<html><body><div>One</div><div>Two</div></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.len(), 4);
+    assert_eq!(dom.filter("div").len(), 2);
+    assert!(!dom.filter("div").is_empty());
+    assert_eq!(dom.filter("span").len(), 0);
+    assert!(dom.filter("span").is_empty());
+    assert!(dom.filter("span").filter("div").is_empty());
+}
+
+// Test Display and Debug
+#[test]
+fn test_display_and_debug() {
+
+    let html = "<html><body><a href='/more' class=\"link\">Read more about this topic on the next page, please</a><p>Hi</p></body></html>";
+
+    let dom = parse_html(html).unwrap();
+    let links = dom.filter("a");
+
+    assert_eq!(format!("{}", links.tag[0]), "<a href='/more' class=\"link\">Read more about this topic on the next page, please</a>");
+    assert_eq!(format!("{}", links), links.tag[0].to_string());
+    assert_eq!(dom.to_string(), html);
+
+    assert_eq!(format!("{:?}", links.tag[0]), r#"Tag { name: "a", attributes: {"href": "/more", "class": "link"}, text: "Read more about this topic on the next p..." }"#);
+    assert_eq!(format!("{:?}", dom.filter("p")), r#"Dom { tag: [Tag { name: "p", attributes: {}, text: "Hi" }] }"#);
+
+    let dom = parse_html("<html><body><p>é<b>ü</b>日本語のテキストはここで切り捨てられる前にもう少し長く続きますがそれでも終わりません</p></body></html>").unwrap();
+    assert_eq!(format!("{:?}", dom.filter("p").tag[0]), r#"Tag { name: "p", attributes: {}, text: "éü日本語のテキストはここで切り捨てられる前にもう少し長く続きますがそれでも終わ..." }"#);
+}
+
+// Test comparing tags
+#[test]
+fn test_tag_equality() {
+
+    let html = "<html><body><p class='a'>Hello</p><p class=\"a\">Hello</p><P class=a>Hello</P><p class='b'>Hello</p></body></html>";
+
+    let dom = parse_html(html).unwrap();
+    let paragraphs = dom.filter("p");
+
+    assert_eq!(paragraphs.tag[0], paragraphs.tag[1]);
+    assert_eq!(paragraphs.tag[0], paragraphs.tag[2]);
+    assert_ne!(paragraphs.tag[0], paragraphs.tag[3]);
+    assert_eq!(paragraphs.tag[0], parse_html("<p class=a>Hello</p>").unwrap().tag[0]);
+
+    let unique: HashSet<&Tag> = paragraphs.iter().collect();
+    assert_eq!(unique.len(), 2);
+
+    assert_eq!(paragraphs.get_text(), "Hello");
+    assert_eq!(dom.filter("p").get_attr_value("class"), "ab");
+}
+
+// Test serde support
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+
+    let html = "<html><body><div id='a' data-x='1 &amp; 2'>Hello <b>World</b><!-- note --><br></div><p>Bye</p></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let json = serde_json::to_string(&dom.filter("div").tag[0]).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["name"], "div");
+    assert_eq!(value["attributes"], serde_json::json!([["id", "a"], ["data-x", "1 & 2"]]));
+    assert_eq!(value["children"][0], serde_json::json!({"Text": "Hello "}));
+    assert_eq!(value["children"][1], serde_json::json!({"Element": {"name": "b", "attributes": [], "text": "World", "children": [{"Text": "World"}]}}));
+    assert_eq!(value["children"][2], serde_json::json!({"Comment": " note "}));
+    assert_eq!(value["children"][3]["Element"]["name"], "br");
+
+    let tag: Tag = serde_json::from_str(&json).unwrap();
+    assert_eq!(tag, dom.filter("div").tag[0]);
+    assert_eq!(tag.get_attr_value("data-x"), "1 & 2");
+
+    let copy: Dom = serde_json::from_str(&serde_json::to_string(&dom).unwrap()).unwrap();
+    assert_eq!(copy.len(), dom.len());
+    assert_eq!(copy.filter("p").get_text(), "Bye");
+    assert_eq!(copy.to_html(), dom.to_html());
+
+    let filtered: Dom = serde_json::from_str(&serde_json::to_string(&dom.filter("b, p")).unwrap()).unwrap();
+    assert_eq!(filtered.get_text(), "WorldBye");
+
+    assert!(serde_json::from_str::<Tag>(r#"{"name":"nothing"}"#).is_err());
+
+    let dom = parse_html("<html><body><p>é<b>ü</b> 日本語</p></body></html>").unwrap();
+
+    let value = serde_json::to_value(&dom.filter("p").tag[0]).unwrap();
+    assert_eq!(value["text"], "éü 日本語");
+    assert_eq!(value["children"][1]["Element"]["text"], "ü");
+}
+
+// Test to_json method
+#[test]
+fn test_to_json_method() {
+
+    let html = "<html><body><a href='/a?x=1&amp;y=\"2\"' title='line\nbreak'>Tom \\ Jerry</a><br></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.filter("a").to_json(), r#"[{"name":"a","attributes":{"href":"/a?x=1&y=\"2\"","title":"line\nbreak"},"text":"Tom \\ Jerry","children":[]}]"#);
+    assert_eq!(dom.filter("br").to_json(), r#"[{"name":"br","attributes":{},"text":"","children":[]}]"#);
+    assert_eq!(dom.filter("span").to_json(), "[]");
+    assert!(dom.to_json().starts_with(r#"[{"name":"html","attributes":{},"text":"#));
+    assert_eq!(dom.to_json().matches("\"name\":\"body\"").count(), 1);
+
+    let dom = parse_html("<html><body><p>é<b>ü</b> 日本語</p></body></html>").unwrap();
+
+    assert_eq!(dom.filter("p").to_json(), r#"[{"name":"p","attributes":{},"text":"éü 日本語","children":[{"name":"b","attributes":{},"text":"ü","children":[]}]}]"#);
+}
+
+// Test parse_fragment function
+#[test]
+fn test_parse_fragment() {
+
+    let html = "<li class='a'>One</li>\n<li>Two</li>";
+
+    let dom = parse_fragment(html).unwrap();
+
+    assert_eq!(dom.len(), 2);
+    assert_eq!(dom.get_text(), "One\nTwo");
+    assert_eq!(dom.get_inner_html(), html);
+    assert_eq!(dom.to_string(), html);
+    assert_eq!(dom.filter("li").len(), 2);
+    assert_eq!(dom.filter(".a").get_text(), "One");
+
+    assert!(parse_fragment("no tags").unwrap().is_empty());
+    assert_eq!(parse_html(html).unwrap().get_text(), "One");
+}
+
+// Test parse_html_bytes function
+#[test]
+fn test_parse_html_bytes() {
+
+    let dom = parse_html_bytes(b"<html><head><meta charset='ISO-8859-1'></head><body><p>Gr\xfc\xdfe</p></body></html>").unwrap();
+    assert_eq!(dom.filter("p").get_text(), "Grüße");
+
+    let dom = parse_html_bytes(b"<html><head><meta http-equiv='Content-Type' content='text/html; charset=windows-1251'></head><body><p>\xcf\xf0\xe8\xe2\xe5\xf2</p></body></html>").unwrap();
+    assert_eq!(dom.filter("p").get_text(), "Привет");
+
+    let dom = parse_html_bytes("<html><body><p>Grüße</p></body></html>".as_bytes()).unwrap();
+    assert_eq!(dom.filter("p").get_text(), "Grüße");
+
+    let dom = parse_html_bytes(b"\xef\xbb\xbf<html><head><meta charset='ISO-8859-1'></head><body><p>\xc3\xbc</p></body></html>").unwrap();
+    assert_eq!(dom.filter("p").get_text(), "ü");
+
+    let dom = parse_html_bytes(b"\xff\xfe<\x00p\x00>\x00\xfc\x00<\x00/\x00p\x00>\x00").unwrap();
+    assert_eq!(dom.filter("p").get_text(), "ü");
+
+    // transcoded text with child tags, also directly after a non-ascii character
+    let dom = parse_html_bytes(b"<meta charset=iso-8859-1><p>caf\xe9 <b>x</b></p><div>caf\xe9<b>cr\xe8me</b> br\xfbl\xe9e</div>").unwrap();
+    assert_eq!(dom.filter("p").get_text(), "café x");
+    assert_eq!(dom.filter("div").get_text(), "cafécrème brûlée");
+
+    assert!(parse_html_bytes(b"no html").is_err());
+}
+
+// Test get_links method
+#[test]
+fn test_get_links_method() {
+
+    let html = "<html><body><a name='top'>Top</a><a href='/a?x=1&amp;y=2' REL='nofollow'>\n  First </a><nav><a href='https://example.com' target='_blank'>Second</a></nav></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let links = dom.get_links();
+
+    assert_eq!(links, vec![
+        Link{href: "/a?x=1&y=2".to_string(), text: "First".to_string(), rel: Some("nofollow".to_string()), target: None},
+        Link{href: "https://example.com".to_string(), text: "Second".to_string(), rel: None, target: Some("_blank".to_string())},
+    ]);
+
+    assert_eq!(dom.filter("nav").get_links().len(), 1);
+    assert!(dom.filter("p").get_links().is_empty());
+
+    let dom = parse_html("<html><body><a href='/cafe'> Café <b>crème</b> </a></body></html>").unwrap();
+
+    assert_eq!(dom.get_links()[0].text, "Café crème");
+}
+
+// Test resolve_urls method
+#[test]
+fn test_resolve_urls_method() {
+
+    assert_eq!(url::resolve("https://example.com/a/b/c", "../d?x=1#top").unwrap(), "https://example.com/a/d?x=1#top");
+    assert_eq!(url::resolve("https://example.com/a/", "//cdn.example.com/x.js").unwrap(), "https://cdn.example.com/x.js");
+    assert_eq!(url::resolve("https://example.com/a/", "http://other.org/").unwrap(), "http://other.org/");
+    assert!(url::resolve("/relative", "x").is_err());
+
+    let html = "<html><body><a href='../about?a=1&amp;b=2'>About</a><img src='img/logo.png' alt='logo'><a href='mailto:a@example.com'>Mail</a></body></html>";
+
+    let dom = parse_html(html).unwrap().resolve_urls("https://example.com/shop/items/").unwrap();
+
+    assert_eq!(dom.filter("a").tag[0].get_attr_value("href"), "https://example.com/shop/about?a=1&b=2");
+    assert_eq!(dom.filter("a").tag[1].get_attr_value("href"), "mailto:a@example.com");
+    assert_eq!(dom.filter("img").get_attr_value("src"), "https://example.com/shop/items/img/logo.png");
+    assert_eq!(dom.filter("img").get_attr_value("alt"), "logo");
+    assert_eq!(dom.get_text(), parse_html(html).unwrap().get_text());
+
+    let html = "<html><head><base href='/static/'></head><body><p><a href='x.html'>X</a></p><p>Y</p></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.resolve_urls("https://example.com/page").unwrap().filter("a").get_attr_value("href"), "https://example.com/static/x.html");
+
+    let resolved = dom.filter("p").resolve_urls("https://example.com/page").unwrap();
+    assert_eq!(resolved.len(), 2);
+    assert_eq!(resolved.filter("a").get_attr_value("href"), "https://example.com/static/x.html");
+
+    assert!(dom.resolve_urls("example.com").is_err());
+}
+
+// Test get_tables method
+#[test]
+fn test_get_tables_method() {
+
+    let html = "<html><body><table>
+        <thead><tr><th rowspan='2'>Name</th><th colspan='2'>Scores</th></tr><tr><th>Math</th><th>Art</th></tr></thead>
+        <tbody><tr><td>Anna</td><td>1</td><td rowspan='2'>2</td></tr><tr><td>Ben</td><td>3</td></tr>
+        <tr><td colspan='3'>Total <b>6</b></td></tr></tbody>
+        </table>
+        <table><tr><td>A</td><td><table><tr><td>Inner</td></tr></table></td></tr></table></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let tables = dom.get_tables();
+
+    assert_eq!(tables.len(), 3);
+    assert_eq!(tables[0].headers, vec!["Name", "Math", "Art"]);
+    assert_eq!(tables[0].rows, vec![
+        vec!["Anna", "1", "2"],
+        vec!["Ben", "3", "2"],
+        vec!["Total 6", "Total 6", "Total 6"],
+    ]);
+
+    assert!(tables[1].headers.is_empty());
+    assert_eq!(tables[1].rows, vec![vec!["A", "Inner"]]);
+    assert_eq!(tables[2].rows, vec![vec!["Inner"]]);
+
+    assert_eq!(dom.filter("p").get_tables(), vec![]);
+}
+
+// Test get_forms method
+#[test]
+fn test_get_forms_method() {
+
+    let html = "<html><body>
+        <form action='/search'><input name='q' value='rust &amp; html'><input type='submit'></form>
+        <form action='/login' method='POST'>
+        <input type='hidden' name='token' value='abc'>
+        <input type='checkbox' name='remember' checked>
+        <select name='lang'><option value='de'>German</option><option selected> English </option></select>
+        <textarea name='note'>Hello &amp; bye</textarea>
+        </form></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let forms = dom.get_forms();
+
+    assert_eq!(forms.len(), 2);
+
+    assert_eq!(forms[0].action, "/search");
+    assert_eq!(forms[0].method, "GET");
+    assert_eq!(forms[0].fields, vec![FormField{name: "q".to_string(), kind: "text".to_string(), value: "rust & html".to_string(), checked: false, options: vec![]}]);
+
+    assert_eq!(forms[1].method, "POST");
+    let fields: Vec<(&str, &str, &str, bool)> = forms[1].fields.iter().map(|f| (f.name.as_str(), f.kind.as_str(), f.value.as_str(), f.checked)).collect();
+    assert_eq!(fields, vec![
+        ("token", "hidden", "abc", false),
+        ("remember", "checkbox", "on", true),
+        ("lang", "select", "English", false),
+        ("note", "textarea", "Hello & bye", false),
+    ]);
+    assert_eq!(forms[1].fields[2].options, vec!["de", "English"]);
+
+    assert!(dom.filter("p").get_forms().is_empty());
+}
+
+// Test get_meta method
+#[test]
+fn test_get_meta_method() {
+
+    let html = "<html><head><meta charset='utf-8'><meta name='description' content='Tom &amp; Jerry'>
+        <meta property='og:image' content='/a.png'><meta property='og:image' content='/b.png'>
+        <meta http-equiv='Content-Type' content='text/html'><meta name='robots'></head><body><p>Hi</p></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let meta = dom.get_meta();
+
+    assert_eq!(meta.len(), 5);
+    assert_eq!(meta["charset"], "utf-8");
+    assert_eq!(meta["description"], "Tom & Jerry");
+    assert_eq!(meta["og:image"], "/a.png");
+    assert_eq!(meta["content-type"], "text/html");
+    assert_eq!(meta["robots"], "");
+    assert_eq!(dom.filter("p").get_meta(), meta);
+
+    assert!(parse_html("<html><body></body></html>").unwrap().get_meta().is_empty());
+}
+
+// Test open_graph and twitter_card methods
+#[test]
+fn test_open_graph_and_twitter_card() {
+
+    let html = "<html><head>
+        <meta property='og:title' content='Rust &amp; HTML'><meta property='og:type' content='article'>
+        <meta property='og:image:url' content='/a.png'><meta property='og:description' content=''>
+        <meta name='twitter:card' content='summary_large_image'><meta property='twitter:creator' content='@someone'>
+        <meta name='twitter:image:src' content='/b.png'>
+        </head><body></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.open_graph(), OpenGraph{
+        title: Some("Rust & HTML".to_string()),
+        kind: Some("article".to_string()),
+        image: Some("/a.png".to_string()),
+        ..Default::default()
+    });
+
+    assert_eq!(dom.twitter_card(), TwitterCard{
+        card: Some("summary_large_image".to_string()),
+        creator: Some("@someone".to_string()),
+        image: Some("/b.png".to_string()),
+        ..Default::default()
+    });
+
+    assert_eq!(parse_html("<html><head></head></html>").unwrap().open_graph(), OpenGraph::default());
+}
+
+// Test get_microdata method
+#[test]
+fn test_get_microdata_method() {
+
+    let html = "<html><body>
+        <div itemscope itemtype='https://schema.org/Product' itemid='urn:1'>
+        <h1 itemprop='name'>Kettle <small>XL</small></h1>
+        <img itemprop='image' src='/a.png'><img itemprop='image' src='/b.png'>
+        <div itemprop='offers' itemscope itemtype='https://schema.org/Offer'>
+        <meta itemprop='price' content='19.99'><span itemprop='name'>Offer</span>
+        </div>
+        <time itemprop='releaseDate' datetime='2020-01-01'>January</time>
+        </div>
+        <p itemscope><a itemprop='url sameAs' href='/x'>X</a></p>
+        <p itemprop='orphan'>No item</p>
+        </body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let items = dom.get_microdata();
+
+    assert_eq!(items.len(), 2);
+
+    let product = &items[0];
+    assert_eq!(product.kind, vec!["https://schema.org/Product"]);
+    assert_eq!(product.id, Some("urn:1".to_string()));
+    assert_eq!(product.get("name"), Some(&ItemValue::Text("Kettle XL".to_string())));
+    assert_eq!(product.properties.iter().filter(|(n, _)| n == "image").count(), 2);
+    assert_eq!(product.get("releaseDate"), Some(&ItemValue::Text("2020-01-01".to_string())));
+    assert_eq!(product.get("price"), None);
+
+    match product.get("offers") {
+        Some(ItemValue::Item(offer)) => {
+            assert_eq!(offer.get("price"), Some(&ItemValue::Text("19.99".to_string())));
+            assert_eq!(offer.get("name"), Some(&ItemValue::Text("Offer".to_string())));
+        },
+        v => panic!("{:?}", v)
+    }
+
+    assert!(items[1].kind.is_empty());
+    assert_eq!(items[1].get("url"), items[1].get("sameAs"));
+
+    assert_eq!(dom.filter("p").get_microdata().len(), 1);
+    assert_eq!(dom.filter("span").get_microdata().len(), 0);
+}
+
+// Test get_rdfa method
+#[test]
+fn test_get_rdfa_method() {
+
+    let html = "<html><body><div vocab='https://schema.org/' typeof='Person' resource='#ada'>
+        <span property='name'>Ada</span><a property='url' href='/ada'>Home</a>
+        <div property='address' typeof='PostalAddress'><span property='addressLocality'>London</span></div>
+        <meta property='birthDate' content='1815-12-10'>
+        </div></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let items = dom.get_rdfa();
+
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].kind, vec!["Person"]);
+    assert_eq!(items[0].id, Some("#ada".to_string()));
+
+    let properties: Vec<&str> = items[0].properties.iter().map(|(n, _)| n.as_str()).collect();
+    assert_eq!(properties, vec!["name", "url", "address", "birthDate"]);
+    assert_eq!(items[0].get("url"), Some(&ItemValue::Text("/ada".to_string())));
+    assert_eq!(items[0].get("birthDate"), Some(&ItemValue::Text("1815-12-10".to_string())));
+
+    match items[0].get("address") {
+        Some(ItemValue::Item(address)) => assert_eq!(address.get("addressLocality"), Some(&ItemValue::Text("London".to_string()))),
+        v => panic!("{:?}", v)
+    }
+
+    assert!(dom.get_microdata().is_empty());
+}
+
+// Test get_text_with method
+#[test]
+fn test_get_text_with_method() {
+
+    let html = "<html><body><div>\n  <b>Tom &amp;</b>\t Jerry<!-- c -->\n  <i>Age:</i> 42\n</div><p>A</p><p> B </p><script>if (a &amp;&amp; b) {}</script></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let div = &dom.filter("div").tag[0];
+
+    assert_eq!(div.get_text_with(TextOptions::default()), "\n  Tom &\t Jerry\n  Age: 42\n");
+    assert_eq!(div.get_text_with(TextOptions{collapse_whitespace: true, ..Default::default()}), " Tom & Jerry Age: 42 ");
+    assert_eq!(div.get_text_with(TextOptions{collapse_whitespace: true, trim: true, ..Default::default()}), "Tom & Jerry Age: 42");
+    assert_eq!(div.get_text_with(TextOptions{separator: Some("|".to_string()), ..Default::default()}), "Tom &|Jerry|Age:|42");
+
+    assert_eq!(dom.filter("p").get_text_with(TextOptions{separator: Some(", ".to_string()), ..Default::default()}), "A, B");
+    assert_eq!(dom.filter("p").get_text_with(TextOptions::default()), "A B ");
+    assert_eq!(dom.filter("script").get_text_with(TextOptions::default()), "if (a &amp;&amp; b) {}");
+    assert_eq!(dom.filter("body, div").get_text_with(TextOptions{trim: true, ..Default::default()}), dom.filter("body").get_text_with(TextOptions{trim: true, ..Default::default()}));
+
+    let dom = parse_fragment("<li>One</li>\n<li>Two</li>").unwrap();
+    assert_eq!(dom.get_text_with(TextOptions{separator: Some("; ".to_string()), ..Default::default()}), "One; Two");
+}
+
+// Test to_markdown method
+#[test]
+fn test_to_markdown_method() {
+
+    let html = "<html><head><title>T</title><style>p {}</style></head><body>
+        <h2>Intro  <small>text</small></h2>
+        <p>Some <strong>bold</strong> and <em> italic </em> text with a_b*c and <code>x &lt; y</code>.<br>Next line</p>
+        <p>A <a href='/x y'>link</a>, an <a href='https://example.com'></a> and <img src='/a.png' alt='pic'></p>
+        <ol start='3'><li>Three</li><li>Four<ul><li>Sub</li></ul></li></ol>
+        <blockquote><p>Quoted</p><p>Twice</p></blockquote>
+        <pre class='language-rust'>
+fn main() {
+
+    println!(\"*\");
+}</pre>
+        <hr>
+        <table><tr><th>A</th><th>B</th></tr><tr><td>1|2</td><td>3</td></tr></table>
+        <script>var a = '<b>';</script>
+        </body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let expected = "## Intro text
+
+Some **bold** and *italic* text with a\\_b\\*c and `x < y`.  
+Next line
+
+A [link](</x y>), an [https://example.com](https://example.com) and ![pic](/a.png)
+
+3. Three
+4. Four
+
+   - Sub
+
+> Quoted
+>
+> Twice
+
+```rust
+fn main() {
+
+    println!(\"*\");
+}
+```
+
+---
+
+| A | B |
+| --- | --- |
+| 1\\|2 | 3 |";
+
+    assert_eq!(dom.to_markdown(), expected);
+
+    assert_eq!(dom.filter("h2, strong").to_markdown(), "## Intro text\n\n**bold**");
+    assert_eq!(dom.filter("li").tag[0].to_markdown(), "Three");
+    assert_eq!(dom.filter("span").to_markdown(), "");
+}
+
+// Test render_text method
+#[test]
+fn test_render_text_method() {
+
+    let html = "<html><head><title>T</title></head><body>
+        <h1>Title</h1>
+        <div>First <b>line</b></div><div>Second<br>Third</div>
+        <p>A   paragraph
+           over lines.</p>
+        <ul><li>One</li><li>Two<ol start='2'><li>Sub</li></ol></li></ul>
+        <table><tr><th>A</th><th>B</th></tr><tr><td>1</td><td>2</td></tr></table>
+        <pre>
+  indented
+    code</pre>
+        <span>End</span><script>var x;</script>
+        </body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let expected = "Title
+
+First line
+Second
+Third
+
+A paragraph over lines.
+
+• One
+• Two
+  2. Sub
+
+A\tB
+1\t2
+
+  indented
+    code
+
+End";
+
+    assert_eq!(dom.render_text(), expected);
+    assert_eq!(dom.filter("div").render_text(), "First line\nSecond\nThird");
+    assert_eq!(dom.filter("p").tag[0].render_text(), "A paragraph over lines.");
+    assert_eq!(dom.filter("span, em").render_text(), "End");
+}
+
+// Test alt_text option
+#[test]
+fn test_alt_text_option() {
+
+    let html = "<html><body><p>Logo: <img src='/a.png' alt='ACME &amp; Co'> <a href='/s'><i class='icon' aria-label='Search'></i></a>
+        <button title='Close'><svg></svg></button><img src='/b.png' alt=''><span title='Hint'>Text</span></p></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let options = TextOptions{separator: Some("|".to_string()), alt_text: true, ..Default::default()};
+
+    assert_eq!(dom.filter("p").get_text_with(options.clone()), "Logo:|ACME & Co|Search|Close|Text");
+    assert_eq!(dom.filter("p").tag[0].get_text_with(options), "Logo:|ACME & Co|Search|Close|Text");
+    assert_eq!(dom.filter("p").get_text_with(TextOptions{separator: Some("|".to_string()), ..Default::default()}), "Logo:|Text");
+}
+
+// Test title and meta_description methods
+#[test]
+fn test_title_and_meta_description() {
+
+    let html = "<html><head><title>\n  Tom &amp;\n  Jerry </title><meta name='Description' content='  Cartoons  '></head>
+        <body><svg><title>Icon</title></svg><p>Text</p></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.title(), Some("Tom & Jerry".to_string()));
+    assert_eq!(dom.meta_description(), Some("Cartoons".to_string()));
+    assert_eq!(dom.filter("p").title(), Some("Tom & Jerry".to_string()));
+
+    let dom = parse_html("<html><head><title> </title><meta name='description' content=''></head><body><svg><title>Icon</title></svg></body></html>").unwrap();
+
+    assert_eq!(dom.title(), None);
+    assert_eq!(dom.meta_description(), None);
+
+    let dom = parse_html("<html><body><svg><title>Icon</title></svg></body></html>").unwrap();
+
+    assert_eq!(dom.title(), None);
+}
+
+// Test get_contacts method
+#[test]
+fn test_get_contacts_method() {
+
+    let html = "<html><body>
+        <p>Mail <b>Sales@Example.COM</b>, sales@example.com or jane.doe+news@mail.example.co.uk.</p>
+        <p>Call 030 / 123 45 67, 0049 (0)30 1234567 or (030) 1234567.</p>
+        <p>Since 2015, only 19.99 EUR, until 01.02.2024, order 12345678.</p>
+        <a href='mailto:Support@Example.com,team@example.com?subject=Hi'>Support</a>
+        <a href='TEL:+1-202-555-0143;ext=12'>US</a><a href='tel:123'>Short</a><a href='/contact'>Contact</a>
+        </body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let contacts = dom.get_contacts();
+
+    assert_eq!(contacts.emails, vec!["support@example.com", "team@example.com", "sales@example.com", "jane.doe+news@mail.example.co.uk"]);
+    assert_eq!(contacts.phones, vec!["+12025550143", "0301234567", "+49301234567"]);
+
+    assert_eq!(dom.filter("a[href^=TEL]").get_contacts().phones, vec!["+12025550143"]);
+    assert_eq!(parse_html("<html><body><p>No contacts</p></body></html>").unwrap().get_contacts(), Contacts::default());
+}
+
+// Test canonical_url and hreflang_links methods
+#[test]
+fn test_canonical_url_and_hreflang_links() {
+
+    let html = "<html><head>
+        <link rel='stylesheet' href='/a.css'>
+        <link rel='Canonical' href=' https://example.com/page?a=1&amp;b=2 '>
+        <link rel='canonical' href='https://example.com/other'>
+        <link rel='alternate' hreflang='en-US' href='https://example.com/en/'>
+        <link rel='alternate' type='application/rss+xml' href='/feed'>
+        <link rel='alternate nofollow' hreflang='x-default' href='https://example.com/'>
+        <link rel='canonical'>
+        </head><body><p>Text</p></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.canonical_url(), Some("https://example.com/page?a=1&b=2".to_string()));
+    assert_eq!(dom.hreflang_links(), vec![
+        ("en-US".to_string(), "https://example.com/en/".to_string()),
+        ("x-default".to_string(), "https://example.com/".to_string()),
+    ]);
+    assert_eq!(dom.filter("p").canonical_url(), dom.canonical_url());
+
+    let dom = parse_html("<html><head><link rel='icon' href='/favicon.ico'></head></html>").unwrap();
+
+    assert_eq!(dom.canonical_url(), None);
+    assert!(dom.hreflang_links().is_empty());
+}
+
+// Test feed_links method
+#[test]
+fn test_feed_links_method() {
+
+    let html = "<html><head>
+        <link rel='alternate' type='application/rss+xml' title='News &amp; More' href='/news.rss'>
+        <link rel='alternate' hreflang='de' href='/de/'>
+        <link rel='Alternate' type='Application/Atom+XML; charset=utf-8' href='https://example.com/atom.xml'>
+        <link rel='stylesheet' type='application/rss+xml' href='/wrong'>
+        <link rel='alternate' type='application/json' href='/data.json'>
+        </head><body></body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    assert_eq!(dom.feed_links(), vec![
+        FeedLink{href: "/news.rss".to_string(), title: Some("News & More".to_string()), kind: "application/rss+xml".to_string()},
+        FeedLink{href: "https://example.com/atom.xml".to_string(), title: None, kind: "application/atom+xml".to_string()},
+    ]);
+
+    assert!(parse_html("<html><head></head></html>").unwrap().feed_links().is_empty());
+}
+
+// Test feeds module
+#[test]
+fn test_feeds() {
+
+    let xml = "<?xml version='1.0' encoding='UTF-8'?>
+        <!-- a comment -->
+        <rss version='2.0' xmlns:content='http://purl.org/rss/1.0/modules/content/'>
+        <channel>
+        <title>Tom &amp; Jerry News</title><link>https://example.com/</link><description>Daily</description>
+        <item>
+        <title><![CDATA[First <post>]]></title>
+        <link>https://example.com/first</link>
+        <guid isPermaLink='false'>id-1</guid>
+        <pubDate>Mon, 06 Sep 2021 16:45:00 GMT</pubDate>
+        <description>Short</description>
+        <content:encoded><![CDATA[<p>Long & <b>bold</b></p>]]></content:encoded>
+        </item>
+        <item><guid>https://example.com/second</guid><description>&lt;p&gt;Second&lt;/p&gt;</description></item>
+        </channel></rss>";
+
+    let feed = feeds::parse(xml).unwrap();
+
+    assert_eq!(feed.kind, feeds::FeedKind::Rss);
+    assert_eq!(feed.title, "Tom & Jerry News");
+    assert_eq!(feed.link, "https://example.com/");
+    assert_eq!(feed.description, "Daily");
+    assert_eq!(feed.entries, vec![
+        feeds::Entry{
+            title: "First <post>".to_string(),
+            link: "https://example.com/first".to_string(),
+            id: Some("id-1".to_string()),
+            published: Some("Mon, 06 Sep 2021 16:45:00 GMT".to_string()),
+            content: "<p>Long & <b>bold</b></p>".to_string(),
+        },
+        feeds::Entry{
+            title: "".to_string(),
+            link: "https://example.com/second".to_string(),
+            id: Some("https://example.com/second".to_string()),
+            published: None,
+            content: "<p>Second</p>".to_string(),
+        },
+    ]);
+
+    let xml = "<?xml version='1.0' encoding='utf-8'?>
+        <feed xmlns='http://www.w3.org/2005/Atom'>
+        <title>Blog</title><subtitle>About things</subtitle>
+        <link rel='self' href='https://example.com/atom.xml'/><link href='https://example.com/'/>
+        <entry>
+        <title type='html'>A &amp;lt;b&amp;gt;</title>
+        <link rel='alternate' href='https://example.com/a'/>
+        <id>urn:uuid:1</id><updated>2021-09-06T16:45:00Z</updated>
+        <content type='xhtml'><div xmlns='http://www.w3.org/1999/xhtml'><p>Hi</p></div></content>
+        </entry>
+        <entry><title>B</title><published>2021-09-01T00:00:00Z</published><updated>2021-09-02T00:00:00Z</updated><summary>Sum</summary></entry>
+        </feed>";
+
+    let feed = feeds::parse(xml).unwrap();
+
+    assert_eq!(feed.kind, feeds::FeedKind::Atom);
+    assert_eq!(feed.title, "Blog");
+    assert_eq!(feed.link, "https://example.com/");
+    assert_eq!(feed.description, "About things");
+    assert_eq!(feed.entries.len(), 2);
+    assert_eq!(feed.entries[0].title, "A &lt;b&gt;");
+    assert_eq!(feed.entries[0].link, "https://example.com/a");
+    assert_eq!(feed.entries[0].id, Some("urn:uuid:1".to_string()));
+    assert_eq!(feed.entries[0].published, Some("2021-09-06T16:45:00Z".to_string()));
+    assert_eq!(feed.entries[0].content, "<div xmlns='http://www.w3.org/1999/xhtml'><p>Hi</p></div>");
+    assert_eq!(feed.entries[1].published, Some("2021-09-01T00:00:00Z".to_string()));
+    assert_eq!(feed.entries[1].content, "Sum");
+
+    assert!(feeds::parse("<html><body></body></html>").is_err());
+}
+
+// Test text_stats method
+#[test]
+fn test_text_stats_method() {
+
+    let html = "<html><head><title>Not counted</title></head><body>
+        <h1>Title</h1>
+        <p>First sentence. Second one?! Version 1.5 is out...</p>
+        <ul><li>Item one</li><li>-</li></ul><ol><li>Step</li></ol>
+        <script>var not = 'counted';</script>
+        </body></html>";
+
+    let dom = parse_html(html).unwrap();
+
+    let stats = dom.text_stats();
+
+    assert_eq!(stats.words, 12);
+    assert_eq!(stats.characters, "TitleFirstsentence.Secondone?!Version1.5isout...Itemone-Step".chars().count());
+    assert_eq!(stats.sentences, 6);
+    assert_eq!(stats.reading_time, std::time::Duration::from_millis(3600));
+
+    assert_eq!(dom.filter("h1").text_stats().words, 1);
+    assert_eq!(dom.filter("span").text_stats(), TextStats{words: 0, characters: 0, sentences: 0, reading_time: std::time::Duration::ZERO});
+}
+
+// Test language method
+#[test]
+fn test_language_method() {
+
+    let dom = parse_html("<html lang=' en-US '><head><meta http-equiv='Content-Language' content='de'></head><body><p>Text</p></body></html>").unwrap();
+    assert_eq!(dom.language(), Some("en-US".to_string()));
+    assert_eq!(dom.filter("p").language(), Some("en-US".to_string()));
+
+    let dom = parse_html("<html><head><meta http-equiv='content-language' content='fr, en'></head><body><svg lang='xx'></svg></body></html>").unwrap();
+    assert_eq!(dom.language(), Some("fr".to_string()));
+
+    let dom = parse_html("<html lang=''><body><p>1 2 3</p></body></html>").unwrap();
+    assert_eq!(dom.language(), None);
+}
+
+// Test language detection
+#[cfg(feature = "detect-language")]
+#[test]
+fn test_language_detection() {
+
+    let guess = |text: &str| parse_html(&format!("<html><head><title>Title</title></head><body><p>{}</p></body></html>", text)).unwrap().language();
+
+    assert_eq!(guess("The quick brown fox jumps over the lazy dog and this is the end of it."), Some("en".to_string()));
+    assert_eq!(guess("Der schnelle braune Fuchs springt über den faulen Hund und das ist nicht alles."), Some("de".to_string()));
+    assert_eq!(guess("Le renard brun rapide saute par-dessus le chien paresseux et les chats dans la maison."), Some("fr".to_string()));
+    assert_eq!(guess("El rápido zorro marrón salta sobre el perro perezoso y los gatos de la casa."), Some("es".to_string()));
+    assert_eq!(guess("Быстрая коричневая лиса прыгает через ленивую собаку."), Some("ru".to_string()));
+    assert_eq!(guess("東京は日本の首都です。ひらがなとカタカナもあります。"), Some("ja".to_string()));
+    assert_eq!(guess("北京是中国的首都。"), Some("zh".to_string()));
+    assert_eq!(guess("Rust"), None);
+
+    assert_eq!(parse_html("<html lang='it'><body><p>The quick brown fox and the dog.</p></body></html>").unwrap().language(), Some("it".to_string()));
+}
+
+// Test get_scripts method
+#[test]
+fn test_get_scripts_method() {
+
+    let html = "<html><head><script src=' /app.js ' defer></script><script>var a = '</div>' + 1 < 2 && '&amp;';</script></head>
+        <body><script type='application/ld+json'> {\"@type\": \"Thing\"} </script><div><script src=''></script></div></body></html>";
+
+    let dom = parse_html(html).unwrap();
+    let scripts = dom.get_scripts();
+
+    assert_eq!(scripts.len(), 4);
+    assert_eq!(scripts[0], Script{src: Some("/app.js".to_string()), content: String::new(), kind: None});
+    assert_eq!(scripts[1].content, "var a = '</div>' + 1 < 2 && '&amp;';");
+    assert_eq!(scripts[2].kind, Some("application/ld+json".to_string()));
+    assert_eq!(scripts[2].content, "{\"@type\": \"Thing\"}");
+    assert_eq!(scripts[3].src, None);
+
+    assert_eq!(dom.filter("body").get_scripts().len(), 2);
+}
+
+// Test get_stylesheets method
+#[test]
+fn test_get_stylesheets_method() {
+
+    let html = "<html><head><link rel='icon' href='/favicon.ico'><link rel='Alternate StyleSheet' href=' /dark.css ' title='Dark'>
+        <style media='screen and (max-width: 600px)'> p > a { color: red; } </style><link rel='stylesheet'></head>
+        <body><style></style></body></html>";
+
+    let dom = parse_html(html).unwrap();
+    let stylesheets = dom.get_stylesheets();
+
+    assert_eq!(stylesheets.len(), 3);
+    assert_eq!(stylesheets[0], Stylesheet{href: Some("/dark.css".to_string()), media: None, content: String::new()});
+    assert_eq!(stylesheets[1].media, Some("screen and (max-width: 600px)".to_string()));
+    assert_eq!(stylesheets[1].content, "p > a { color: red; }");
+    assert_eq!(stylesheets[2], Stylesheet{href: None, media: None, content: String::new()});
+
+    assert_eq!(dom.filter("body").get_stylesheets().len(), 1);
+}
+
+// Test srcset
+#[test]
+fn test_srcset() {
+
+    use srcset::{Candidate, Descriptor};
+
+    let candidates = srcset::parse(" small.jpg 480w,medium.jpg  800w 600h , data:image/png;base64,iVBO,, large.jpg 1080w, bad.jpg 2q, tall.jpg 100h, image(1).jpg 1.5x");
+
+    assert_eq!(candidates, vec![
+        Candidate{url: "small.jpg".to_string(), descriptor: Descriptor::Width(480)},
+        Candidate{url: "medium.jpg".to_string(), descriptor: Descriptor::Width(800)},
+        Candidate{url: "data:image/png;base64,iVBO".to_string(), descriptor: Descriptor::Density(1.0)},
+        Candidate{url: "large.jpg".to_string(), descriptor: Descriptor::Width(1080)},
+        Candidate{url: "image(1).jpg".to_string(), descriptor: Descriptor::Density(1.5)},
+    ]);
+
+    assert_eq!(srcset::best(&candidates, 300).unwrap().url, "small.jpg");
+    assert_eq!(srcset::best(&candidates, 800).unwrap().url, "medium.jpg");
+    assert_eq!(srcset::best(&candidates, 2000).unwrap().url, "large.jpg");
+
+    let candidates = srcset::parse("a.png 0.5x, b.png 2x, c.png");
+    assert_eq!(srcset::best(&candidates, 100).unwrap().url, "c.png");
+    assert_eq!(srcset::best(&candidates[..1], 100).unwrap().url, "a.png");
+
+    assert!(srcset::parse(" , ").is_empty());
+    assert_eq!(srcset::best(&[], 100), None);
+}
+
+// Test data_attrs method
+#[test]
+fn test_data_attrs_method() {
+
+    let dom = parse_html("<html><body><div data-Product-ID='42' data-empty data-product-id='43' data-='x' dataset='y' id='z'>Coffee</div><p>Text</p></body></html>").unwrap();
+
+    let data = dom.filter("div").tag[0].data_attrs();
+
+    assert_eq!(data.len(), 2);
+    assert_eq!(data["product-id"], "42");
+    assert_eq!(data["empty"], "");
+
+    assert!(dom.filter("p").tag[0].data_attrs().is_empty());
+}
+
+// Test breadcrumbs method
+#[test]
+fn test_breadcrumbs_method() {
+
+    let crumb = |name: &str, url: Option<&str>| Breadcrumb{name: name.to_string(), url: url.map(|u| u.to_string())};
+
+    // JSON-LD inside of a graph, ordered by position, with the item given as url or object
+    let html = r#"<html><head><script type="application/ld+json">{"@context": "https://schema.org", "@graph": [{"@type": "WebPage"},
+        {"@type": "BreadcrumbList", "itemListElement": [
+            {"@type": "ListItem", "position": 2, "item": {"@id": "https://example.com/books", "name": "Books \u00e0 la carte"}},
+            {"@type": "ListItem", "position": "1", "name": "Home", "item": "https://example.com/"},
+            {"@type": "ListItem", "position": 3, "name": "Rust"}]}]}</script></head>
+        <body><nav class='breadcrumb'><a href='/'>Start</a></nav></body></html>"#;
+    assert_eq!(parse_html(html).unwrap().breadcrumbs(), vec![
+        crumb("Home", Some("https://example.com/")), crumb("Books à la carte", Some("https://example.com/books")), crumb("Rust", None)
+    ]);
+
+    // microdata
+    let html = "<html><body><ol itemscope itemtype='https://schema.org/BreadcrumbList'>
+        <li itemprop='itemListElement' itemscope itemtype='https://schema.org/ListItem'><a itemprop='item' href='/'><span itemprop='name'>Home</span></a><meta itemprop='position' content='1'></li> ›
+        <li itemprop='itemListElement' itemscope itemtype='https://schema.org/ListItem'><span itemprop='name'>Books</span><meta itemprop='position' content='2'></li>
+        </ol></body></html>";
+    assert_eq!(parse_html(html).unwrap().filter("li").breadcrumbs(), vec![crumb("Home", Some("/")), crumb("Books", None)]);
+
+    // links without list items and the current page as text
+    let html = "<html><body><div id='Breadcrumbs'><a href='/'>Home</a> &gt; <span><a href='/books'> Books </a></span> &gt; Rust  </div></body></html>";
+    assert_eq!(parse_html(html).unwrap().breadcrumbs(), vec![crumb("Home", Some("/")), crumb("Books", Some("/books")), crumb("Rust", None)]);
+
+    // list items with separators of their own
+    let html = "<html><body><ul class='crumbs' aria-label='Breadcrumb'><li><a href='/'>Home</a> /</li><li>/</li><li>Books</li></ul></body></html>";
+    assert_eq!(parse_html(html).unwrap().breadcrumbs(), vec![crumb("Home", Some("/")), crumb("Books", None)]);
+
+    assert!(parse_html("<html><body><script type='application/ld+json'>{invalid</script><nav><a href='/'>Home</a></nav></body></html>").unwrap().breadcrumbs().is_empty());
+}
+
+// Test pagination method
+#[test]
+fn test_pagination_method() {
+
+    let html = "<html><head><link rel='next' href=' /list?page=3 '></head><body><a href='/list?page=4'>Next</a><a rel='Prev nofollow' href='/list?page=1'>Back</a></body></html>";
+    let pagination = parse_html(html).unwrap().filter("a").pagination();
+    assert_eq!(pagination.next, Some("/list?page=3".to_string()));
+    assert_eq!(pagination.prev, Some("/list?page=1".to_string()));
+
+    let html = "<html><body><a href='#'>Next</a><a href='/a'>Nextcloud</a><a href='/older' title='Older posts'><i class='icon'></i></a>
+        <a href='/b'>  NÄCHSTE   SEITE &raquo;</a><a href='/c'>&lsaquo;&lsaquo;</a><a href='/d'>&lsaquo;</a></body></html>";
+    let pagination = parse_html(html).unwrap().pagination();
+    assert_eq!(pagination.next, Some("/older".to_string()));
+    assert_eq!(pagination.prev, Some("/d".to_string()));
+
+    let html = "<html><body><ul><li class='page-item'><a href='/1'>1</a></li><li class='page-item page-next'><a href='/2'><svg></svg></a></li></ul>
+        <a class='preview' href='/p'>Preview</a></body></html>";
+    assert_eq!(parse_html(html).unwrap().pagination(), Pagination{next: Some("/2".to_string()), prev: None});
+}
+
+// Test diff method
+#[test]
+fn test_diff_method() {
+
+    let old = parse_html("<html><body><div id='a' class='x' title='t'>Hello <b>World</b></div><ul><li>1</li><li>2</li><li>3</li></ul><p>Bye</p></body></html>").unwrap();
+    let new = parse_html("<html><body>\n<div title='t' class='x'  id='a'>Hello\n <b>Earth</b></div><ul><li>0</li><li>1</li><li>3</li></ul><footer>Bye</footer></body></html>").unwrap();
+
+    let changes: Vec<String> = old.diff(&new).into_iter().map(|c| match c {
+        DomChange::Added(tag) => format!("+ {}", tag),
+        DomChange::Removed(tag) => format!("- {}", tag),
+        DomChange::TextChanged{old, new} => format!("text {} {}", old, new),
+        DomChange::AttributesChanged{old, new} => format!("attributes {} {}", old.get_tagname(), new.get_tagname()),
+    }).collect();
+
+    assert_eq!(changes, vec![
+        "text <b>World</b> <b>Earth</b>",
+        "+ <li>0</li>",
+        "- <li>2</li>",
+        "- <p>Bye</p>",
+        "+ <footer>Bye</footer>",
+    ]);
+
+    assert!(old.diff(&old).is_empty());
+    assert!(old.filter("ul").diff(&new.filter("ul")).len() == 2);
+
+    let changes = old.filter("div").diff(&parse_html("<html><body><div id='a' class='y' title='t'>Hi <b>World</b></div></body></html>").unwrap().filter("div"));
+    assert!(matches!(&changes[..], [DomChange::AttributesChanged{..}, DomChange::TextChanged{..}]));
+
+    let dom = parse_html("<p>A</p>").unwrap();
+    assert!(matches!(&dom.diff(&dom.filter("span"))[..], [DomChange::Removed(_)]));
+}
+
+// Test fingerprint method
+#[test]
+fn test_fingerprint_method() {
+
+    let dom = parse_html("<html><head><meta name='csrf-token' content='abc'></head><body><!-- a --><p class='x' title='a  b'>Tom &amp; Jerry</p><p>Two</p></body></html>").unwrap();
+
+    let same = parse_html("<html>\n<head><meta content='abc' name=\"csrf-token\"/></head>\n<BODY><P title='a b' class=x>Tom  &#38;\nJerry</P> <p>Two</p><!-- b --></BODY></html>").unwrap();
+    assert_eq!(dom.fingerprint(), same.fingerprint());
+    assert_eq!(dom.fingerprint(), dom.fingerprint());
+
+    let nested = parse_html("<html><head><meta name='csrf-token' content='abc'></head><body><p class='x' title='a  b'>Tom &amp; <b>Jerry</b></p><p>Two</p></body></html>").unwrap();
+    assert_ne!(dom.fingerprint(), nested.fingerprint());
+    assert_eq!(dom.filter("p").fingerprint(), dom.filter("p").fingerprint());
+    assert_ne!(dom.filter("p").fingerprint(), dom.filter("body").fingerprint());
+    assert_ne!(parse_html("<p>ab</p><p>c</p>").unwrap().fingerprint(), parse_html("<p>a</p><p>bc</p>").unwrap().fingerprint());
+
+    let token = parse_html("<html><head><meta name='csrf-token' content='abc'></head><body><p class='x' title='a  b' data-csrf-token='1'>Tom &amp; Jerry</p><p>Two</p></body></html>").unwrap();
+    assert_ne!(dom.fingerprint(), token.fingerprint());
+    let options = FingerprintOptions{ignore_dynamic_attributes: true, ..Default::default()};
+    assert_eq!(dom.fingerprint_with(options.clone()), token.fingerprint_with(options));
+    let options = FingerprintOptions{ignored_attributes: vec!["DATA-CSRF-TOKEN".to_string()], ..Default::default()};
+    assert_eq!(dom.fingerprint_with(options.clone()), token.fingerprint_with(options));
+}
+
+// Test visible_only option
+#[test]
+fn test_visible_only_option() {
+
+    let html = "<html><head><title>Title</title><style>p { color: red; }</style><script>var a = 1;</script></head>
+        <body><p>Shown</p><template><p>Template</p></template><div hidden><p>Hidden</p></div>
+        <p style='color: red; DISPLAY : none !important'>None</p><span style='visibility:hidden'>Invisible</span>
+        <span style='display: block'>Block</span><noscript>No script</noscript><img alt='Logo' style='display:none'></body></html>";
+
+    let dom = parse_html(html).unwrap();
+    let options = TextOptions{visible_only: true, separator: Some("|".to_string()), alt_text: true, ..Default::default()};
+
+    assert_eq!(dom.get_text_with(options.clone()), "Title|Shown|Block|No script");
+    assert_eq!(dom.filter("body").tag[0].get_text_with(options.clone()), "Shown|Block|No script");
+    assert_eq!(dom.filter("p").get_text_with(options.clone()), "Shown");
+    assert_eq!(dom.filter("script").tag[0].get_text_with(options.clone()), "");
+    assert!(dom.get_text_with(TextOptions{separator: Some("|".to_string()), ..Default::default()}).contains("var a = 1;|Shown|Template|Hidden|None|Invisible"));
+}
+
+// Test strip_boilerplate method
+#[test]
+fn test_strip_boilerplate_method() {
+
+    let html = "<html><head><title>Page</title></head><body class='has-sidebar cookies-accepted'>
+        <header class=site><nav><a href='/'>Home</a></nav></header><div role='Navigation'>Menu</div>
+        <main><article><header><h1>Title</h1></header><p>Text <a href='/x' class='social-link'>Tweet</a></p><footer>By Tom</footer></article></main>
+        <div class='sidebar'>Related</div><div class='shared-content'>Kept</div><form role='search'><input name='q'></form>
+        <div class='gdpr_banner'><p>Accept?</p></div><footer><p>Imprint</footer></body></html>";
+
+    let dom = parse_html(html).unwrap();
+    let stripped = dom.strip_boilerplate();
+    let options = TextOptions{separator: Some("|".to_string()), ..Default::default()};
+
+    assert_eq!(stripped.get_text_with(options.clone()), "Page|Title|Text|By Tom|Kept");
+    assert_eq!(stripped.filter("body").tag[0].get_attr_value("class"), "has-sidebar cookies-accepted");
+    assert_eq!(dom.filter("article").strip_boilerplate().get_text_with(options.clone()), "Title|Text|By Tom");
+    assert_eq!(dom.filter("article").strip_boilerplate().to_string(), "<article><header><h1>Title</h1></header><p>Text </p><footer>By Tom</footer></article>");
+    assert!(dom.filter("nav, aside").strip_boilerplate().is_empty());
+
+    let fragment = parse_fragment("<nav>Menu</nav><p>Text</p>").unwrap().strip_boilerplate();
+    assert_eq!(fragment.to_string(), "<p>Text</p>");
+}
+
+// Test FromDom derive macro
+#[cfg(feature = "derive")]
+#[test]
+fn test_from_dom_derive() {
+
+    #[derive(FromDom)]
+    struct Article {
+        #[select("h1", text)]
+        title: String,
+        #[select("time", attr = "datetime")]
+        date: Option<String>,
+        #[select("span.views", text)]
+        views: u32,
+        #[select("a.tag", text)]
+        tags: Vec<String>,
+        #[select("div.body", html)]
+        body: String,
+        #[select("div.comment")]
+        comments: Vec<Comment>,
+        #[select("aside")]
+        aside: Option<Comment>,
+        #[select("div.body")]
+        body_dom: Dom,
+        extra: Vec<u8>,
+    }
+
+    #[derive(FromDom)]
+    struct Comment {
+        #[select("b", text)]
+        author: String,
+        #[select("a[href]", attr = "href")]
+        link: Option<String>,
+    }
+
+    let html = "<html><body><h1>\n  Hello   <i>World</i> </h1><span class='views'> 42 </span><a class='tag'>rust</a><a class='tag'>html</a>
+        <div class='body'><p>Text</p></div><div class='comment'><b>Tom</b><a href='/tom'>Profile</a></div><div class='comment'><b>Ann</b></div></body></html>";
+
+    let article = Article::from_dom(&parse_html(html).unwrap()).unwrap();
+
+    assert_eq!(article.title, "Hello World");
+    assert_eq!(article.date, None);
+    assert_eq!(article.views, 42);
+    assert_eq!(article.tags, vec!["rust", "html"]);
+    assert_eq!(article.body, "<p>Text</p>");
+    assert_eq!(article.comments.len(), 2);
+    assert_eq!((article.comments[0].author.as_str(), article.comments[0].link.as_deref()), ("Tom", Some("/tom")));
+    assert_eq!((article.comments[1].author.as_str(), article.comments[1].link.as_deref()), ("Ann", None));
+    assert!(article.aside.is_none());
+    assert_eq!(article.body_dom.get_text(), "Text");
+    assert!(article.extra.is_empty());
+
+    let error = Article::from_dom(&parse_html("<html><body><h1>Title</h1></body></html>").unwrap()).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::NotFound);
+    assert!(error.to_string().contains("`views`"));
+
+    let error = Article::from_dom(&parse_html("<html><body><h1>Title</h1><span class='views'>many</span></body></html>").unwrap()).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+// Test text with non-ascii characters before child tags
+#[test]
+fn test_non_ascii_text() {
+
+    let dom = parse_html("<html><body><p>é<b>ü</b> – 日本<i>語</i><!-- ß --></p><div>Grüße</img>!</div></body></html>").unwrap();
+
+    assert_eq!(dom.filter("p").get_text(), "éü – 日本語");
+    assert_eq!(dom.filter("p").tag[0].get_text(), "éü – 日本語");
+    assert_eq!(dom.filter("div").get_text(), "Grüße!");
+    assert!(dom.get_text().contains("éü – 日本語Grüße!"));
+}
@@ -1,27 +1,116 @@
 pub (in crate) mod fetch;
+pub (in crate) mod lexer;
+pub (in crate) mod document;
 pub (in crate) mod text;
-mod innerhtml;
+pub (in crate) mod tree;
+pub (in crate) mod attributes;
+pub (in crate) mod xpath;
+pub (in crate) mod selector;
+pub (in crate) mod query;
+pub (in crate) mod metadata;
+pub (in crate) mod json;
+pub (in crate) mod table;
+pub (in crate) mod form;
+pub (in crate) mod items;
+pub (in crate) mod markdown;
+pub (in crate) mod render;
+pub (in crate) mod contacts;
+pub (in crate) mod breadcrumbs;
+pub (in crate) mod pagination;
+pub (in crate) mod diff;
+pub (in crate) mod fingerprint;
+pub (in crate) mod boilerplate;
+#[cfg(feature = "detect-language")]
+pub (in crate) mod language;
 mod tagnames;
 
+// Returns a tag-name without its namespace prefix, e.g. `path` for `svg:path`
+pub (crate) fn local_name(tagname: &str) -> &str {
+    match tagname.split_once(':') {
+        Some((_, local)) => local,
+        None => tagname
+    }
+}
+
+// An empty argument or "*" matches everything
+pub (crate) fn is_wildcard(arg: &str) -> bool {
+    arg.is_empty() || arg == "*"
+}
+
 pub trait Args {
-    fn extract(self) -> (&'static str, &'static str, &'static str);
+    fn extract(self) -> Vec<query::Query>;
+}
+
+// Empty entries of a list of tag-names are skipped, so `["h1", ""]` only matches `h1`.
+// A list of nothing but empty entries matches everything like a single empty argument.
+fn tag_names(names: &[&'static str]) -> Vec<query::Query> {
+
+    let queries: Vec<query::Query> = names.iter().filter(|t| !t.is_empty()).map(|t| query::Query::new(t, "", "")).collect();
+
+    if queries.is_empty() && !names.is_empty() {
+        return vec![query::Query::new("", "", "")];
+    }
+
+    queries
 }
 
 
 impl Args for &'static str {
-    fn extract(self) -> (&'static str, &'static str, &'static str) {
-        (self, "", "")
+    fn extract(self) -> Vec<query::Query> {
+        vec![query::Query::new(self, "", "")]
     }
 }
 
 impl Args for (&'static str, &'static str) {
-    fn extract(self) -> (&'static str, &'static str, &'static str) {
-        (self.0, self.1, "")
+    fn extract(self) -> Vec<query::Query> {
+        vec![query::Query::new(self.0, self.1, "")]
     }
 }
 
 impl Args for (&'static str, &'static str, &'static str) {
-    fn extract(self) -> (&'static str, &'static str, &'static str) {
-        (self.0, self.1, self.2)
+    fn extract(self) -> Vec<query::Query> {
+        vec![query::Query::new(self.0, self.1, self.2)]
+    }
+}
+
+impl Args for &[&'static str] {
+    fn extract(self) -> Vec<query::Query> {
+        tag_names(self)
+    }
+}
+
+impl<const N: usize> Args for &[&'static str; N] {
+    fn extract(self) -> Vec<query::Query> {
+        tag_names(self)
     }
-}
\ No newline at end of file
+}
+
+impl<const N: usize> Args for [&'static str; N] {
+    fn extract(self) -> Vec<query::Query> {
+        tag_names(&self)
+    }
+}
+
+impl Args for crate::Filter {
+    fn extract(self) -> Vec<query::Query> {
+        vec![query::Query::Selector(self.selector)]
+    }
+}
+
+impl Args for &crate::Filter {
+    fn extract(self) -> Vec<query::Query> {
+        vec![query::Query::Selector(self.selector.clone())]
+    }
+}
+
+impl Args for selector::Selector {
+    fn extract(self) -> Vec<query::Query> {
+        vec![query::Query::Selector(self)]
+    }
+}
+
+impl Args for &selector::Selector {
+    fn extract(self) -> Vec<query::Query> {
+        vec![query::Query::Selector(self.clone())]
+    }
+}
@@ -0,0 +1,85 @@
+// Splits the content of an opening tag like `<a href="/" class='x' hidden>`
+// into its attribute names and values. Attributes without a value get an empty value.
+
+pub (crate) fn parse(tagcontent: &str) -> Vec<(String, String)> {
+    split(tagcontent).0
+}
+
+// Returns whether the attributes of an opening tag are well-formed: every quoted value is closed and followed
+// by whitespace or the end of the tag, every `=` follows a name, names and unquoted values contain no quotes
+// and no attribute is given twice
+pub (crate) fn is_valid(tagcontent: &str) -> bool {
+
+    let (attributes, valid) = split(tagcontent);
+
+    valid && attributes.iter().enumerate().all(|(i, (name, _))| !attributes[..i].iter().any(|(n, _)| n.eq_ignore_ascii_case(name)))
+}
+
+fn split(tagcontent: &str) -> (Vec<(String, String)>, bool) {
+
+    let chars: Vec<char> = tagcontent.chars().collect();
+    let mut out: Vec<(String, String)> = vec![];
+    let mut valid = true;
+    let mut i = 0;
+
+    // skip the tag name
+    while i < chars.len() && chars[i] == '<' {
+        i += 1;
+    }
+    while i < chars.len() && !chars[i].is_whitespace() && chars[i] != '>' && chars[i] != '/' {
+        i += 1;
+    }
+
+    loop {
+        while i < chars.len() && (chars[i].is_whitespace() || chars[i] == '/') {
+            i += 1;
+        }
+        if i >= chars.len() || chars[i] == '>' {
+            break
+        }
+
+        let mut name = String::new();
+        while i < chars.len() && !chars[i].is_whitespace() && chars[i] != '=' && chars[i] != '>' && chars[i] != '/' {
+            name.push(chars[i]);
+            i += 1;
+        }
+
+        while i < chars.len() && chars[i].is_whitespace() {
+            i += 1;
+        }
+
+        let mut value = String::new();
+        if i < chars.len() && chars[i] == '=' {
+            i += 1;
+            while i < chars.len() && chars[i].is_whitespace() {
+                i += 1;
+            }
+            if i < chars.len() && (chars[i] == '"' || chars[i] == '\'') {
+                let quote = chars[i];
+                i += 1;
+                while i < chars.len() && chars[i] != quote {
+                    value.push(chars[i]);
+                    i += 1;
+                }
+                i += 1;
+                valid &= i < chars.len() && (chars[i].is_whitespace() || chars[i] == '/' || chars[i] == '>');
+            } else {
+                while i < chars.len() && !chars[i].is_whitespace() && chars[i] != '>' {
+                    value.push(chars[i]);
+                    i += 1;
+                }
+                valid &= !value.contains(['"', '\'', '<', '=', '`']);
+            }
+        }
+
+        valid &= !name.is_empty() && !name.contains(['"', '\'', '<']);
+
+        if !name.is_empty() {
+            out.push((name, value));
+        } else {
+            i += 1;
+        }
+    }
+
+    (out, valid)
+}
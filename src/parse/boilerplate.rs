@@ -0,0 +1,75 @@
+use crate::parse::document::Document;
+use crate::parse::local_name;
+use std::ops::Range;
+
+// Finds the parts of a page around its content, like navigation, headers and footers, sidebars, cookie banners and share buttons,
+// by their tag-names, their ARIA roles or the words of their classes and ids.
+
+// Tags that are part of the page around the content
+const TAGNAMES: [&str; 4] = ["nav", "header", "footer", "aside"];
+
+// Landmark roles of the page around the content
+const ROLES: [&str; 5] = ["navigation", "banner", "contentinfo", "complementary", "search"];
+
+// Words of classes and ids like `cookie-banner` or `share_buttons`
+const WORDS: [&str; 11] = ["cookie", "cookies", "consent", "gdpr", "share", "sharing", "social", "addthis", "sharethis", "sidebar", "navbar"];
+
+// Tags that hold the content and therefore are never removed
+const CONTENT_TAGNAMES: [&str; 4] = ["html", "body", "main", "article"];
+
+// Returns the html of the given range with the boilerplate among the given tags cut out, leaving the rest as it is written
+pub (crate) fn strip(document: &Document, range: Range<usize>, tags: Range<usize>) -> String {
+
+    let mut out = String::new();
+    let mut position = range.start;
+    let mut i = tags.start;
+
+    while i < tags.end {
+        match is_boilerplate(document, i) {
+            true => {
+                out.push_str(&document.html[position..document[i].start]);
+                position = document[i].end;
+                // the tags inside of it are gone as well
+                i = document[i].subtree_end;
+            },
+            false => i += 1
+        }
+    }
+
+    out.push_str(&document.html[position..range.end]);
+
+    out
+}
+
+fn is_boilerplate(document: &Document, index: usize) -> bool {
+
+    let tagname = local_name(document.tagname(index)).to_ascii_lowercase();
+
+    if CONTENT_TAGNAMES.contains(&tagname.as_str()) {
+        return false;
+    }
+
+    let ancestors = || std::iter::successors(document[index].parent, |p| document[*p].parent);
+
+    // headers and footers of articles hold their title, author or date
+    let in_article = || ancestors().any(|a| ["article", "main"].iter().any(|t| local_name(document.tagname(a)).eq_ignore_ascii_case(t)));
+
+    match tagname.as_str() {
+        "header" | "footer" => return !in_article(),
+        t if TAGNAMES.contains(&t) => return true,
+        _ => ()
+    }
+
+    let attrs = document.attributes(index);
+    let value = |name: &str| attrs.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, v)| v.to_ascii_lowercase()).unwrap_or_default();
+
+    if value("role").split_whitespace().any(|r| ROLES.contains(&r)) {
+        return true;
+    }
+
+    let words = format!("{} {}", value("class"), value("id"));
+
+    words.split(|c: char| c.is_whitespace() || c == '-' || c == '_').any(|w| WORDS.contains(&w))
+        // a tag holding the content, like `<div class="has-sidebar">` around the article, is not boilerplate
+        && !crate::parse::tree::descendants(document, index).any(|d| CONTENT_TAGNAMES.contains(&local_name(document.tagname(d)).to_ascii_lowercase().as_str()))
+}
@@ -0,0 +1,197 @@
+use crate::parse::document::Document;
+use crate::parse::items;
+use crate::parse::json::{self, Value};
+use crate::{Breadcrumb, Item, ItemValue};
+
+// Reads the breadcrumb trail of a page. A schema.org BreadcrumbList given as JSON-LD, microdata or RDFa is preferred,
+// as its items are given explicitly. Otherwise the links of a navigation labeled or classed as breadcrumb are taken.
+
+// Characters that separate breadcrumbs, like in `Home › Books`
+const SEPARATORS: [char; 10] = ['›', '»', '>', '/', '|', '·', '•', '→', '‹', '\\'];
+
+pub (crate) fn read(document: &Document) -> Vec<Breadcrumb> {
+
+    let trail = json_ld(document);
+    if !trail.is_empty() {
+        return trail;
+    }
+
+    let all: Vec<usize> = (0..document.len()).collect();
+
+    for syntax in [&items::MICRODATA, &items::RDFA] {
+        let list = items::read(document, &all, syntax).iter().find_map(find_item).map(item_trail);
+        if let Some(trail) = list.filter(|t| !t.is_empty()) {
+            return trail;
+        }
+    }
+
+    navigation(document)
+}
+
+// Returns the trail of the first BreadcrumbList given in a `<script type="application/ld+json">`
+fn json_ld(document: &Document) -> Vec<Breadcrumb> {
+
+    let scripts = (0..document.len()).filter(|i| document.tagname(*i).eq_ignore_ascii_case("script") && attr(document, *i, "type")
+        .is_some_and(|t| t.trim().eq_ignore_ascii_case("application/ld+json")));
+
+    for i in scripts {
+        let list = json::read(document.inner_html(i).trim()).and_then(|v| find_value(&v).cloned());
+        let trail = list.map(|l| value_trail(&l)).unwrap_or_default();
+        if !trail.is_empty() {
+            return trail;
+        }
+    }
+
+    vec![]
+}
+
+// Finds a BreadcrumbList anywhere in JSON-LD, which may be inside of a `@graph` or a property like `breadcrumb` of a page
+fn find_value(value: &Value) -> Option<&Value> {
+    match value {
+        Value::Object(members) => {
+            let types = value.get("@type").map(|t| t.items()).unwrap_or_default();
+            match types.iter().filter_map(|t| t.as_str()).any(|t| is(t, "BreadcrumbList")) {
+                true => Some(value),
+                false => members.iter().find_map(|(_, v)| find_value(v))
+            }
+        },
+        Value::Array(items) => items.iter().find_map(find_value),
+        _ => None
+    }
+}
+
+fn value_trail(list: &Value) -> Vec<Breadcrumb> {
+
+    let elements = list.get("itemListElement").map(|e| e.items()).unwrap_or_default();
+
+    let mut trail: Vec<(f64, Breadcrumb)> = elements.iter().filter_map(|element| {
+        // the item is the url of the page or an object describing the page
+        let item = element.get("item");
+        let name = element.get("name").or_else(|| item.and_then(|i| i.get("name"))).and_then(|n| n.as_str());
+        let url = item.and_then(|i| i.as_str().or_else(|| i.get("@id").or_else(|| i.get("url")).and_then(|u| u.as_str())));
+        let position = match element.get("position") {
+            Some(Value::Number(n)) => *n,
+            Some(Value::String(s)) => s.trim().parse().unwrap_or(f64::MAX),
+            _ => f64::MAX
+        };
+        breadcrumb(name.unwrap_or_default(), url).map(|b| (position, b))
+    }).collect();
+
+    trail.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    trail.into_iter().map(|(_, b)| b).collect()
+}
+
+// Finds a BreadcrumbList among microdata or RDFa items and the items nested in them
+fn find_item(item: &Item) -> Option<&Item> {
+
+    if item.kind.iter().any(|k| is(k, "BreadcrumbList")) {
+        return Some(item);
+    }
+
+    item.properties.iter().find_map(|(_, v)| match v {
+        ItemValue::Item(i) => find_item(i),
+        ItemValue::Text(_) => None
+    })
+}
+
+fn item_trail(list: &Item) -> Vec<Breadcrumb> {
+
+    let text = |item: &Item, name: &str| item.properties.iter().find_map(|(n, v)| match v {
+        ItemValue::Text(t) if is(n, name) => Some(t.clone()),
+        _ => None
+    });
+
+    let mut trail: Vec<(f64, Breadcrumb)> = list.properties.iter().filter_map(|(n, v)| match v {
+        ItemValue::Item(element) if is(n, "itemListElement") => {
+            let item = element.properties.iter().find(|(n, _)| is(n, "item")).map(|(_, v)| v);
+            // the item is a link to the page or an item describing the page
+            let (name, url) = match item {
+                Some(ItemValue::Text(url)) => (text(element, "name"), Some(url.clone())),
+                Some(ItemValue::Item(page)) => (text(element, "name").or_else(|| text(page, "name")), page.id.clone().or_else(|| text(page, "url"))),
+                None => (text(element, "name"), None)
+            };
+            let position = text(element, "position").and_then(|p| p.trim().parse().ok()).unwrap_or(f64::MAX);
+            breadcrumb(&name.unwrap_or_default(), url.as_deref()).map(|b| (position, b))
+        },
+        _ => None
+    }).collect();
+
+    trail.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    trail.into_iter().map(|(_, b)| b).collect()
+}
+
+// Reads the trail from the first tag labeled as breadcrumb by `aria-label`, or else the first tag with a class or id containing `breadcrumb`.
+// Every list item is a breadcrumb, or, if there are no list items, every link and the text following the last link.
+fn navigation(document: &Document) -> Vec<Breadcrumb> {
+
+    let contains = |i: usize, name: &str| attr(document, i, name).is_some_and(|v| v.to_lowercase().contains("breadcrumb"));
+
+    let container = (0..document.len()).find(|i| contains(*i, "aria-label"))
+        .or_else(|| (0..document.len()).find(|i| contains(*i, "class") || contains(*i, "id")));
+
+    let container = match container {
+        Some(v) => v,
+        None => return vec![]
+    };
+
+    let descendants = crate::parse::tree::descendants(document, container);
+    let named = |tagname: &str| descendants.clone().filter(|i| document.tagname(*i).eq_ignore_ascii_case(tagname)).collect::<Vec<usize>>();
+    let link = |i: usize| match document.tagname(i).eq_ignore_ascii_case("a") {
+        true => attr(document, i, "href"),
+        false => None
+    };
+
+    let list_items = named("li");
+
+    if !list_items.is_empty() {
+        return list_items.into_iter().filter_map(|li| {
+            let href = crate::parse::tree::descendants(document, li).find_map(link);
+            breadcrumb(&text(document, li), href.as_deref())
+        }).collect();
+    }
+
+    let links: Vec<usize> = descendants.clone().filter(|i| link(*i).is_some()).collect();
+
+    let mut trail: Vec<Breadcrumb> = links.iter().filter_map(|i| breadcrumb(&text(document, *i), link(*i).as_deref())).collect();
+
+    // the current page is usually given as text without a link after the last link
+    if let Some(last) = links.last() {
+        let all = text(document, container);
+        let last = text(document, *last);
+        if let Some(v) = all.rfind(&last).filter(|_| !last.is_empty()) {
+            trail.extend(breadcrumb(&all[v + last.len()..], None));
+        }
+    }
+
+    trail
+}
+
+// Returns a breadcrumb unless its name is empty once the separators around it are removed
+fn breadcrumb(name: &str, url: Option<&str>) -> Option<Breadcrumb> {
+
+    let name = name.trim_matches(|c: char| c.is_whitespace() || SEPARATORS.contains(&c));
+
+    match name.is_empty() {
+        true => None,
+        false => Some(Breadcrumb{name: name.to_string(), url: url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty())})
+    }
+}
+
+// Returns whether a type or property, which may be written as a full url like `https://schema.org/BreadcrumbList`
+// or with a prefix like `schema:BreadcrumbList`, has the given name
+fn is(value: &str, name: &str) -> bool {
+    value.rsplit(['/', ':', '#']).next() == Some(name)
+}
+
+// Returns the text of a tag with its whitespace collapsed
+fn text(document: &Document, index: usize) -> String {
+    let mut segments: Vec<String> = vec![];
+    document.text_segments(document.contents(index), false, &mut segments);
+    segments.concat().split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+fn attr(document: &Document, index: usize, name: &str) -> Option<String> {
+    document.attributes(index).into_iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, v)| v)
+}
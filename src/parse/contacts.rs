@@ -0,0 +1,102 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+// Finds email addresses and phone numbers in text and in the targets of `mailto:` and `tel:` links
+
+fn email_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"[A-Za-z0-9._%+\-]+@[A-Za-z0-9\-]+(?:\.[A-Za-z0-9\-]+)*\.[A-Za-z]{2,}").unwrap())
+}
+
+// Numbers in text have to start with `+`, `00`, `(` or `0` like international and local phone numbers,
+// so numbers like prices or years are not taken for phone numbers
+fn phone_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"(?:\+|\(|\b0)[0-9][0-9 ().\-/]{4,}[0-9]").unwrap())
+}
+
+fn date_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"^\(?[0-9]{1,4}[./\-][0-9]{1,2}[./\-][0-9]{1,4}\)?$").unwrap())
+}
+
+pub (crate) fn find(text: &str, links: &[String]) -> crate::Contacts {
+
+    let mut emails: Vec<String> = vec![];
+    let mut phones: Vec<String> = vec![];
+
+    for link in links {
+        let link = link.trim();
+        if let Some(addresses) = strip_scheme(link, "mailto:") {
+            // the addresses may be followed by a subject or other fields
+            let addresses = addresses.split('?').next().unwrap_or("");
+            for address in addresses.split(',') {
+                if let Some(v) = email_pattern().find(&crate::entities::decode(&address.replace("%40", "@"))) {
+                    push(&mut emails, v.as_str().to_lowercase());
+                }
+            }
+        } else if let Some(number) = strip_scheme(link, "tel:") {
+            if let Some(v) = normalize_phone(number) {
+                push(&mut phones, v);
+            }
+        }
+    }
+
+    for v in email_pattern().find_iter(text) {
+        push(&mut emails, v.as_str().to_lowercase());
+    }
+
+    for v in phone_pattern().find_iter(text) {
+        if date_pattern().is_match(v.as_str()) {
+            continue;
+        }
+        if let Some(v) = normalize_phone(v.as_str()) {
+            push(&mut phones, v);
+        }
+    }
+
+    crate::Contacts{emails, phones}
+}
+
+fn strip_scheme<'a>(link: &'a str, scheme: &str) -> Option<&'a str> {
+    match link.len() >= scheme.len() && link.is_char_boundary(scheme.len()) && link[..scheme.len()].eq_ignore_ascii_case(scheme) {
+        true => Some(&link[scheme.len()..]),
+        false => None
+    }
+}
+
+// Keeps only the digits of a phone number, with a leading `+` for international numbers given by `+` or `00`.
+// Returns None if the number has less than 7 or more than 15 digits, which phone numbers cannot have.
+fn normalize_phone(number: &str) -> Option<String> {
+
+    // an extension or other fields may follow the number in a `tel:` link
+    let number = number.split([';', '?']).next().unwrap_or("").trim();
+
+    // the `(0)` in international numbers like `+49 (0)30 1234567` is only dialed within the country
+    let number = match number.starts_with('+') || number.starts_with("00") {
+        true => number.replace("(0)", ""),
+        false => number.to_string()
+    };
+
+    let digits: String = number.chars().filter(|c| c.is_ascii_digit()).collect();
+
+    let (international, digits) = match digits.strip_prefix("00") {
+        Some(rest) if !number.starts_with('+') => (true, rest.to_string()),
+        _ => (number.starts_with('+'), digits)
+    };
+
+    if digits.len() < 7 || digits.len() > 15 {
+        return None;
+    }
+
+    match international {
+        true => Some(format!("+{}", digits)),
+        false => Some(digits)
+    }
+}
+
+fn push(list: &mut Vec<String>, value: String) {
+    if !list.contains(&value) {
+        list.push(value);
+    }
+}
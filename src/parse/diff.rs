@@ -0,0 +1,115 @@
+use crate::parse::document::{Content, Document};
+use crate::DomChange;
+use std::sync::Arc;
+
+// Compares two trees of tags. The children of two matching tags are lined up like the lines of two files in a diff:
+// identical tags are matched first and tags with the same tag-name and id next, keeping their order.
+// Tags without a match have been removed or added, matching tags are compared by their attributes and their own text
+// and then their children are compared the same way.
+
+pub (crate) fn diff(old: &Arc<Document>, old_tags: &[usize], new: &Arc<Document>, new_tags: &[usize]) -> Vec<DomChange> {
+    let mut out: Vec<DomChange> = vec![];
+    Diff{old, new}.siblings(old_tags, new_tags, &mut out);
+    out
+}
+
+struct Diff<'a> {
+    old: &'a Arc<Document>,
+    new: &'a Arc<Document>,
+}
+
+impl Diff<'_> {
+
+    fn siblings(&self, old_tags: &[usize], new_tags: &[usize], out: &mut Vec<DomChange>) {
+
+        let old_html: Vec<String> = old_tags.iter().map(|i| html(self.old, *i)).collect();
+        let new_html: Vec<String> = new_tags.iter().map(|i| html(self.new, *i)).collect();
+
+        // how well two tags match: identical tags count more than tags that are only alike
+        let score = |a: usize, b: usize| match old_html[a] == new_html[b] {
+            true => 3,
+            false if self.alike(old_tags[a], new_tags[b]) => 1,
+            false => 0
+        };
+
+        // best[a][b] is the highest total score of matching the tags from a and from b on
+        let mut best = vec![vec![0; new_tags.len() + 1]; old_tags.len() + 1];
+
+        for a in (0..old_tags.len()).rev() {
+            for b in (0..new_tags.len()).rev() {
+                best[a][b] = best[a + 1][b].max(best[a][b + 1]);
+                let s = score(a, b);
+                if s > 0 {
+                    best[a][b] = best[a][b].max(best[a + 1][b + 1] + s);
+                }
+            }
+        }
+
+        let (mut a, mut b) = (0, 0);
+
+        while a < old_tags.len() || b < new_tags.len() {
+
+            if a < old_tags.len() && b < new_tags.len() && score(a, b) > 0 && best[a][b] == best[a + 1][b + 1] + score(a, b) {
+                if old_html[a] != new_html[b] {
+                    self.tags(old_tags[a], new_tags[b], out);
+                }
+                a += 1;
+                b += 1;
+            } else if a < old_tags.len() && (b == new_tags.len() || best[a][b] == best[a + 1][b]) {
+                out.push(DomChange::Removed(crate::Tag{document: self.old.clone(), index: old_tags[a]}));
+                a += 1;
+            } else {
+                out.push(DomChange::Added(crate::Tag{document: self.new.clone(), index: new_tags[b]}));
+                b += 1;
+            }
+        }
+    }
+
+    // Compares two matching tags and their children
+    fn tags(&self, old: usize, new: usize, out: &mut Vec<DomChange>) {
+
+        let tag = |document: &Arc<Document>, index: usize| crate::Tag{document: document.clone(), index};
+
+        if attributes(self.old, old) != attributes(self.new, new) {
+            out.push(DomChange::AttributesChanged{old: tag(self.old, old), new: tag(self.new, new)});
+        }
+
+        if own_text(self.old, old) != own_text(self.new, new) {
+            out.push(DomChange::TextChanged{old: tag(self.old, old), new: tag(self.new, new)});
+        }
+
+        self.siblings(&self.old.children(old), &self.new.children(new), out);
+    }
+
+    fn alike(&self, old: usize, new: usize) -> bool {
+        let id = |document: &Document, index: usize| document.attributes(index).into_iter().find(|(n, _)| n.eq_ignore_ascii_case("id")).map(|(_, v)| v);
+        self.old.tagname(old).eq_ignore_ascii_case(self.new.tagname(new)) && id(self.old, old) == id(self.new, new)
+    }
+}
+
+fn html(document: &Document, index: usize) -> String {
+    let mut out = String::new();
+    document.write_html(index, &mut out);
+    out
+}
+
+// Returns the attributes of a tag ordered by their names, as their order does not matter
+fn attributes(document: &Document, index: usize) -> Vec<(String, String)> {
+    let mut attributes: Vec<(String, String)> = document.attributes(index).into_iter().map(|(n, v)| (n.to_ascii_lowercase(), v)).collect();
+    attributes.sort();
+    attributes
+}
+
+// Returns the text directly inside of a tag, leaving out the text of its children, with whitespace collapsed
+fn own_text(document: &Document, index: usize) -> String {
+
+    let mut segments: Vec<String> = vec![];
+
+    for c in document.contents(index) {
+        if let Content::Text(range) = c {
+            document.text_segments(vec![Content::Text(range)], false, &mut segments);
+        }
+    }
+
+    segments.join(" ").split_whitespace().collect::<Vec<&str>>().join(" ")
+}
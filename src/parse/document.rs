@@ -0,0 +1,354 @@
+use crate::parse::{attributes, fetch, text};
+use std::ops::{Index, Range};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// A parsed html-document. It keeps the html it was parsed from, so its tags only have to store
+// where they start and end instead of copies of their contents, and links every tag to its parent.
+// Tags are stored in document order in a single Vec, so the descendants of a tag directly follow it
+// and its children can be found by skipping from one subtree to the next.
+
+#[derive(Debug, Default)]
+pub (crate) struct Document {
+    pub (crate) html: String,
+    pub (crate) nodes: Vec<Node>,
+    // the distinct namespaces and tag-names of the tags, which every tag refers to by their positions
+    pub (crate) namespaces: Vec<String>,
+    pub (crate) tagnames: Vec<String>,
+    // the byte ranges of all comments including `<!--` and `-->`
+    pub (crate) comments: Vec<Range<usize>>,
+    // the byte range of the `<!DOCTYPE ...>` declaration
+    pub (crate) doctype: Option<Range<usize>>,
+    // the byte offsets at which the lines of the html start
+    pub (crate) lines: Vec<usize>,
+    // the problems found while parsing and the byte ranges of the tags they were found at
+    pub (crate) warnings: Vec<(crate::WarningKind, Range<usize>)>,
+    pub (crate) options: crate::ParseOptions,
+    // whether the html is a fragment like `<li>a</li><li>b</li>` rather than a whole document
+    pub (crate) fragment: bool,
+    pub (crate) id: DocumentId,
+}
+
+// A number that is different for every document created, so a NodeId can tell which document it belongs to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub (crate) struct DocumentId(u64);
+
+impl Default for DocumentId {
+    fn default() -> DocumentId {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        DocumentId(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+// A piece of the content of a tag
+pub (crate) enum Content {
+    Element(usize),
+    Text(Range<usize>),
+    Comment(Range<usize>),
+}
+
+#[derive(Debug)]
+pub (crate) struct Node {
+    pub (crate) depth: usize,
+    pub (crate) namespace: usize,
+    pub (crate) tagname: usize,
+    pub (crate) parent: Option<usize>,
+    // byte offsets of the opening tag, the inner html and the end of the closing tag in the html
+    pub (crate) start: usize,
+    pub (crate) inner: Range<usize>,
+    pub (crate) end: usize,
+    // the index following the last descendant
+    pub (crate) subtree_end: usize,
+}
+
+impl Document {
+
+    pub (crate) fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub (crate) fn tagname(&self, index: usize) -> &str {
+        &self.tagnames[self.nodes[index].tagname]
+    }
+
+    pub (crate) fn namespace(&self, index: usize) -> &str {
+        &self.namespaces[self.nodes[index].namespace]
+    }
+
+    // Returns the tags at the top of the document
+    pub (crate) fn roots(&self) -> Vec<usize> {
+        self.following(0, self.nodes.len())
+    }
+
+    // Returns the direct children of a tag
+    pub (crate) fn children(&self, index: usize) -> Vec<usize> {
+        self.following(index + 1, self.nodes[index].subtree_end)
+    }
+
+    // Returns the tags from `start` to `end` that are not nested in one another
+    fn following(&self, start: usize, end: usize) -> Vec<usize> {
+        let mut out: Vec<usize> = vec![];
+        let mut i = start;
+        while i < end {
+            out.push(i);
+            i = self.nodes[i].subtree_end;
+        }
+        out
+    }
+
+    // Returns the opening tag like `<a href="/">`
+    pub (crate) fn tagcontent(&self, index: usize) -> &str {
+        &self.html[self.nodes[index].start..self.nodes[index].inner.start]
+    }
+
+    pub (crate) fn inner_html(&self, index: usize) -> &str {
+        &self.html[self.nodes[index].inner.clone()]
+    }
+
+    pub (crate) fn outer_html(&self, index: usize) -> &str {
+        &self.html[self.nodes[index].start..self.nodes[index].end]
+    }
+
+    // Returns the text of a tag put together from the text inside of it and inside of the tags it contains, leaving out comments
+    pub (crate) fn text(&self, index: usize) -> String {
+        let mut segments: Vec<String> = vec![];
+        self.text_segments(self.contents(index), text::is_verbatim(self.tagname(index)), &mut segments);
+        segments.concat()
+    }
+
+    // Adds the pieces of text of the contents and the tags inside of them in document order, leaving out comments.
+    // Character references are decoded unless this was turned off or the text is `verbatim` like the content of `<script>`.
+    pub (crate) fn text_segments(&self, contents: Vec<Content>, verbatim: bool, out: &mut Vec<String>) {
+        self.text_segments_with(contents, verbatim, &crate::TextOptions::default(), out)
+    }
+
+    // Works like text_segments, but if `alt_text` is set, tags without text like images or icons
+    // are replaced by their `alt`, `aria-label` or `title` attribute, and if `visible_only` is set, hidden tags are left out
+    pub (crate) fn text_segments_with(&self, contents: Vec<Content>, verbatim: bool, options: &crate::TextOptions, out: &mut Vec<String>) {
+        for c in contents {
+            match c {
+                Content::Element(i) if options.visible_only && self.is_hidden(i) => (),
+                Content::Element(i) => {
+                    let start = out.len();
+                    self.text_segments_with(self.contents(i), text::is_verbatim(self.tagname(i)), options, out);
+                    if options.alt_text && out[start..].iter().all(|s| s.trim().is_empty()) {
+                        let attrs = self.attributes(i);
+                        let label = ["alt", "aria-label", "title"].iter()
+                            .find_map(|a| attrs.iter().find(|(n, v)| n.eq_ignore_ascii_case(a) && !v.trim().is_empty()));
+                        if let Some((_, label)) = label {
+                            out.push(label.clone());
+                        }
+                    }
+                },
+                Content::Text(range) => {
+                    for range in self.without_stray_tags(range) {
+                        match self.options.decode_text && !verbatim {
+                            true => out.push(crate::entities::decode(&self.html[range])),
+                            false => out.push(self.html[range].to_string())
+                        }
+                    }
+                },
+                Content::Comment(_) => ()
+            }
+        }
+    }
+
+    // Splits text at closing tags without an opening tag like `</img>`, which are kept in the text of the html but are not part of it
+    fn without_stray_tags(&self, range: Range<usize>) -> Vec<Range<usize>> {
+
+        let mut out: Vec<Range<usize>> = vec![];
+        let mut position = range.start;
+
+        let mut stray: Vec<&Range<usize>> = self.warnings.iter()
+            .filter(|(kind, span)| *kind == crate::WarningKind::StrayClosingTag && span.start >= range.start && span.end <= range.end)
+            .map(|(_, span)| span)
+            .collect();
+        stray.sort_by_key(|span| span.start);
+
+        for span in stray {
+            if span.start > position {
+                out.push(position..span.start);
+            }
+            position = position.max(span.end);
+        }
+
+        if range.end > position {
+            out.push(position..range.end);
+        }
+
+        out
+    }
+
+    // Whether browsers show a tag, which they do not if the tag or one of its ancestors is hidden
+    pub (crate) fn is_visible(&self, index: usize) -> bool {
+        !std::iter::successors(Some(index), |i| self.nodes[*i].parent).any(|i| self.is_hidden(i))
+    }
+
+    // Whether browsers do not show a tag: `<script>`, `<style>` and `<template>`, tags with a `hidden` attribute
+    // and tags hidden by `display: none` or `visibility: hidden` in their `style` attribute
+    pub (crate) fn is_hidden(&self, index: usize) -> bool {
+
+        if ["script", "style", "template"].iter().any(|t| crate::parse::local_name(self.tagname(index)).eq_ignore_ascii_case(t)) {
+            return true;
+        }
+
+        self.attributes(index).iter().any(|(n, v)| match n.to_ascii_lowercase().as_str() {
+            "hidden" => true,
+            "style" => text::hides(v),
+            _ => false
+        })
+    }
+
+    // Returns the text of the whole html
+    pub (crate) fn html_text(&self) -> String {
+        let mut segments: Vec<String> = vec![];
+        self.text_segments(self.top_contents(), false, &mut segments);
+        segments.concat()
+    }
+
+    // Returns the attributes of a tag with their values decoded unless this was turned off.
+    // If an attribute is given twice, only the first one is kept like browsers do.
+    pub (crate) fn attributes(&self, index: usize) -> Vec<(String, String)> {
+
+        let mut out: Vec<(String, String)> = vec![];
+
+        for (name, value) in attributes::parse(self.tagcontent(index)) {
+            if !out.iter().any(|(n, _)| n.eq_ignore_ascii_case(&name)) {
+                out.push((name, self.attr_value(value)));
+            }
+        }
+
+        out
+    }
+
+    // Decodes the character references of an attribute-value unless this was turned off
+    pub (crate) fn attr_value(&self, value: String) -> String {
+        match self.options.decode_attributes {
+            true => crate::entities::decode(&value),
+            false => value
+        }
+    }
+
+    // Returns the line and column of a byte offset, both starting at 1
+    pub (crate) fn position(&self, offset: usize) -> (usize, usize) {
+        let line = self.lines.partition_point(|l| *l <= offset);
+        let column = self.html[self.lines[line - 1]..offset].chars().count() + 1;
+        (line, column)
+    }
+
+    // Returns the text of a comment without `<!--` and `-->`
+    pub (crate) fn comment(&self, range: Range<usize>) -> &str {
+        let comment = &self.html[range];
+        let comment = comment.strip_prefix("<!--").unwrap_or(comment);
+        comment.strip_suffix("-->").unwrap_or(comment)
+    }
+
+    // Returns the children of a tag together with the text and comments between them in document order
+    pub (crate) fn contents(&self, index: usize) -> Vec<Content> {
+        self.contents_in(self.nodes[index].inner.clone(), self.children(index))
+    }
+
+    // Returns the tags at the top of the document together with the text and comments between them
+    pub (crate) fn top_contents(&self) -> Vec<Content> {
+        self.contents_in(0..self.html.len(), self.roots())
+    }
+
+    fn contents_in(&self, range: Range<usize>, children: Vec<usize>) -> Vec<Content> {
+
+        let mut out: Vec<Content> = vec![];
+        let mut position = range.start;
+
+        for c in children {
+            self.push_text(&mut out, position..self.nodes[c].start);
+            out.push(Content::Element(c));
+            position = self.nodes[c].end;
+        }
+
+        self.push_text(&mut out, position..range.end);
+
+        out
+    }
+
+    // Writes the html of the contents as it is, except for the tags, which are written by `write_html`
+    pub (crate) fn write_contents(&self, contents: Vec<Content>, out: &mut String) {
+        self.write_contents_with(contents, out, &|_, _, value| value)
+    }
+
+    // Works like write_contents, but passes every attribute-value to `f` together with the index of its tag
+    // and the attribute-name, and writes the value returned instead
+    pub (crate) fn write_contents_with(&self, contents: Vec<Content>, out: &mut String, f: &dyn Fn(usize, &str, String) -> String) {
+        for c in contents {
+            match c {
+                Content::Element(i) => self.write_html_with(i, out, f),
+                Content::Text(range) | Content::Comment(range) => out.push_str(&self.html[range]),
+            }
+        }
+    }
+
+    // Writes a tag and its contents as normalized html: attribute-values are put in double quotes,
+    // void tags and empty svg or MathML tags are closed by `/>` and closing tags that were left out are added
+    pub (crate) fn write_html(&self, index: usize, out: &mut String) {
+        self.write_html_with(index, out, &|_, _, value| value)
+    }
+
+    pub (crate) fn write_html_with(&self, index: usize, out: &mut String, f: &dyn Fn(usize, &str, String) -> String) {
+
+        let tagname = self.tagname(index);
+
+        out.push('<');
+        out.push_str(tagname);
+
+        let mut names: Vec<String> = vec![];
+
+        for (name, value) in attributes::parse(self.tagcontent(index)) {
+            if names.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+                continue;
+            }
+            let value = f(index, &name, value);
+            out.push(' ');
+            out.push_str(&name);
+            if !value.is_empty() {
+                out.push_str("=\"");
+                out.push_str(&value.replace('"', "&quot;"));
+                out.push('"');
+            }
+            names.push(name);
+        }
+
+        if fetch::check_void(tagname) || (!self.namespace(index).is_empty() && self.nodes[index].inner.is_empty()) {
+            out.push_str("/>");
+            return;
+        }
+
+        out.push('>');
+        self.write_contents_with(self.contents(index), out, f);
+        out.push_str("</");
+        out.push_str(tagname);
+        out.push('>');
+    }
+
+    // Splits the html between two tags into text and comments
+    fn push_text(&self, out: &mut Vec<Content>, range: Range<usize>) {
+
+        let mut position = range.start;
+
+        for comment in self.comments.iter().filter(|c| c.start >= range.start && c.end <= range.end) {
+            if comment.start > position {
+                out.push(Content::Text(position..comment.start));
+            }
+            out.push(Content::Comment(comment.clone()));
+            position = comment.end;
+        }
+
+        if range.end > position {
+            out.push(Content::Text(position..range.end));
+        }
+    }
+}
+
+impl Index<usize> for Document {
+    type Output = Node;
+
+    fn index(&self, index: usize) -> &Node {
+        &self.nodes[index]
+    }
+}
@@ -1,27 +1,29 @@
-use crate::parse::{tagnames, innerhtml};
-use slicestring::Slice;
+use crate::parse::tagnames;
+use crate::parse::attributes;
+use crate::parse::lexer::{self, Markup, Next};
+use crate::parse::document::{Document, Node};
+use std::ops::Range;
+use std::sync::Arc;
 
-pub fn fetch(html: String) -> crate::Dom  {
+pub fn fetch(html: String, options: crate::ParseOptions) -> crate::Dom  {
+    from_document(parse_with(html, options))
+}
 
-    let mut dm = crate::Dom::new();
-    let mut html_string = html.clone();
-    
-    loop {
+// Builds a Dom from a fragment, whose text and html are those of the whole fragment instead of its first tag
+pub fn fetch_fragment(html: String, options: crate::ParseOptions) -> crate::Dom  {
+    let mut document = parse_with(html, options);
+    document.fragment = true;
+    from_document(document)
+}
 
-        if !html_string.contains("<") {
-            break
-        }
+// Builds a Dom containing all tags of the document
+pub (crate) fn from_document(document: Document) -> crate::Dom {
 
-        let (tagname, tagcontent) = get_tagname_and_content(&html_string);
+    let document = Arc::new(document);
 
-        html_string=html_string[html_string.find(&tagcontent).unwrap()+tagcontent.len()..].to_string();
+    let indices = (0..document.len()).collect();
 
-        if check_tagname(&tagname) {
-            dm.tag.push(crate::Tag{tagname: tagname.clone(), tagcontent: tagcontent, innerhtml: innerhtml::get(&tagname, html_string.to_string()).replace(innerhtml::BREAK_SUBST, "<br>")});
-        }
-    }
-    
-    dm=clear_closing_tags(dm);
+    let mut dm = crate::Dom::select(document, indices);
 
     dm.is_parsed=true;
 
@@ -29,42 +31,276 @@ pub fn fetch(html: String) -> crate::Dom  {
 
 }
 
-fn clear_closing_tags(d: crate::Dom) -> crate::Dom {
-    let mut new = crate::Dom::new();
-    for i in 0..d.tag.len() {
-        if !d.tag[i].tagcontent.contains("</") {
-            new.tag.push(d.tag[i].clone());
+// What the parser finds in the html in document order, given by byte ranges in the html
+pub (crate) enum Found {
+    Doctype(Range<usize>),
+    Comment(Range<usize>),
+    // the html between tags and comments, including anything that is not a known tag
+    Text(Range<usize>),
+    // an opening tag, which does not stay open if it is a void or self-closing tag
+    Start{name: Range<usize>, tag: Range<usize>, open: bool},
+    // the end of the innermost open tag; the range of the closing tag is empty if the tag was closed implicitly
+    End{name: Range<usize>, tag: Range<usize>},
+    // a problem with the opening or closing tag at the range, which was worked around
+    Warning(crate::WarningKind, Range<usize>),
+}
+
+// The position of the scanner in the html, so it can go on when more html follows
+#[derive(Default)]
+pub (crate) struct Scanner {
+    // the names of the tags that have not been closed yet
+    open_tags: Vec<Range<usize>>,
+    position: usize,
+    // the end of the last tag or comment, where the text following it starts
+    text: usize,
+    started: bool,
+    // whether the innermost open tag contains raw text
+    raw: bool,
+}
+
+// Walks through the html and passes everything it finds to `f`. Every tag that stays open is ended by an `End`,
+// tags left open inside of a closed tag end right before its closing tag and tags that are never closed end with the html.
+pub (crate) fn scan<F: FnMut(Found)>(html: &str, f: F) {
+    Scanner::default().scan(html, true, f);
+}
+
+impl Scanner {
+
+    // Goes on from where the last call stopped. If the html is not `complete`, more of it may be appended,
+    // so the scanner stops at the first tag or comment that is not finished yet and keeps the text before it.
+    pub (crate) fn scan<F: FnMut(Found)>(&mut self, html: &str, complete: bool, mut f: F) {
+
+        loop {
+
+            // the content of a tag like `<script>` is text up to its closing tag
+            if self.raw {
+                let tagname = self.open_tags.last().map(|n| &html[n.clone()]).unwrap_or("");
+                match lexer::raw_text_end(html, self.position, tagname) {
+                    Some(v) => self.position = v,
+                    None if !complete => {
+                        // the closing tag may start with the last `<`
+                        self.position += html[self.position..].rfind('<').unwrap_or(html.len() - self.position);
+                        return;
+                    },
+                    None => self.position = html.len()
+                }
+                self.raw = false;
+            }
+
+            let (start, end) = match lexer::next(html, self.position, complete) {
+                Next::Markup(Markup::Tag(range)) => (range.start, range.end),
+                Next::Markup(Markup::Comment(range)) => {
+                    text_until(&mut f, self.text, range.start);
+                    self.position = range.end;
+                    self.text = range.end;
+                    f(Found::Comment(range));
+                    continue;
+                },
+                Next::Incomplete(start) => {
+                    self.position = start;
+                    return;
+                },
+                Next::End => break
+            };
+
+            self.position = end;
+
+            let tagcontent = &html[start..end];
+            let tagname = get_tagname(tagcontent);
+
+            if !self.started && tagname.eq_ignore_ascii_case("!doctype") {
+                self.started = true;
+                text_until(&mut f, self.text, start);
+                f(Found::Doctype(start..end));
+                self.text = end;
+                continue;
+            }
+
+            if let Some(closing) = tagname.strip_prefix('/') {
+                if let Some(v) = self.open_tags.iter().rposition(|n| html[n.clone()].eq_ignore_ascii_case(closing)) {
+                    text_until(&mut f, self.text, start);
+                    if v + 1 < self.open_tags.len() {
+                        f(Found::Warning(crate::WarningKind::MismatchedClosingTag, start..end));
+                    }
+                    // tags that were left open inside of the closed tag end right before its closing tag
+                    for name in self.open_tags.drain(v + 1..).rev() {
+                        f(Found::End{name, tag: start..start});
+                    }
+                    if let Some(name) = self.open_tags.pop() {
+                        f(Found::End{name, tag: start..end});
+                    }
+                    self.text = end;
+                } else if check_tagname(closing) {
+                    f(Found::Warning(crate::WarningKind::StrayClosingTag, start..end));
+                }
+                continue;
+            }
+
+            if !check_tagname(&tagname) {
+                continue;
+            }
+
+            self.started = true;
+
+            let name = start + 1..start + 1 + tagname.len();
+            let open = !check_void(&tagname) && !tagcontent.ends_with("/>");
+
+            text_until(&mut f, self.text, start);
+            if !attributes::is_valid(tagcontent) {
+                f(Found::Warning(crate::WarningKind::InvalidAttributes, start..end));
+            }
+            f(Found::Start{name: name.clone(), tag: start..end, open});
+            self.text = end;
+
+            if open {
+                self.open_tags.push(name);
+                self.raw = check_raw_text(&tagname);
+            }
+        }
+
+        if !complete {
+            self.position = html.len();
+            return;
         }
-     }
-     new
+
+        text_until(&mut f, self.text, html.len());
+        self.text = html.len();
+
+        // tags that are never closed end with the html
+        for name in std::mem::take(&mut self.open_tags).into_iter().rev() {
+            f(Found::End{name, tag: html.len()..html.len()});
+        }
+    }
 }
 
-pub (super) fn get_tagname_and_content(html: &str) -> (String, String)  {
+// Passes the text from the end of the last tag up to `start` if there is any
+fn text_until<F: FnMut(Found)>(f: &mut F, text: usize, start: usize) {
+    if start > text {
+        f(Found::Text(text..start));
+    }
+}
 
-    let mut tagcontent = html.to_string().slice(html.find("<").unwrap(), html.len());
+// Builds a document from what the scanner finds, linking every tag to its parent
+// and storing where its opening tag, inner html and closing tag are located in the html
+pub (crate) struct Builder {
+    scanner: Scanner,
+    document: Document,
+    // the indices of the tags that have not been closed yet
+    open_tags: Vec<usize>,
+}
 
-    tagcontent = tagcontent.slice(0, tagcontent.find(">").unwrap()+1);
+impl Builder {
+
+    pub (crate) fn new(options: crate::ParseOptions) -> Builder {
+        Builder{scanner: Scanner::default(), document: Document{namespaces: vec![String::new()], options, ..Default::default()}, open_tags: vec![]}
+    }
 
-    let mut tagname = String::new();
+    // Adds the tags of the html that have not been added yet. The html has to start with the html passed before.
+    pub (crate) fn feed(&mut self, html: &str, complete: bool) {
 
-    if tagcontent.contains(" ") {
-        tagname = tagcontent.slice(1, tagcontent.find(" ").unwrap()).to_string();
-    } else {
-        tagname = match tagcontent.find(">") {
-            Some(v) => tagcontent.slice(1, v).to_string(),
-            None => tagname
-        };
+        let Builder{scanner, document, open_tags} = self;
+
+        scanner.scan(html, complete, |found| match found {
+            Found::Doctype(range) => document.doctype = Some(range),
+            Found::Comment(range) => document.comments.push(range),
+            Found::Text(_) => (),
+            Found::Warning(kind, range) => document.warnings.push((kind, range)),
+            Found::Start{name, tag, open} => {
+                let index = document.nodes.len();
+                let parent = open_tags.last().copied();
+                let namespace = get_namespace(&html[name.clone()], parent.map(|p| (document.tagname(p), document.namespace(p))));
+                // the tag-names of html tags are lowercased unless this was turned off, svg and MathML tag-names keep their case
+                let tagname = match document.options.lowercase_tagnames && namespace.is_empty() {
+                    true => html[name.clone()].to_ascii_lowercase(),
+                    false => html[name.clone()].to_string()
+                };
+                let namespace = intern(&mut document.namespaces, namespace);
+                let tagname = intern(&mut document.tagnames, tagname);
+                document.nodes.push(Node{
+                    depth: open_tags.len(),
+                    namespace,
+                    tagname,
+                    parent,
+                    start: tag.start,
+                    inner: tag.end..tag.end,
+                    end: tag.end,
+                    subtree_end: index + 1,
+                });
+                if open {
+                    open_tags.push(index);
+                }
+            },
+            Found::End{tag, ..} => {
+                if let Some(i) = open_tags.pop() {
+                    let subtree_end = document.nodes.len();
+                    let node = &mut document.nodes[i];
+                    // the closing tag is missing
+                    if tag.is_empty() {
+                        document.warnings.push((crate::WarningKind::UnclosedTag, node.start..node.inner.start));
+                    }
+                    node.inner.end = tag.start;
+                    node.end = tag.end;
+                    node.subtree_end = subtree_end;
+                }
+            },
+        });
     }
 
-    (tagname, tagcontent)
+    // Finishes the document with the whole html
+    pub (crate) fn finish(mut self, html: String) -> Document {
+
+        self.feed(&html, true);
+
+        self.document.lines = std::iter::once(0).chain(html.match_indices('\n').map(|(i, _)| i + 1)).collect();
+
+        self.document.html = html;
 
+        self.document
+    }
+}
+
+// Returns the position of a string in a list of distinct strings, adding it if it is not there yet
+fn intern(list: &mut Vec<String>, value: String) -> usize {
+    match list.iter().position(|v| *v == value) {
+        Some(v) => v,
+        None => {
+            list.push(value);
+            list.len() - 1
+        }
+    }
+}
+
+// Parses the html into a document
+pub (crate) fn parse(html: String) -> Document {
+    parse_with(html, crate::ParseOptions::default())
+}
+
+pub (crate) fn parse_with(html: String, options: crate::ParseOptions) -> Document {
+    Builder::new(options).finish(html)
+}
+
+// Returns the name of a tag from its opening or closing tag, e.g. `div` for `<div class='a'>`
+pub (crate) fn get_tagname(tagcontent: &str) -> String {
+
+    let tagname = tagcontent[1..].split(|c: char| c.is_whitespace() || c == '>').next().unwrap_or("");
+
+    if tagname.len() > 1 {
+        return tagname.trim_end_matches('/').to_string();
+    }
+
+    tagname.to_string()
 }
 
 pub (super) fn check_tagname(tagname: &str) -> bool {
 
-    for n in tagnames::TAGNAMES {
+    // tag-names with a namespace prefix like `svg:path`
+    if let Some((prefix, local)) = tagname.split_once(':') {
+        return is_name(prefix) && is_name(local);
+    }
+
+    for n in tagnames::TAGNAMES.iter().chain(tagnames::SVG_TAGNAMES.iter()).chain(tagnames::MATHML_TAGNAMES.iter()) {
 
-        if tagname == n {
+        if tagname.eq_ignore_ascii_case(n) {
             return true;
         }
 
@@ -73,3 +309,44 @@ pub (super) fn check_tagname(tagname: &str) -> bool {
     false
 }
 
+fn is_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic()) && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+}
+
+// Returns the namespace of a tag: its prefix, `svg` or `math` for tags inside of inline svg and MathML content
+// or an empty string for html
+// The parent is given by its tag-name and namespace
+fn get_namespace(tagname: &str, parent: Option<(&str, &str)>) -> String {
+
+    if let Some((prefix, _)) = tagname.split_once(':') {
+        return prefix.to_lowercase();
+    }
+
+    if tagname.eq_ignore_ascii_case("svg") || tagname.eq_ignore_ascii_case("math") {
+        return tagname.to_lowercase();
+    }
+
+    match parent {
+        // html content embedded into svg
+        Some((name, _)) if name.eq_ignore_ascii_case("foreignObject") || name.ends_with(":foreignObject") => String::new(),
+        Some((_, namespace)) => namespace.to_string(),
+        None => String::new()
+    }
+}
+
+pub (crate) fn check_raw_text(tagname: &str) -> bool {
+    tagnames::RAW_TEXT_TAGNAMES.iter().any(|n| tagname.eq_ignore_ascii_case(n))
+}
+
+pub (crate) fn check_void(tagname: &str) -> bool {
+
+    for n in tagnames::VOID_TAGNAMES {
+
+        if tagname.eq_ignore_ascii_case(n) {
+            return true;
+        }
+
+    }
+
+    false
+}
@@ -0,0 +1,78 @@
+use crate::FingerprintOptions;
+use crate::parse::document::{Content, Document};
+
+// Hashes the normalized contents of tags, so the hash stays the same as long as the content does.
+// Runs of whitespace count as one space and whitespace between tags does not count, attributes are hashed ordered by their names
+// and comments are left out. The hash is FNV-1a, which unlike the hasher of the standard library is the same in every Rust version.
+
+const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const PRIME: u64 = 0x0100_0000_01b3;
+
+// Parts of the names of attributes whose values change on every request, like `nonce` or `data-csrf-token`
+const DYNAMIC_ATTRIBUTES: [&str; 3] = ["nonce", "csrf", "token"];
+
+pub (crate) fn hash(document: &Document, contents: Vec<Content>, options: &FingerprintOptions) -> u64 {
+    let mut hasher = Hasher{document, options, hash: OFFSET};
+    hasher.contents(contents);
+    hasher.hash
+}
+
+struct Hasher<'a> {
+    document: &'a Document,
+    options: &'a FingerprintOptions,
+    hash: u64,
+}
+
+impl Hasher<'_> {
+
+    fn contents(&mut self, contents: Vec<Content>) {
+        for c in contents {
+            match c {
+                Content::Element(i) => self.element(i),
+                Content::Text(range) => {
+                    let mut segments: Vec<String> = vec![];
+                    self.document.text_segments(vec![Content::Text(range)], false, &mut segments);
+                    let text = segments.concat().split_whitespace().collect::<Vec<&str>>().join(" ");
+                    if !text.is_empty() {
+                        self.write("#text");
+                        self.write(&text);
+                    }
+                },
+                Content::Comment(_) => ()
+            }
+        }
+    }
+
+    fn element(&mut self, index: usize) {
+
+        self.write(&self.document.tagname(index).to_ascii_lowercase());
+
+        let mut attributes: Vec<(String, String)> = self.document.attributes(index).into_iter()
+            .map(|(n, v)| (n.to_ascii_lowercase(), v.split_whitespace().collect::<Vec<&str>>().join(" ")))
+            .filter(|(n, _)| !self.ignored(n))
+            .collect();
+        attributes.sort();
+
+        for (name, value) in attributes {
+            self.write(&name);
+            self.write(&value);
+        }
+
+        self.write("#contents");
+        self.contents(self.document.contents(index));
+        self.write("#end");
+    }
+
+    fn ignored(&self, name: &str) -> bool {
+        self.options.ignored_attributes.iter().any(|a| a.eq_ignore_ascii_case(name))
+            || (self.options.ignore_dynamic_attributes && DYNAMIC_ATTRIBUTES.iter().any(|d| name.contains(d)))
+    }
+
+    // Hashes a string followed by a byte that cannot be part of UTF-8, so `ab` and `c` hash differently than `a` and `bc`
+    fn write(&mut self, s: &str) {
+        for b in s.bytes().chain(std::iter::once(0xff)) {
+            self.hash ^= b as u64;
+            self.hash = self.hash.wrapping_mul(PRIME);
+        }
+    }
+}
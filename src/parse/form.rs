@@ -0,0 +1,60 @@
+use crate::parse::document::Document;
+
+// Reads a html form: where and how it is sent and the fields it sends with their default values
+
+pub (crate) fn read(document: &Document, index: usize) -> crate::Form {
+
+    let method = match attr(document, index, "method") {
+        Some(v) if v.trim().eq_ignore_ascii_case("post") => "POST",
+        _ => "GET"
+    };
+
+    // fields without a name are not sent, so they are left out
+    let fields = (index + 1..document[index].subtree_end)
+        .filter(|i| attr(document, *i, "name").is_some())
+        .filter_map(|i| field(document, i))
+        .collect();
+
+    crate::Form{action: attr(document, index, "action").unwrap_or_default(), method: method.to_string(), fields}
+}
+
+fn field(document: &Document, index: usize) -> Option<crate::FormField> {
+
+    let tagname = document.tagname(index).to_ascii_lowercase();
+    let name = attr(document, index, "name").unwrap_or_default();
+
+    let field = match tagname.as_str() {
+        "input" => {
+            let kind = attr(document, index, "type").map(|t| t.trim().to_ascii_lowercase()).filter(|t| !t.is_empty()).unwrap_or_else(|| "text".to_string());
+            // checkboxes and radio buttons without a value send `on`
+            let value = match attr(document, index, "value") {
+                Some(v) => v,
+                None if kind == "checkbox" || kind == "radio" => "on".to_string(),
+                None => String::new()
+            };
+            crate::FormField{name, checked: attr(document, index, "checked").is_some(), kind, value, options: vec![]}
+        },
+        "select" => {
+            let options: Vec<(usize, String)> = (index + 1..document[index].subtree_end)
+                .filter(|i| document.tagname(*i).eq_ignore_ascii_case("option"))
+                .map(|i| (i, attr(document, i, "value").unwrap_or_else(|| text(document, i))))
+                .collect();
+            // the first option is selected unless another one is selected in the html
+            let value = options.iter().find(|(i, _)| attr(document, *i, "selected").is_some()).or(options.first()).map(|(_, v)| v.clone()).unwrap_or_default();
+            crate::FormField{name, kind: "select".to_string(), value, checked: false, options: options.into_iter().map(|(_, v)| v).collect()}
+        },
+        "textarea" => crate::FormField{name, kind: "textarea".to_string(), value: document.text(index), checked: false, options: vec![]},
+        _ => return None
+    };
+
+    Some(field)
+}
+
+fn attr(document: &Document, index: usize, name: &str) -> Option<String> {
+    document.attributes(index).into_iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, v)| v)
+}
+
+// The text of an option without the whitespace browsers strip from it
+fn text(document: &Document, index: usize) -> String {
+    document.text(index).split_whitespace().collect::<Vec<&str>>().join(" ")
+}
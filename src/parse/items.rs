@@ -0,0 +1,106 @@
+use crate::parse::document::Document;
+use crate::{Item, ItemValue};
+
+// Reads structured data given by microdata or RDFa attributes into trees of items. Both work alike: an item is started
+// by a tag with a certain attribute and its properties are the named tags inside of it, up to the tags starting items of their own.
+
+pub (crate) struct Syntax {
+    // the attribute that starts an item
+    scope: &'static str,
+    // the attribute giving the names of a property
+    property: &'static str,
+    // the attribute giving the types of an item
+    kind: &'static str,
+    // the attributes giving the identifier of an item
+    id: &'static [&'static str],
+    // the attributes holding the value of a property instead of its text, by tag-name, where an empty tag-name matches every tag
+    values: &'static [(&'static str, &'static str)],
+}
+
+pub (crate) const MICRODATA: Syntax = Syntax{
+    scope: "itemscope",
+    property: "itemprop",
+    kind: "itemtype",
+    id: &["itemid"],
+    values: &[
+        ("meta", "content"),
+        ("audio", "src"), ("embed", "src"), ("iframe", "src"), ("img", "src"), ("source", "src"), ("track", "src"), ("video", "src"),
+        ("a", "href"), ("area", "href"), ("link", "href"),
+        ("object", "data"),
+        ("data", "value"), ("meter", "value"),
+        ("time", "datetime"),
+    ],
+};
+
+pub (crate) const RDFA: Syntax = Syntax{
+    scope: "typeof",
+    property: "property",
+    kind: "typeof",
+    id: &["resource", "about"],
+    values: &[("", "content"), ("", "resource"), ("", "href"), ("", "src"), ("time", "datetime")],
+};
+
+// Returns the items among the given tags that are not properties of other items
+pub (crate) fn read(document: &Document, indices: &[usize], syntax: &Syntax) -> Vec<Item> {
+    indices.iter()
+        .filter(|i| has(document, **i, syntax.scope))
+        .filter(|i| !has(document, **i, syntax.property) || !ancestors(document, **i).any(|a| has(document, a, syntax.scope)))
+        .map(|i| item(document, *i, syntax))
+        .collect()
+}
+
+fn item(document: &Document, index: usize, syntax: &Syntax) -> Item {
+
+    let kind = attr(document, index, syntax.kind).map(|v| v.split_whitespace().map(|t| t.to_string()).collect()).unwrap_or_default();
+    let id = syntax.id.iter().find_map(|a| attr(document, index, a));
+
+    let mut properties: Vec<(String, ItemValue)> = vec![];
+    collect(document, index, syntax, &mut properties);
+
+    Item{kind, id, properties}
+}
+
+// Adds the properties inside of a tag, leaving out the contents of tags that start items of their own
+fn collect(document: &Document, index: usize, syntax: &Syntax, out: &mut Vec<(String, ItemValue)>) {
+
+    for child in document.children(index) {
+
+        let scope = has(document, child, syntax.scope);
+
+        if let Some(names) = attr(document, child, syntax.property) {
+            let value = match scope {
+                true => ItemValue::Item(item(document, child, syntax)),
+                false => ItemValue::Text(value(document, child, syntax))
+            };
+            for name in names.split_whitespace() {
+                out.push((name.to_string(), value.clone()));
+            }
+        }
+
+        if !scope {
+            collect(document, child, syntax, out);
+        }
+    }
+}
+
+fn value(document: &Document, index: usize, syntax: &Syntax) -> String {
+
+    let tagname = document.tagname(index);
+
+    syntax.values.iter()
+        .filter(|(t, _)| t.is_empty() || tagname.eq_ignore_ascii_case(t))
+        .find_map(|(_, a)| attr(document, index, a))
+        .unwrap_or_else(|| document.text(index).split_whitespace().collect::<Vec<&str>>().join(" "))
+}
+
+fn ancestors(document: &Document, index: usize) -> impl Iterator<Item = usize> + '_ {
+    std::iter::successors(document[index].parent, move |p| document[*p].parent)
+}
+
+fn has(document: &Document, index: usize, name: &str) -> bool {
+    attr(document, index, name).is_some()
+}
+
+fn attr(document: &Document, index: usize, name: &str) -> Option<String> {
+    document.attributes(index).into_iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, v)| v)
+}
@@ -0,0 +1,273 @@
+use crate::parse::document::Document;
+
+// Writes tags as a JSON tree and reads JSON embedded in pages, like JSON-LD, without depending on a JSON library.
+// Every tag becomes an object with its tag-name, its attributes, its text and its child tags.
+
+// A JSON value, whose objects keep their members in the order they are written
+#[derive(Clone, Debug, PartialEq)]
+pub (crate) enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+
+    // Returns the value of the first member with the given key if this is an object
+    pub (crate) fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None
+        }
+    }
+
+    pub (crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None
+        }
+    }
+
+    // Returns the elements of an array, or the value itself as the only element, as JSON-LD allows for single values
+    pub (crate) fn items(&self) -> &[Value] {
+        match self {
+            Value::Array(items) => items,
+            v => std::slice::from_ref(v)
+        }
+    }
+}
+
+// Reads a JSON document, returning None if it is not valid JSON
+pub (crate) fn read(json: &str) -> Option<Value> {
+
+    let mut reader = Reader{json, position: 0};
+
+    let value = reader.value()?;
+
+    reader.whitespace();
+
+    match reader.position == json.len() {
+        true => Some(value),
+        false => None
+    }
+}
+
+pub (crate) fn write_tags(document: &Document, indices: &[usize], out: &mut String) {
+
+    out.push('[');
+
+    for (n, i) in indices.iter().enumerate() {
+        if n > 0 {
+            out.push(',');
+        }
+        write_tag(document, *i, out);
+    }
+
+    out.push(']');
+}
+
+fn write_tag(document: &Document, index: usize, out: &mut String) {
+
+    out.push_str("{\"name\":");
+    write_string(document.tagname(index), out);
+
+    out.push_str(",\"attributes\":{");
+    for (n, (name, value)) in document.attributes(index).iter().enumerate() {
+        if n > 0 {
+            out.push(',');
+        }
+        write_string(name, out);
+        out.push(':');
+        write_string(value, out);
+    }
+
+    out.push_str("},\"text\":");
+    write_string(&document.text(index), out);
+
+    out.push_str(",\"children\":");
+    write_tags(document, &document.children(index), out);
+
+    out.push('}');
+}
+
+fn write_string(s: &str, out: &mut String) {
+
+    out.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c)
+        }
+    }
+
+    out.push('"');
+}
+
+struct Reader<'a> {
+    json: &'a str,
+    position: usize,
+}
+
+impl Reader<'_> {
+
+    fn value(&mut self) -> Option<Value> {
+
+        self.whitespace();
+
+        match self.peek()? {
+            '{' => self.object(),
+            '[' => self.array(),
+            '"' => self.string().map(Value::String),
+            't' => self.literal("true", Value::Bool(true)),
+            'f' => self.literal("false", Value::Bool(false)),
+            'n' => self.literal("null", Value::Null),
+            _ => self.number()
+        }
+    }
+
+    fn object(&mut self) -> Option<Value> {
+
+        self.position += 1;
+
+        let mut members: Vec<(String, Value)> = vec![];
+
+        self.whitespace();
+        if self.eat('}') {
+            return Some(Value::Object(members));
+        }
+
+        loop {
+            self.whitespace();
+            let key = self.string()?;
+            self.whitespace();
+            if !self.eat(':') {
+                return None;
+            }
+            members.push((key, self.value()?));
+            self.whitespace();
+            if self.eat('}') {
+                return Some(Value::Object(members));
+            }
+            if !self.eat(',') {
+                return None;
+            }
+        }
+    }
+
+    fn array(&mut self) -> Option<Value> {
+
+        self.position += 1;
+
+        let mut items: Vec<Value> = vec![];
+
+        self.whitespace();
+        if self.eat(']') {
+            return Some(Value::Array(items));
+        }
+
+        loop {
+            items.push(self.value()?);
+            self.whitespace();
+            if self.eat(']') {
+                return Some(Value::Array(items));
+            }
+            if !self.eat(',') {
+                return None;
+            }
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+
+        if !self.eat('"') {
+            return None;
+        }
+
+        let mut out = String::new();
+
+        loop {
+            let c = self.next()?;
+            match c {
+                '"' => return Some(out),
+                '\\' => match self.next()? {
+                    'n' => out.push('\n'),
+                    't' => out.push('\t'),
+                    'r' => out.push('\r'),
+                    'b' => out.push('\u{8}'),
+                    'f' => out.push('\u{c}'),
+                    'u' => {
+                        let mut code = self.hex()?;
+                        // characters outside of the basic multilingual plane are written as surrogate pairs
+                        if (0xd800..0xdc00).contains(&code) && self.json[self.position..].starts_with("\\u") {
+                            self.position += 2;
+                            let low = self.hex()?;
+                            code = 0x10000 + ((code - 0xd800) << 10) + (low.checked_sub(0xdc00)? & 0x3ff);
+                        }
+                        out.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                    },
+                    c => out.push(c)
+                },
+                c => out.push(c)
+            }
+        }
+    }
+
+    fn hex(&mut self) -> Option<u32> {
+        let digits = self.json.get(self.position..self.position + 4)?;
+        self.position += 4;
+        u32::from_str_radix(digits, 16).ok()
+    }
+
+    fn number(&mut self) -> Option<Value> {
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')) {
+            self.position += 1;
+        }
+        self.json[start..self.position].parse().ok().map(Value::Number)
+    }
+
+    fn literal(&mut self, literal: &str, value: Value) -> Option<Value> {
+        match self.json[self.position..].starts_with(literal) {
+            true => {
+                self.position += literal.len();
+                Some(value)
+            },
+            false => None
+        }
+    }
+
+    fn whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.position += 1;
+        }
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        match self.peek() == Some(c) {
+            true => {
+                self.position += 1;
+                true
+            },
+            false => false
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.json[self.position..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+}
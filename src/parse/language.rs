@@ -0,0 +1,60 @@
+// Guesses the language of a text that does not declare it. Texts in scripts used by a single language or a few related ones,
+// like Greek, Hangul or Cyrillic, are recognized by their characters. Texts in Latin script are recognized by the
+// frequency of the most common words of each language, which make up a large part of any text.
+
+// Languages written in Latin script and their most common words
+const COMMON_WORDS: [(&str, [&str; 15]); 7] = [
+    ("en", ["the", "and", "of", "to", "is", "in", "that", "it", "for", "with", "was", "on", "are", "this", "you"]),
+    ("de", ["der", "die", "und", "das", "ist", "nicht", "ein", "eine", "zu", "den", "mit", "sich", "auf", "für", "ich"]),
+    ("fr", ["le", "la", "les", "et", "des", "est", "un", "une", "du", "que", "pour", "dans", "pas", "sur", "qui"]),
+    ("es", ["el", "la", "los", "las", "y", "de", "que", "en", "es", "un", "una", "por", "con", "para", "del"]),
+    ("it", ["il", "la", "di", "che", "e", "è", "per", "un", "una", "non", "sono", "del", "della", "con", "gli"]),
+    ("pt", ["o", "a", "os", "as", "de", "que", "e", "é", "do", "da", "em", "um", "uma", "para", "não"]),
+    ("nl", ["de", "het", "een", "en", "van", "is", "dat", "niet", "op", "te", "zijn", "met", "voor", "ik", "je"]),
+];
+
+// Returns the ISO 639-1 code of the language of the text, or None if the text is too short or the language is unknown
+pub (crate) fn guess(text: &str) -> Option<String> {
+
+    if let Some(v) = by_script(text) {
+        return Some(v.to_string());
+    }
+
+    let words: Vec<String> = text.split(|c: char| !c.is_alphabetic()).filter(|w| !w.is_empty()).map(|w| w.to_lowercase()).collect();
+
+    let (language, hits) = COMMON_WORDS.iter()
+        .map(|(language, common)| (language, words.iter().filter(|w| common.contains(&w.as_str())).count()))
+        .max_by_key(|(_, hits)| *hits)?;
+
+    // a few common words may appear in any text, like names or quotes in another language
+    match hits >= 3 && hits * 10 >= words.len() {
+        true => Some(language.to_string()),
+        false => None
+    }
+}
+
+// Returns the language of a text written mostly in a script other than Latin
+fn by_script(text: &str) -> Option<&'static str> {
+
+    let letters: Vec<char> = text.chars().filter(|c| c.is_alphabetic()).collect();
+
+    let count = |range: &[(u32, u32)]| letters.iter().filter(|c| range.iter().any(|(a, b)| (*a..=*b).contains(&(**c as u32)))).count();
+
+    // Japanese is written with Kana as well as the Han characters of Chinese
+    if count(&[(0x3040, 0x30ff)]) * 10 > letters.len() {
+        return Some("ja");
+    }
+
+    let scripts: [(&str, &[(u32, u32)]); 8] = [
+        ("zh", &[(0x4e00, 0x9fff), (0x3400, 0x4dbf)]),
+        ("ko", &[(0xac00, 0xd7af), (0x1100, 0x11ff)]),
+        ("ru", &[(0x0400, 0x04ff)]),
+        ("el", &[(0x0370, 0x03ff)]),
+        ("ar", &[(0x0600, 0x06ff)]),
+        ("he", &[(0x0590, 0x05ff)]),
+        ("hi", &[(0x0900, 0x097f)]),
+        ("th", &[(0x0e00, 0x0e7f)]),
+    ];
+
+    scripts.iter().find(|(_, range)| count(range) * 2 > letters.len()).map(|(language, _)| *language)
+}
@@ -0,0 +1,103 @@
+use std::ops::Range;
+
+// Splits html into its markup, which are comments and anything inside of `<` and `>`.
+// Everything between the markup is text.
+
+pub (crate) enum Markup {
+    Comment(Range<usize>),
+    Tag(Range<usize>),
+}
+
+pub (crate) enum Next {
+    Markup(Markup),
+    // the markup starting at this position is not finished yet, but more html may follow
+    Incomplete(usize),
+    // there is no more markup, so the rest of the html is text
+    End,
+}
+
+// Finds the next markup starting at `position`. If the html is not `complete`, more of it may be appended later.
+pub (crate) fn next(html: &str, position: usize, complete: bool) -> Next {
+
+    let mut start = position;
+
+    // a `<` that is not followed by a tag-name, `/`, `!` or `?` is text, like in `1 < 2`
+    loop {
+        start = match html[start..].find('<') {
+            Some(v) => start + v,
+            None => return Next::End
+        };
+        match html[start + 1..].chars().next() {
+            Some(c) if c.is_ascii_alphabetic() || c == '/' || c == '!' || c == '?' => break,
+            None if !complete => return Next::Incomplete(start),
+            _ => start += 1
+        }
+    }
+
+    // tags inside of comments are not markup
+    if html[start..].starts_with("<!--") {
+        return match html[start + 4..].find("-->") {
+            Some(v) => Next::Markup(Markup::Comment(start..start + 4 + v + 3)),
+            None if !complete => Next::Incomplete(start),
+            None => Next::Markup(Markup::Comment(start..html.len()))
+        };
+    }
+
+    // the beginning of a comment
+    if !complete && "<!--".starts_with(&html[start..]) {
+        return Next::Incomplete(start);
+    }
+
+    match tag_end(html, start) {
+        Some(v) => Next::Markup(Markup::Tag(start..v)),
+        None if !complete => Next::Incomplete(start),
+        // a quote that is never closed
+        None => match html[start..].find('>') {
+            Some(v) => Next::Markup(Markup::Tag(start..start + v + 1)),
+            None => Next::End
+        }
+    }
+}
+
+// Returns the end of the tag starting at `start`, where a `>` inside of a quoted attribute-value like `title="a > b"` does not end the tag
+fn tag_end(html: &str, start: usize) -> Option<usize> {
+
+    let bytes = html.as_bytes();
+    let mut i = start + 1;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'>' => return Some(i + 1),
+            b'=' => {
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                    i += 1;
+                }
+                if i < bytes.len() && (bytes[i] == b'"' || bytes[i] == b'\'') {
+                    i += 1 + html[i + 1..].find(bytes[i] as char)? + 1;
+                }
+            },
+            _ => i += 1
+        }
+    }
+
+    None
+}
+
+// Finds the closing tag of a tag whose content is raw text, like `<script>` or `<style>`, where `<` and `>` are no markup.
+// Returns where the closing tag starts, or None if it is not in the html (yet).
+pub (crate) fn raw_text_end(html: &str, position: usize, tagname: &str) -> Option<usize> {
+
+    let mut start = position;
+
+    while let Some(v) = html[start..].find("</") {
+        start += v;
+        let rest = &html.as_bytes()[start + 2..];
+        if rest.len() > tagname.len() && rest[..tagname.len()].eq_ignore_ascii_case(tagname.as_bytes()) && matches!(rest[tagname.len()], b'>' | b'/' | b' ' | b'\t' | b'\n' | b'\r' | b'\x0c') {
+            return Some(start);
+        }
+        start += 2;
+    }
+
+    None
+}
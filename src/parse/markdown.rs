@@ -0,0 +1,329 @@
+use crate::parse::{local_name, table};
+use crate::parse::document::{Content, Document};
+
+// Converts tags into Markdown. Block tags like `<p>` or `<h1>` are separated by blank lines, inline tags like `<b>` or `<a>`
+// become Markdown within the text, and tags without a Markdown equivalent only contribute their contents.
+
+// Tags that are put on their own, separated from the text around them by blank lines
+const BLOCK_TAGNAMES: [&str; 24] = [
+    "address", "article", "aside", "body", "caption", "center", "dd", "details", "div", "dl", "dt", "fieldset",
+    "figcaption", "figure", "footer", "form", "header", "html", "li", "main", "nav", "p", "section", "summary",
+];
+
+// Tags whose contents are not shown as text
+const HIDDEN_TAGNAMES: [&str; 6] = ["head", "noscript", "script", "style", "template", "title"];
+
+pub (crate) fn write(document: &Document, contents: Vec<Content>) -> String {
+    let mut out = String::new();
+    Writer{document}.contents(contents, &mut out);
+    finish(&out)
+}
+
+struct Writer<'a> {
+    document: &'a Document,
+}
+
+impl Writer<'_> {
+
+    fn contents(&self, contents: Vec<Content>, out: &mut String) {
+        for c in contents {
+            match c {
+                Content::Element(i) => self.element(i, out),
+                Content::Text(range) => text(&self.decode(&self.document.html[range]), out),
+                Content::Comment(_) => ()
+            }
+        }
+    }
+
+    fn element(&self, index: usize, out: &mut String) {
+
+        let tagname = local_name(self.document.tagname(index)).to_ascii_lowercase();
+
+        match tagname.as_str() {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = tagname[1..].parse().unwrap_or(1);
+                block(out);
+                out.push_str(&"#".repeat(level));
+                out.push(' ');
+                out.push_str(self.inline(index).replace('\n', " ").trim());
+                block(out);
+            },
+            "strong" | "b" => self.wrap(index, "**", out),
+            "em" | "i" => self.wrap(index, "*", out),
+            "del" | "s" | "strike" => self.wrap(index, "~~", out),
+            "code" => {
+                let code = self.raw_text(index).split_whitespace().collect::<Vec<&str>>().join(" ");
+                let ticks = match code.contains('`') {
+                    true => "``",
+                    false => "`"
+                };
+                out.push_str(ticks);
+                out.push_str(&code);
+                out.push_str(ticks);
+            },
+            "a" => {
+                let label = self.inline(index).trim().to_string();
+                match self.attr(index, "href") {
+                    Some(href) if label.is_empty() => out.push_str(&format!("[{}]({})", escape(&href), destination(&href))),
+                    Some(href) => out.push_str(&format!("[{}]({})", label, destination(&href))),
+                    None => out.push_str(&label)
+                }
+            },
+            "img" => {
+                if let Some(src) = self.attr(index, "src") {
+                    out.push_str(&format!("![{}]({})", escape(&self.attr(index, "alt").unwrap_or_default()), destination(&src)));
+                }
+            },
+            "br" => {
+                trim_spaces(out);
+                out.push_str("  \n");
+            },
+            "hr" => {
+                block(out);
+                out.push_str("---");
+                block(out);
+            },
+            "pre" => self.code_block(index, out),
+            "ul" | "ol" => self.list(index, tagname == "ol", out),
+            "blockquote" => {
+                let quote = self.blocks(index);
+                block(out);
+                let lines: Vec<String> = quote.lines().map(|l| match l.is_empty() {
+                    true => ">".to_string(),
+                    false => format!("> {}", l)
+                }).collect();
+                out.push_str(&lines.join("\n"));
+                block(out);
+            },
+            "table" => {
+                block(out);
+                out.push_str(&self.table(index));
+                block(out);
+            },
+            _ if HIDDEN_TAGNAMES.contains(&tagname.as_str()) => (),
+            _ if BLOCK_TAGNAMES.contains(&tagname.as_str()) => {
+                block(out);
+                self.contents(self.document.contents(index), out);
+                block(out);
+            },
+            _ => self.contents(self.document.contents(index), out)
+        }
+    }
+
+    // Writes the contents of an inline tag between the given markers, keeping whitespace around them outside of the markers
+    fn wrap(&self, index: usize, marker: &str, out: &mut String) {
+
+        let inner = self.inline(index);
+        let trimmed = inner.trim();
+
+        if trimmed.is_empty() {
+            out.push_str(&inner);
+            return;
+        }
+
+        if inner.starts_with(' ') {
+            text(" ", out);
+        }
+        out.push_str(marker);
+        out.push_str(trimmed);
+        out.push_str(marker);
+        if inner.ends_with(' ') {
+            out.push(' ');
+        }
+    }
+
+    fn code_block(&self, index: usize, out: &mut String) {
+
+        // the language is given css-like by a class `language-...` of the `<pre>` or of a `<code>` inside of it
+        let language = std::iter::once(index).chain(self.document.children(index))
+            .filter_map(|i| self.attr(i, "class"))
+            .find_map(|c| c.split_whitespace().find_map(|n| n.strip_prefix("language-").or_else(|| n.strip_prefix("lang-"))).map(|l| l.to_string()))
+            .unwrap_or_default();
+
+        let code = self.raw_text(index);
+        // a line break directly after `<pre>` is not part of the content
+        let code = code.strip_prefix('\n').unwrap_or(&code);
+
+        let fence = match code.contains("```") {
+            true => "~~~",
+            false => "```"
+        };
+
+        block(out);
+        out.push_str(fence);
+        out.push_str(&language);
+        out.push('\n');
+        out.push_str(code.trim_end_matches('\n'));
+        out.push('\n');
+        out.push_str(fence);
+        block(out);
+    }
+
+    fn list(&self, index: usize, ordered: bool, out: &mut String) {
+
+        let start: usize = match ordered {
+            true => self.attr(index, "start").and_then(|s| s.trim().parse().ok()).unwrap_or(1),
+            false => 0
+        };
+
+        block(out);
+
+        let items = self.document.children(index).into_iter().filter(|c| self.document.tagname(*c).eq_ignore_ascii_case("li"));
+
+        for (n, item) in items.enumerate() {
+            let marker = match ordered {
+                true => format!("{}. ", start + n),
+                false => "- ".to_string()
+            };
+            // lines after the first one are indented to line up with the text after the marker
+            let indent = " ".repeat(marker.len());
+            let content: Vec<String> = self.blocks(item).lines().enumerate().map(|(n, l)| match n {
+                0 => format!("{}{}", marker, l),
+                _ if l.is_empty() => String::new(),
+                _ => format!("{}{}", indent, l)
+            }).collect();
+            match content.is_empty() {
+                true => out.push_str(marker.trim_end()),
+                false => out.push_str(&content.join("\n"))
+            }
+            out.push('\n');
+        }
+
+        block(out);
+    }
+
+    fn table(&self, index: usize) -> String {
+
+        let mut table = table::read(self.document, index);
+
+        // a Markdown table always has a header row
+        if table.headers.is_empty() && !table.rows.is_empty() {
+            table.headers = table.rows.remove(0);
+        }
+
+        let columns = std::iter::once(&table.headers).chain(table.rows.iter()).map(|r| r.len()).max().unwrap_or(0);
+
+        let row = |cells: &[String]| {
+            let cells: Vec<String> = (0..columns).map(|c| cells.get(c).map(|t| escape(t).replace('|', "\\|")).unwrap_or_default()).collect();
+            format!("| {} |", cells.join(" | "))
+        };
+
+        let mut lines = vec![row(&table.headers), format!("|{}", " --- |".repeat(columns))];
+        lines.extend(table.rows.iter().map(|r| row(r)));
+
+        lines.join("\n")
+    }
+
+    // Returns the Markdown of the contents of an inline tag
+    fn inline(&self, index: usize) -> String {
+        let mut out = String::new();
+        self.contents(self.document.contents(index), &mut out);
+        out
+    }
+
+    // Returns the Markdown of the contents of a tag that holds blocks, like a list item or a quote
+    fn blocks(&self, index: usize) -> String {
+        finish(&self.inline(index))
+    }
+
+    // Returns the text of a tag as it is written, without leaving out whitespace
+    fn raw_text(&self, index: usize) -> String {
+        let mut segments: Vec<String> = vec![];
+        self.document.text_segments(self.document.contents(index), false, &mut segments);
+        segments.concat()
+    }
+
+    fn decode(&self, text: &str) -> String {
+        match self.document.options.decode_text {
+            true => crate::entities::decode(text),
+            false => text.to_string()
+        }
+    }
+
+    fn attr(&self, index: usize, name: &str) -> Option<String> {
+        self.document.attributes(index).into_iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, v)| v)
+    }
+}
+
+// Adds text with its whitespace collapsed like browsers do, leaving out whitespace at the beginning of a line
+fn text(text: &str, out: &mut String) {
+
+    let mut collapsed = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+
+    if text.starts_with(char::is_whitespace) && !collapsed.is_empty() {
+        collapsed.insert(0, ' ');
+    }
+    if text.ends_with(char::is_whitespace) && !collapsed.ends_with(' ') {
+        collapsed.push(' ');
+    }
+
+    if out.ends_with('\n') || out.ends_with(' ') {
+        collapsed = collapsed.trim_start().to_string();
+    }
+
+    out.push_str(&escape(&collapsed));
+}
+
+// Escapes the characters that would be read as Markdown
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+// Puts a link destination into `<` and `>` if it contains characters that would end it
+fn destination(url: &str) -> String {
+    match url.contains([' ', '(', ')']) {
+        true => format!("<{}>", url),
+        false => url.to_string()
+    }
+}
+
+fn trim_spaces(out: &mut String) {
+    out.truncate(out.trim_end_matches(' ').len());
+}
+
+// Starts or ends a block by a blank line unless there is one already or nothing before it
+fn block(out: &mut String) {
+
+    trim_spaces(out);
+
+    if out.is_empty() || out.ends_with("\n\n") {
+        return;
+    }
+
+    match out.ends_with('\n') {
+        true => out.push('\n'),
+        false => out.push_str("\n\n")
+    }
+}
+
+// Removes blank lines following other blank lines outside of code blocks and whitespace around the Markdown
+fn finish(markdown: &str) -> String {
+
+    let mut out: Vec<&str> = vec![];
+    let mut fence: Option<&str> = None;
+
+    for line in markdown.lines() {
+
+        let trimmed = line.trim_start();
+
+        match fence {
+            Some(f) if trimmed.starts_with(f) => fence = None,
+            Some(_) => (),
+            None if trimmed.starts_with("```") => fence = Some("```"),
+            None if trimmed.starts_with("~~~") => fence = Some("~~~"),
+            None if line.trim().is_empty() && out.last().map_or(true, |l| l.trim().is_empty()) => continue,
+            None => ()
+        }
+
+        out.push(line);
+    }
+
+    out.join("\n").trim().to_string()
+}
@@ -0,0 +1,239 @@
+use crate::parse::{attributes, fetch, local_name};
+use crate::parse::document::Document;
+use encoding_rs::Encoding;
+use std::collections::HashMap;
+
+// Reads information about the whole document: its doctype declaration,
+// whether browsers would render it in quirks mode and its charset.
+
+// Public identifiers of legacy doctypes that put browsers into quirks mode
+const QUIRKS_PREFIXES: [&str; 21] = [
+    "+//Silmaril//dtd html Pro v0r11 19970101//",
+    "-//AS//DTD HTML 3.0 asWedit + extensions//",
+    "-//AdvaSoft Ltd//DTD HTML 3.0 asWedit + extensions//",
+    "-//IETF//DTD HTML",
+    "-//Metrius//DTD Metrius Presentational//",
+    "-//Microsoft//DTD Internet Explorer",
+    "-//Netscape Comm. Corp.//DTD",
+    "-//O'Reilly and Associates//DTD HTML",
+    "-//SQ//DTD HTML 2.0 HoTMetaL + extensions//",
+    "-//SoftQuad Software//DTD HoTMetaL PRO",
+    "-//SoftQuad//DTD HoTMetaL PRO",
+    "-//Spyglass//DTD HTML 2.0 Extended//",
+    "-//Sun Microsystems Corp.//DTD HotJava",
+    "-//W3C//DTD HTML 3 1995-03-24//",
+    "-//W3C//DTD HTML 3.2",
+    "-//W3C//DTD HTML 4.0 Frameset//",
+    "-//W3C//DTD HTML 4.0 Transitional//",
+    "-//W3C//DTD HTML Experimental",
+    "-//W3C//DTD W3 HTML//",
+    "-//W3O//DTD W3 HTML 3.0//",
+    "-//WebTechs//DTD Mozilla HTML",
+];
+
+// Public identifiers of legacy doctypes that have to match as a whole
+const QUIRKS_IDS: [&str; 3] = [
+    "-//W3O//DTD W3 HTML Strict 3.0//EN//",
+    "-/W3C/DTD HTML 4.0 Transitional/EN",
+    "HTML",
+];
+
+// Public identifiers that only put browsers into quirks mode if there is no system identifier
+const QUIRKS_WITHOUT_SYSTEM_ID: [&str; 2] = [
+    "-//W3C//DTD HTML 4.01 Frameset//",
+    "-//W3C//DTD HTML 4.01 Transitional//",
+];
+
+// Returns the doctype declaration without `<!DOCTYPE` and `>`, e.g. `html` for `<!DOCTYPE html>`
+pub (crate) fn doctype(document: &Document) -> Option<&str> {
+    let declaration = &document.html[document.doctype.clone()?];
+    Some(declaration[9..].trim_end_matches('>').trim())
+}
+
+pub (crate) fn is_quirks_mode(document: &Document) -> bool {
+
+    let doctype = match doctype(document) {
+        Some(v) => v,
+        None => return true
+    };
+
+    let (name, public_id, system_id) = split_doctype(doctype);
+
+    if !name.eq_ignore_ascii_case("html") {
+        return true;
+    }
+
+    let public_id = public_id.unwrap_or("");
+
+    if system_id.is_some_and(|s| s.eq_ignore_ascii_case("http://www.ibm.com/data/dtd/v11/ibmxhtml1-transitional.dtd")) {
+        return true;
+    }
+
+    if QUIRKS_PREFIXES.iter().any(|p| starts_with_ignore_case(public_id, p)) || QUIRKS_IDS.iter().any(|p| public_id.eq_ignore_ascii_case(p)) {
+        return true;
+    }
+
+    system_id.is_none() && QUIRKS_WITHOUT_SYSTEM_ID.iter().any(|p| starts_with_ignore_case(public_id, p))
+}
+
+// Returns the charset given by a `<meta charset>` tag or the `content` of a `<meta http-equiv='Content-Type'>` tag
+pub (crate) fn charset(document: &Document) -> Option<String> {
+
+    if document.html.starts_with('\u{feff}') {
+        return Some("UTF-8".to_string());
+    }
+
+    for i in (0..document.len()).filter(|i| local_name(document.tagname(*i)).eq_ignore_ascii_case("meta")) {
+
+        let attrs = attributes::parse(document.tagcontent(i));
+
+        let value = |name: &str| attrs.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, v)| v.trim());
+
+        if let Some(charset) = value("charset") {
+            return Some(charset.to_string());
+        }
+
+        if value("http-equiv").is_some_and(|v| v.eq_ignore_ascii_case("content-type")) {
+            let content = value("content").unwrap_or("");
+            if let Some(v) = content.to_ascii_lowercase().find("charset=") {
+                let charset = content[v + 8..].split(';').next().unwrap_or("");
+                return Some(charset.trim().trim_matches(|c| c == '"' || c == '\'').to_string());
+            }
+        }
+    }
+
+    None
+}
+
+// Returns the `content` of every `<meta>` tag by its `name`, `property` or `http-equiv` in lowercase,
+// and the value of `<meta charset>` by `charset`. If a name is given several times, the first one is kept.
+pub (crate) fn meta(document: &Document) -> HashMap<String, String> {
+
+    let mut out: HashMap<String, String> = HashMap::new();
+
+    for i in (0..document.len()).filter(|i| local_name(document.tagname(*i)).eq_ignore_ascii_case("meta")) {
+
+        let attrs = document.attributes(i);
+
+        let value = |name: &str| attrs.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, v)| v.clone());
+
+        if let Some(charset) = value("charset") {
+            out.entry("charset".to_string()).or_insert(charset);
+        }
+
+        if let Some(key) = value("name").or_else(|| value("property")).or_else(|| value("http-equiv")) {
+            out.entry(key.trim().to_lowercase()).or_insert(value("content").unwrap_or_default());
+        }
+    }
+
+    out
+}
+
+// Returns the language declared by the `lang` or `xml:lang` attribute of the `<html>` tag
+// or by a `<meta http-equiv="Content-Language">` tag, whose first language is taken if there are several
+pub (crate) fn language(document: &Document) -> Option<String> {
+
+    let html = (0..document.len()).find(|i| document.namespace(*i).is_empty() && document.tagname(*i).eq_ignore_ascii_case("html"));
+
+    if let Some(i) = html {
+        let attrs = document.attributes(i);
+        let lang = ["lang", "xml:lang"].iter().find_map(|a| attrs.iter().find(|(n, v)| n.eq_ignore_ascii_case(a) && !v.trim().is_empty()));
+        if let Some((_, v)) = lang {
+            return Some(v.trim().to_string());
+        }
+    }
+
+    meta(document).remove("content-language")
+        .and_then(|v| v.split(',').next().map(|l| l.trim().to_string()))
+        .filter(|l| !l.is_empty())
+}
+
+// Returns the `href` of every `<link>` tag whose `rel` contains the given link type, together with all of its attributes
+pub (crate) fn links(document: &Document, rel: &str) -> Vec<(String, Vec<(String, String)>)> {
+
+    let mut out: Vec<(String, Vec<(String, String)>)> = vec![];
+
+    for i in (0..document.len()).filter(|i| local_name(document.tagname(*i)).eq_ignore_ascii_case("link")) {
+
+        let attrs = document.attributes(i);
+
+        let value = |name: &str| attrs.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, v)| v.trim().to_string());
+
+        if !value("rel").unwrap_or_default().split_whitespace().any(|r| r.eq_ignore_ascii_case(rel)) {
+            continue;
+        }
+
+        if let Some(href) = value("href") {
+            out.push((href, attrs));
+        }
+    }
+
+    out
+}
+
+// Returns the encoding of html given as bytes: the encoding of its byte order mark, the charset declared by a `<meta>` tag
+// within the first 1024 bytes like browsers do, or UTF-8 if there is neither
+pub (crate) fn encoding(html: &[u8]) -> &'static Encoding {
+
+    if let Some((encoding, _)) = Encoding::for_bom(html) {
+        return encoding;
+    }
+
+    // the declaration only contains ascii characters, which are the same in every encoding it may declare
+    let (head, _) = encoding_rs::WINDOWS_1252.decode_without_bom_handling(&html[..html.len().min(1024)]);
+
+    let declared = charset(&fetch::parse(head.into_owned())).and_then(|c| Encoding::for_label(c.as_bytes()));
+
+    match declared {
+        // a page declaring UTF-16 cannot be UTF-16, since the declaration was readable as ascii
+        Some(v) if v == encoding_rs::UTF_16LE || v == encoding_rs::UTF_16BE => encoding_rs::UTF_8,
+        Some(v) if v == encoding_rs::X_USER_DEFINED => encoding_rs::WINDOWS_1252,
+        Some(v) => v,
+        None => encoding_rs::UTF_8
+    }
+}
+
+// Splits a doctype declaration into its name, public identifier and system identifier
+fn split_doctype(doctype: &str) -> (&str, Option<&str>, Option<&str>) {
+
+    let (name, mut rest) = match doctype.split_once(char::is_whitespace) {
+        Some((name, rest)) => (name, rest.trim_start()),
+        None => return (doctype, None, None)
+    };
+
+    let mut public_id = None;
+
+    if starts_with_ignore_case(rest, "PUBLIC") {
+        let (id, r) = quoted(&rest[6..]);
+        public_id = id;
+        rest = r;
+    } else if starts_with_ignore_case(rest, "SYSTEM") {
+        rest = &rest[6..];
+    } else {
+        return (name, None, None);
+    }
+
+    let (system_id, _) = quoted(rest);
+
+    (name, public_id, system_id)
+}
+
+// Reads a quoted identifier and returns it together with the rest of the declaration
+fn quoted(text: &str) -> (Option<&str>, &str) {
+
+    let text = text.trim_start();
+
+    let quote = match text.chars().next() {
+        Some(c) if c == '"' || c == '\'' => c,
+        _ => return (None, text)
+    };
+
+    match text[1..].find(quote) {
+        Some(v) => (Some(&text[1..v + 1]), &text[v + 2..]),
+        None => (Some(&text[1..]), "")
+    }
+}
+
+fn starts_with_ignore_case(text: &str, prefix: &str) -> bool {
+    text.len() >= prefix.len() && text.is_char_boundary(prefix.len()) && text[..prefix.len()].eq_ignore_ascii_case(prefix)
+}
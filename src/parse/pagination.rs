@@ -0,0 +1,76 @@
+use crate::parse::document::Document;
+use crate::parse::{local_name, metadata};
+
+// Finds the links to the next and the previous page of a paginated listing. Links marked by `rel="next"` or `rel="prev"`
+// are preferred, otherwise a link labeled like a "next page" link by its text, `aria-label` or `title` is taken,
+// or else a link whose class says so, like `<a class="pagination-next">`.
+
+pub (crate) struct Direction {
+    // the link types marking the link, where `previous` is an old synonym of `prev`
+    rel: &'static [&'static str],
+    // the texts and labels of the link in several languages, compared lowercased with arrows and whitespace around them removed
+    labels: &'static [&'static str],
+    // the texts of links consisting of arrows only
+    arrows: &'static [&'static str],
+    // the words a class of the link or of its parent contains
+    classes: &'static [&'static str],
+}
+
+pub (crate) const NEXT: Direction = Direction{
+    rel: &["next"],
+    labels: &[
+        "next", "next page", "older posts", "older entries", "load more", "show more", "more results",
+        "weiter", "nächste", "nächste seite", "suivant", "page suivante", "siguiente", "página siguiente",
+        "successivo", "pagina successiva", "volgende", "próximo", "próxima", "następna", "далее", "следующая",
+    ],
+    arrows: &["›", "»", "→", ">", ">>", "⟩", "❯"],
+    classes: &["next"],
+};
+
+pub (crate) const PREV: Direction = Direction{
+    rel: &["prev", "previous"],
+    labels: &[
+        "prev", "previous", "previous page", "newer posts", "newer entries",
+        "zurück", "vorherige", "vorherige seite", "précédent", "page précédente", "anterior", "página anterior",
+        "precedente", "pagina precedente", "vorige", "poprzednia", "назад", "предыдущая",
+    ],
+    arrows: &["‹", "«", "←", "<", "<<", "⟨", "❮"],
+    classes: &["prev", "previous"],
+};
+
+// Characters decorating the labels of pagination links, like in `Next »` or `‹ Previous`
+const DECORATIONS: [char; 11] = ['›', '»', '→', '>', '⟩', '❯', '‹', '«', '←', '<', '⟨'];
+
+pub (crate) fn find(document: &Document, direction: &Direction) -> Option<String> {
+
+    if let Some((href, _)) = direction.rel.iter().find_map(|r| metadata::links(document, r).into_iter().next()) {
+        return Some(href);
+    }
+
+    let links: Vec<(usize, String)> = (0..document.len())
+        .filter(|i| local_name(document.tagname(*i)).eq_ignore_ascii_case("a"))
+        .filter_map(|i| attr(document, i, "href").map(|h| (i, h.trim().to_string())))
+        // links that do not lead to another page
+        .filter(|(_, h)| !h.is_empty() && !h.starts_with('#') && !h.to_ascii_lowercase().starts_with("javascript:"))
+        .collect();
+
+    let by_rel = links.iter().find(|(i, _)| attr(document, *i, "rel").is_some_and(|r| r.split_whitespace().any(|r| direction.rel.iter().any(|d| r.eq_ignore_ascii_case(d)))));
+
+    let by_label = || links.iter().find(|(i, _)| {
+        let labels = [document.text(*i), attr(document, *i, "aria-label").unwrap_or_default(), attr(document, *i, "title").unwrap_or_default()];
+        labels.iter().any(|l| {
+            let label = l.split_whitespace().collect::<Vec<&str>>().join(" ").to_lowercase();
+            direction.arrows.contains(&label.as_str()) || direction.labels.contains(&label.trim_matches(|c: char| c.is_whitespace() || DECORATIONS.contains(&c)))
+        })
+    });
+
+    let by_class = || links.iter().find(|(i, _)| std::iter::once(*i).chain(document[*i].parent).any(|t| {
+        attr(document, t, "class").is_some_and(|c| c.to_ascii_lowercase().split(|c: char| c.is_whitespace() || c == '-' || c == '_').any(|w| direction.classes.contains(&w)))
+    }));
+
+    by_rel.or_else(by_label).or_else(by_class).map(|(_, href)| href.clone())
+}
+
+fn attr(document: &Document, index: usize, name: &str) -> Option<String> {
+    document.attributes(index).into_iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, v)| v)
+}
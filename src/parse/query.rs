@@ -0,0 +1,151 @@
+use crate::parse::selector;
+use crate::parse::document::Document;
+
+// A single query of a filter call: either a tag-name, attribute-name and attribute-value
+// or a css-like selector.
+
+#[derive(Clone, Debug)]
+pub enum Query {
+    Legacy(&'static str, &'static str, &'static str),
+    Selector(selector::Selector),
+    // a selector passed with an attribute-name and attribute-value like `("div.post", "data-id", "2")`
+    SelectorWithAttr(selector::Selector, &'static str, &'static str),
+    Nothing,
+}
+
+impl Query {
+
+    pub (crate) fn new(tag_name: &'static str, attr_name: &'static str, attr_value: &'static str) -> Query {
+
+        if !selector::is_selector(tag_name) {
+            return Query::Legacy(tag_name, attr_name, attr_value);
+        }
+
+        match selector::parse(tag_name) {
+            Some(s) if attr_name.is_empty() && attr_value.is_empty() => Query::Selector(s),
+            Some(s) => Query::SelectorWithAttr(s, attr_name, attr_value),
+            None => Query::Nothing
+        }
+    }
+
+    // A query without any arguments matches the whole dom
+    pub (crate) fn is_empty(&self) -> bool {
+        match self {
+            Query::Legacy(tag_name, attr_name, attr_value) => tag_name.is_empty() && attr_name.is_empty() && attr_value.is_empty(),
+            _ => false
+        }
+    }
+
+    pub (crate) fn matches(&self, tags: &Document, index: usize, options: crate::FilterOptions) -> bool {
+
+        match self {
+            Query::Legacy(tag_name, attr_name, attr_value) => {
+                if !tag(tags.tagname(index), tag_name, options.case_insensitive) {
+                    return false;
+                }
+                let attributes = tags.attributes(index);
+                attr(&attributes, attr_name, options.case_insensitive) && value(&attributes, attr_name, attr_value, options)
+            },
+            Query::Selector(s) => s.matches(tags, index, options),
+            Query::SelectorWithAttr(s, attr_name, attr_value) => {
+                if !s.matches(tags, index, options) {
+                    return false;
+                }
+                let attributes = tags.attributes(index);
+                attr(&attributes, attr_name, options.case_insensitive) && value(&attributes, attr_name, attr_value, options)
+            },
+            Query::Nothing => false,
+        }
+    }
+}
+
+// Checks the tag-name and several attribute-names and attribute-values at once, which all have to match.
+// Attribute-names are compared regardless of their case like html does.
+pub (crate) fn matches_attrs(tags: &Document, index: usize, tag_name: &str, attrs: &[(&str, &str)], options: crate::FilterOptions) -> bool {
+
+    if !tag(tags.tagname(index), tag_name, options.case_insensitive) {
+        return false;
+    }
+
+    let attributes = tags.attributes(index);
+
+    attrs.iter().all(|(attrname, attrvalue)| {
+        attributes.iter().any(|(name, value)| {
+            name.eq_ignore_ascii_case(attrname) && (crate::parse::is_wildcard(attrvalue) || matches_value(name, value, attrvalue, options.case_insensitive_values))
+        })
+    })
+}
+
+fn tag(name: &str, tagname: &str, case_insensitive: bool) -> bool {
+
+    if crate::parse::is_wildcard(tagname) {
+        return true;
+    }
+
+    let local = crate::parse::local_name(name);
+
+    name == tagname || local == tagname || (case_insensitive && (name.eq_ignore_ascii_case(tagname) || local.eq_ignore_ascii_case(tagname)))
+}
+
+fn name_eq(name: &str, attrname: &str, case_insensitive: bool) -> bool {
+    name == attrname || (case_insensitive && name.eq_ignore_ascii_case(attrname))
+}
+
+// Boolean attributes like `<input required>` count as well
+fn attr(attributes: &[(String, String)], attr: &str, case_insensitive: bool) -> bool {
+
+    if crate::parse::is_wildcard(attr) {
+        return true;
+    }
+
+    attributes.iter().any(|(name, _)| name_eq(name, attr, case_insensitive))
+}
+
+// Checks the values of the given attribute, or of all attributes if no attribute-name is given
+fn value(attributes: &[(String, String)], attrname: &str, attrvalue: &str, options: crate::FilterOptions) -> bool {
+
+    if crate::parse::is_wildcard(attrvalue) {
+        return true;
+    }
+
+    attributes.iter().any(|(name, value)| {
+        (crate::parse::is_wildcard(attrname) || name_eq(name, attrname, options.case_insensitive)) && matches_value(name, value, attrvalue, options.case_insensitive_values)
+    })
+}
+
+fn matches_value(name: &str, value: &str, attrvalue: &str, case_insensitive: bool) -> bool {
+
+    if case_insensitive {
+        return matches_value(name, &value.to_lowercase(), &attrvalue.to_lowercase(), false);
+    }
+
+    // values like `*/product/*` are matched as glob patterns
+    if value == attrvalue || (attrvalue.contains('*') && glob(attrvalue, value)) {
+        return true;
+    }
+
+    // the class attribute is split into its tokens, so `inactive` does not match `active`
+    name.eq_ignore_ascii_case("class") && value.split_whitespace().any(|c| c == attrvalue)
+}
+
+// Checks if the text matches the pattern, where `*` stands for any sequence of characters
+fn glob(pattern: &str, text: &str) -> bool {
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+
+    if text.len() < first.len() + last.len() || !text.starts_with(first) || !text.ends_with(last) {
+        return false;
+    }
+
+    let mut rest = &text[first.len()..text.len() - last.len()];
+
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false
+        }
+    }
+
+    true
+}
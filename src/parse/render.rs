@@ -0,0 +1,174 @@
+use crate::parse::local_name;
+use crate::parse::document::{Content, Document};
+
+// Renders tags as plain text the way a text browser shows them: block tags start new lines, paragraphs and headings
+// are separated by blank lines, list items get bullets or numbers and the cells of a table row are separated by tabs.
+
+// Tags that start a new line and end their line
+const LINE_TAGNAMES: [&str; 21] = [
+    "address", "article", "aside", "body", "caption", "center", "dd", "details", "div", "dl", "dt", "fieldset",
+    "figcaption", "figure", "footer", "form", "header", "html", "main", "nav", "section",
+];
+
+// Tags that are separated from the text around them by blank lines
+const PARAGRAPH_TAGNAMES: [&str; 9] = ["blockquote", "h1", "h2", "h3", "h4", "h5", "h6", "p", "table"];
+
+// Tags whose contents are not shown as text
+const HIDDEN_TAGNAMES: [&str; 6] = ["head", "noscript", "script", "style", "template", "title"];
+
+pub (crate) fn render(document: &Document, contents: Vec<Content>) -> String {
+    let mut out = String::new();
+    Renderer{document}.contents(contents, 0, &mut out);
+    out.trim().to_string()
+}
+
+struct Renderer<'a> {
+    document: &'a Document,
+}
+
+impl Renderer<'_> {
+
+    // Renders the contents, where `depth` is the number of lists they are nested in
+    fn contents(&self, contents: Vec<Content>, depth: usize, out: &mut String) {
+        for c in contents {
+            match c {
+                Content::Element(i) => self.element(i, depth, out),
+                Content::Text(range) => text(&self.decode(&self.document.html[range]), out),
+                Content::Comment(_) => ()
+            }
+        }
+    }
+
+    fn element(&self, index: usize, depth: usize, out: &mut String) {
+
+        let tagname = local_name(self.document.tagname(index)).to_ascii_lowercase();
+
+        match tagname.as_str() {
+            "br" => {
+                trim_spaces(out);
+                out.push('\n');
+            },
+            "hr" => {
+                blank_line(out);
+                out.push_str("---");
+                blank_line(out);
+            },
+            "pre" => {
+                let mut segments: Vec<String> = vec![];
+                self.document.text_segments(self.document.contents(index), false, &mut segments);
+                let code = segments.concat();
+                blank_line(out);
+                // a line break directly after `<pre>` is not part of the content
+                out.push_str(code.strip_prefix('\n').unwrap_or(&code).trim_end());
+                blank_line(out);
+            },
+            "ul" | "ol" => self.list(index, depth, tagname == "ol", out),
+            "tr" => {
+                new_line(out);
+                let cells = self.document.children(index).into_iter().filter(|c| ["td", "th"].iter().any(|n| self.document.tagname(*c).eq_ignore_ascii_case(n)));
+                for (n, cell) in cells.enumerate() {
+                    if n > 0 {
+                        trim_spaces(out);
+                        out.push('\t');
+                    }
+                    self.contents(self.document.contents(cell), depth, out);
+                }
+                new_line(out);
+            },
+            _ if HIDDEN_TAGNAMES.contains(&tagname.as_str()) => (),
+            _ if PARAGRAPH_TAGNAMES.contains(&tagname.as_str()) => {
+                blank_line(out);
+                self.contents(self.document.contents(index), depth, out);
+                blank_line(out);
+            },
+            _ if LINE_TAGNAMES.contains(&tagname.as_str()) || tagname == "li" => {
+                new_line(out);
+                self.contents(self.document.contents(index), depth, out);
+                new_line(out);
+            },
+            _ => self.contents(self.document.contents(index), depth, out)
+        }
+    }
+
+    fn list(&self, index: usize, depth: usize, ordered: bool, out: &mut String) {
+
+        let start: usize = match ordered {
+            true => self.attr(index, "start").and_then(|s| s.trim().parse().ok()).unwrap_or(1),
+            false => 0
+        };
+
+        // lists that are not nested are separated from the text around them by blank lines
+        match depth {
+            0 => blank_line(out),
+            _ => new_line(out)
+        }
+
+        let items = self.document.children(index).into_iter().filter(|c| self.document.tagname(*c).eq_ignore_ascii_case("li"));
+
+        for (n, item) in items.enumerate() {
+            new_line(out);
+            out.push_str(&"  ".repeat(depth));
+            match ordered {
+                true => out.push_str(&format!("{}. ", start + n)),
+                false => out.push_str("• ")
+            }
+            self.contents(self.document.contents(item), depth + 1, out);
+            new_line(out);
+        }
+
+        match depth {
+            0 => blank_line(out),
+            _ => new_line(out)
+        }
+    }
+
+    fn decode(&self, text: &str) -> String {
+        match self.document.options.decode_text {
+            true => crate::entities::decode(text),
+            false => text.to_string()
+        }
+    }
+
+    fn attr(&self, index: usize, name: &str) -> Option<String> {
+        self.document.attributes(index).into_iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, v)| v)
+    }
+}
+
+// Adds text with its whitespace collapsed like browsers do, leaving out whitespace at the beginning of a line
+fn text(text: &str, out: &mut String) {
+
+    let mut collapsed = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+
+    if text.starts_with(char::is_whitespace) && !collapsed.is_empty() {
+        collapsed.insert(0, ' ');
+    }
+    if text.ends_with(char::is_whitespace) && !collapsed.ends_with(' ') {
+        collapsed.push(' ');
+    }
+
+    if out.is_empty() || out.ends_with(['\n', ' ', '\t']) {
+        out.push_str(collapsed.trim_start());
+    } else {
+        out.push_str(&collapsed);
+    }
+}
+
+fn trim_spaces(out: &mut String) {
+    out.truncate(out.trim_end_matches(' ').len());
+}
+
+// Ends the current line unless nothing has been written to it
+fn new_line(out: &mut String) {
+    trim_spaces(out);
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+// Ends the current line and adds a blank line unless there is one already
+fn blank_line(out: &mut String) {
+    new_line(out);
+    if !out.is_empty() && !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
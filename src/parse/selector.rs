@@ -0,0 +1,643 @@
+use crate::parse::tree;
+use crate::parse::document::Document;
+use std::io::{Error, ErrorKind};
+
+// A css-like selector as it can be passed to filter, e.g. `table a[href$=".jpg"], h1`.
+// It consists of a comma-separated list of complex selectors, each made of compound selectors
+// joined by combinators that are matched from right to left.
+
+/// A css-like selector that is parsed once and can be passed to [`Dom::filter`] as often as needed,
+/// so scraping many pages with the same query does not parse the selector again on every call.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper::Selector;
+///
+/// let selector = Selector::parse("div.item a").unwrap();
+///
+/// let dom = sitescraper::parse_html("<html><body><div class='item'><a>Hello World!</a></div></body></html>").unwrap();
+///
+/// println!("{}", dom.filter(&selector).get_text());
+/// //Output: Hello World!
+/// ```
+/// [`Dom::filter`]: struct.Dom.html#method.filter
+#[derive(Clone, Debug)]
+pub struct Selector {
+    complexes: Vec<Complex>,
+}
+
+#[derive(Clone, Debug)]
+struct Complex {
+    compounds: Vec<Compound>,
+    combinators: Vec<Combinator>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Combinator {
+    Descendant,
+    Child,
+    Adjacent,
+    Sibling,
+}
+
+#[derive(Clone, Debug)]
+struct Compound {
+    // None matches any namespace, an empty namespace only html tags
+    namespace: Option<String>,
+    tagname: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+    attrs: Vec<AttrSelector>,
+    pseudos: Vec<Pseudo>,
+}
+
+#[derive(Clone, Debug)]
+struct AttrSelector {
+    name: String,
+    op: AttrOp,
+    value: String,
+    // set by a trailing ` i` like in `[type="text" i]`
+    ignore_case: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum AttrOp {
+    Exists,
+    Equals,
+    StartsWith,
+    EndsWith,
+    Contains,
+    // `~=`, one of the whitespace-separated words of the value
+    Includes,
+    // `|=`, the value or its beginning followed by a `-` like in `[lang|=en]`
+    DashMatch,
+}
+
+// Pseudo-classes, the nth ones holding the `a` and `b` of an `an+b` expression
+#[derive(Clone, Debug)]
+enum Pseudo {
+    NthChild(i64, i64),
+    NthLastChild(i64, i64),
+    NthOfType(i64, i64),
+    NthLastOfType(i64, i64),
+    OnlyChild,
+    OnlyOfType,
+    Contains(String),
+}
+
+// Returns true if the filter argument uses selector syntax instead of a plain tag-name
+pub (crate) fn is_selector(arg: &str) -> bool {
+
+    // a tag-name with a namespace prefix like `svg:path`
+    if let Some((prefix, local)) = arg.split_once(':') {
+        if is_name(prefix) && is_name(local) && parse_pseudo(local, "").is_none() {
+            return false;
+        }
+    }
+
+    arg.contains(',') || arg.contains('|') || arg.contains('[') || arg.contains('.') || arg.contains('#') || arg.contains(':') || arg.contains('>') || arg.contains('+') || arg.contains('~') || arg.trim().contains(' ')
+}
+
+pub (crate) fn parse(selector: &str) -> Option<Selector> {
+
+    let mut complexes: Vec<Complex> = vec![];
+
+    for part in split_list(selector) {
+        complexes.push(parse_complex(&part)?);
+    }
+
+    Some(Selector{complexes})
+}
+
+// Splits a selector list at commas outside of brackets, parentheses and quotes
+fn split_list(selector: &str) -> Vec<String> {
+
+    let mut parts: Vec<String> = vec![];
+    let mut current = String::new();
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+
+    for c in selector.chars() {
+        match (c, quote) {
+            (_, Some(q)) if c == q => quote = None,
+            (_, Some(_)) => (),
+            ('"', None) | ('\'', None) => quote = Some(c),
+            ('[', None) | ('(', None) => depth += 1,
+            (']', None) | (')', None) => depth -= 1,
+            (',', None) if depth == 0 => {
+                parts.push(current);
+                current = String::new();
+                continue
+            },
+            _ => ()
+        }
+        current.push(c);
+    }
+
+    parts.push(current);
+
+    parts
+}
+
+fn parse_complex(selector: &str) -> Option<Complex> {
+
+    let mut compounds: Vec<Compound> = vec![];
+    let mut combinators: Vec<Combinator> = vec![];
+
+    let mut combinator: Option<Combinator> = None;
+
+    for part in split(selector) {
+        match part.as_str() {
+            ">" | "+" | "~" => {
+                if compounds.is_empty() || combinator.is_some() {
+                    return None;
+                }
+                combinator = match part.as_str() {
+                    ">" => Some(Combinator::Child),
+                    "+" => Some(Combinator::Adjacent),
+                    _ => Some(Combinator::Sibling),
+                };
+            },
+            _ => {
+                if !compounds.is_empty() {
+                    combinators.push(combinator.unwrap_or(Combinator::Descendant));
+                }
+                combinator = None;
+                compounds.push(parse_compound(&part)?);
+            }
+        }
+    }
+
+    if compounds.is_empty() || combinator.is_some() {
+        return None;
+    }
+
+    Some(Complex{compounds, combinators})
+}
+
+// Splits a selector at whitespace and combinators outside of brackets, parentheses and quotes.
+// Combinators are returned as parts of their own.
+fn split(selector: &str) -> Vec<String> {
+
+    let mut parts: Vec<String> = vec![];
+    let mut current = String::new();
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+
+    for c in selector.chars() {
+        match (c, quote) {
+            (_, Some(q)) if c == q => quote = None,
+            (_, Some(_)) => (),
+            ('"', None) | ('\'', None) => quote = Some(c),
+            ('[', None) | ('(', None) => depth += 1,
+            (']', None) | (')', None) => depth -= 1,
+            _ => ()
+        }
+
+        let combinator = c == '>' || c == '+' || c == '~';
+
+        if (c.is_whitespace() || combinator) && depth == 0 && quote.is_none() {
+            if !current.is_empty() {
+                parts.push(current);
+                current = String::new();
+            }
+            if combinator {
+                parts.push(c.to_string());
+            }
+        } else {
+            current.push(c);
+        }
+    }
+
+    if !current.is_empty() {
+        parts.push(current);
+    }
+
+    parts
+}
+
+fn parse_compound(selector: &str) -> Option<Compound> {
+
+    let chars: Vec<char> = selector.trim().chars().collect();
+    let mut i = 0;
+
+    let mut tagname = String::new();
+    while i < chars.len() && !is_delimiter(chars[i]) {
+        tagname.push(chars[i]);
+        i += 1;
+    }
+
+    // `ns|name` selects by namespace, `|name` only html tags and `*|name` tags of any namespace
+    let (namespace, tagname) = match tagname.split_once('|') {
+        Some(("*", name)) => (None, name.to_string()),
+        Some(("", name)) => (Some(String::new()), name.to_string()),
+        Some((ns, name)) if is_name(ns) => (Some(ns.to_lowercase()), name.to_string()),
+        Some(_) => return None,
+        None => (None, tagname)
+    };
+
+    let tagname = if tagname.is_empty() || tagname == "*" {
+        None
+    } else if is_name(&tagname) {
+        Some(tagname)
+    } else {
+        return None;
+    };
+
+    let mut id: Option<String> = None;
+    let mut classes: Vec<String> = vec![];
+    let mut attrs: Vec<AttrSelector> = vec![];
+    let mut pseudos: Vec<Pseudo> = vec![];
+
+    while i < chars.len() {
+        match chars[i] {
+            '[' => {
+                let end = i + chars[i..].iter().position(|c| *c == ']')?;
+                attrs.push(parse_attr(&chars[i + 1..end].iter().collect::<String>())?);
+                i = end + 1;
+            },
+            '.' | '#' => {
+                let prefix = chars[i];
+                let mut name = String::new();
+                i += 1;
+                while i < chars.len() && !is_delimiter(chars[i]) {
+                    name.push(chars[i]);
+                    i += 1;
+                }
+                if !is_name(&name) {
+                    return None;
+                }
+                if prefix == '.' {
+                    classes.push(name);
+                } else if id.is_none() {
+                    id = Some(name);
+                } else {
+                    return None;
+                }
+            },
+            ':' => {
+                let mut name = String::new();
+                i += 1;
+                while i < chars.len() && !is_delimiter(chars[i]) && chars[i] != '(' {
+                    name.push(chars[i]);
+                    i += 1;
+                }
+                let mut arg = String::new();
+                if i < chars.len() && chars[i] == '(' {
+                    let end = closing_paren(&chars, i)?;
+                    arg = chars[i + 1..end].iter().collect();
+                    i = end + 1;
+                }
+                pseudos.push(parse_pseudo(&name, &arg)?);
+            },
+            _ => return None
+        }
+    }
+
+    Some(Compound{namespace, tagname, id, classes, attrs, pseudos})
+}
+
+// Returns the position of the parenthesis closing the one at `start`, skipping quoted text
+fn closing_paren(chars: &[char], start: usize) -> Option<usize> {
+
+    let mut quote: Option<char> = None;
+
+    for (i, c) in chars.iter().enumerate().skip(start + 1) {
+        match (*c, quote) {
+            (_, Some(q)) if *c == q => quote = None,
+            (_, Some(_)) => (),
+            ('"', None) | ('\'', None) => quote = Some(*c),
+            (')', None) => return Some(i),
+            _ => ()
+        }
+    }
+
+    None
+}
+
+fn is_delimiter(c: char) -> bool {
+    c == '[' || c == '.' || c == '#' || c == ':'
+}
+
+fn is_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
+
+// An attribute-name, which may have a namespace prefix like `xlink:href`
+fn is_attr_name(name: &str) -> bool {
+    match name.split_once(':') {
+        Some((prefix, local)) => is_name(prefix) && is_name(local),
+        None => is_name(name)
+    }
+}
+
+fn parse_attr(inner: &str) -> Option<AttrSelector> {
+
+    let eq = match inner.find('=') {
+        Some(v) => v,
+        None => {
+            let name = inner.trim();
+            if !is_attr_name(name) {
+                return None;
+            }
+            return Some(AttrSelector{name: name.to_string(), op: AttrOp::Exists, value: String::new(), ignore_case: false});
+        }
+    };
+
+    let (op, name_end) = match inner[..eq].chars().last() {
+        Some('^') => (AttrOp::StartsWith, eq - 1),
+        Some('$') => (AttrOp::EndsWith, eq - 1),
+        Some('*') => (AttrOp::Contains, eq - 1),
+        Some('~') => (AttrOp::Includes, eq - 1),
+        Some('|') => (AttrOp::DashMatch, eq - 1),
+        _ => (AttrOp::Equals, eq)
+    };
+
+    let name = inner[..name_end].trim();
+    if !is_attr_name(name) {
+        return None;
+    }
+
+    let mut value = inner[eq + 1..].trim();
+
+    let ignore_case = value.len() > 2 && (value.ends_with(" i") || value.ends_with(" I"));
+    if ignore_case {
+        value = value[..value.len() - 2].trim_end();
+    }
+
+    if value.len() >= 2 && ((value.starts_with('"') && value.ends_with('"')) || (value.starts_with('\'') && value.ends_with('\''))) {
+        value = &value[1..value.len() - 1];
+    }
+
+    Some(AttrSelector{name: name.to_string(), op, value: value.to_string(), ignore_case})
+}
+
+fn parse_pseudo(name: &str, arg: &str) -> Option<Pseudo> {
+
+    match name {
+        "first-child" => return Some(Pseudo::NthChild(0, 1)),
+        "last-child" => return Some(Pseudo::NthLastChild(0, 1)),
+        "only-child" => return Some(Pseudo::OnlyChild),
+        "first-of-type" => return Some(Pseudo::NthOfType(0, 1)),
+        "last-of-type" => return Some(Pseudo::NthLastOfType(0, 1)),
+        "only-of-type" => return Some(Pseudo::OnlyOfType),
+        "contains" => {
+            let arg = arg.trim();
+            let text = match arg.chars().next() {
+                Some(q) if (q == '"' || q == '\'') && arg.len() > 1 && arg.ends_with(q) => &arg[1..arg.len() - 1],
+                _ => arg
+            };
+            return Some(Pseudo::Contains(text.to_string()));
+        },
+        _ => ()
+    }
+
+    let (a, b) = parse_nth(arg)?;
+
+    match name {
+        "nth-child" => Some(Pseudo::NthChild(a, b)),
+        "nth-last-child" => Some(Pseudo::NthLastChild(a, b)),
+        "nth-of-type" => Some(Pseudo::NthOfType(a, b)),
+        "nth-last-of-type" => Some(Pseudo::NthLastOfType(a, b)),
+        _ => None
+    }
+}
+
+// Parses the argument of :nth-child() and its relatives, e.g. `3`, `odd`, `even` or `2n+1`
+fn parse_nth(arg: &str) -> Option<(i64, i64)> {
+
+    let arg: String = arg.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_lowercase();
+
+    match arg.as_str() {
+        "odd" => return Some((2, 1)),
+        "even" => return Some((2, 0)),
+        _ => ()
+    }
+
+    let n = match arg.find('n') {
+        Some(v) => v,
+        None => return Some((0, arg.parse().ok()?))
+    };
+
+    let a = match &arg[..n] {
+        "" | "+" => 1,
+        "-" => -1,
+        v => v.parse().ok()?
+    };
+
+    let b = match &arg[n + 1..] {
+        "" => 0,
+        v => v.trim_start_matches('+').parse().ok()?
+    };
+
+    Some((a, b))
+}
+
+// Checks if a 1-based position can be written as a*n+b for some n >= 0
+fn nth_matches(a: i64, b: i64, position: i64) -> bool {
+    if a == 0 {
+        return position == b;
+    }
+    // a and b come from the selector, so extreme values must not overflow
+    match position.checked_sub(b) {
+        Some(d) => d.checked_rem(a) == Some(0) && d.checked_div(a).is_some_and(|n| n >= 0),
+        None => false
+    }
+}
+
+impl Selector {
+
+    /// Parses the given selector. It returns a [`Result`] that can be unwrapped to a [`Selector`] if the selector is valid.
+    ///
+    /// [`Selector`]: struct.Selector.html#
+    pub fn parse(selector: &str) -> Result<Selector, Error> {
+        match parse(selector) {
+            Some(s) => Ok(s),
+            None => Err(Error::new(ErrorKind::InvalidInput, format!("An error has occurred when trying to parse the selector! ({})", selector)))
+        }
+    }
+
+    // Builds a selector consisting of a single compound, attributes without a value only have to be present
+    pub (crate) fn from_parts(tagname: Option<String>, id: Option<String>, classes: Vec<String>, attrs: Vec<(String, Option<String>)>) -> Selector {
+
+        let mut attr_selectors: Vec<AttrSelector> = vec![];
+
+        for (name, value) in attrs {
+            attr_selectors.push(match value {
+                Some(v) => AttrSelector{name, op: AttrOp::Equals, value: v, ignore_case: false},
+                None => AttrSelector{name, op: AttrOp::Exists, value: String::new(), ignore_case: false}
+            });
+        }
+
+        let compound = Compound{namespace: None, tagname, id, classes, attrs: attr_selectors, pseudos: vec![]};
+
+        Selector{complexes: vec![Complex{compounds: vec![compound], combinators: vec![]}]}
+    }
+
+    pub (crate) fn matches(&self, tags: &Document, index: usize, options: crate::FilterOptions) -> bool {
+        self.complexes.iter().any(|c| c.matches_from(c.compounds.len() - 1, tags, index, options))
+    }
+}
+
+impl Complex {
+
+    // Matches the compound at position `part` against the tag and the rest of the selector against its ancestors
+    fn matches_from(&self, part: usize, tags: &Document, index: usize, options: crate::FilterOptions) -> bool {
+
+        if !self.compounds[part].matches(tags, index, options) {
+            return false;
+        }
+
+        if part == 0 {
+            return true;
+        }
+
+        match self.combinators[part - 1] {
+            Combinator::Descendant => {
+                let mut current = index;
+                while let Some(p) = tree::parent(tags, current) {
+                    if self.matches_from(part - 1, tags, p, options) {
+                        return true;
+                    }
+                    current = p;
+                }
+                false
+            },
+            Combinator::Child => {
+                match tree::parent(tags, index) {
+                    Some(p) => self.matches_from(part - 1, tags, p, options),
+                    None => false
+                }
+            },
+            Combinator::Adjacent | Combinator::Sibling => {
+                let siblings = tree::siblings(tags, index);
+                let position = match siblings.iter().position(|s| *s == index) {
+                    Some(v) => v,
+                    None => return false
+                };
+                if position == 0 {
+                    return false;
+                }
+                if self.combinators[part - 1] == Combinator::Adjacent {
+                    return self.matches_from(part - 1, tags, siblings[position - 1], options);
+                }
+                siblings[..position].iter().any(|s| self.matches_from(part - 1, tags, *s, options))
+            },
+        }
+    }
+}
+
+fn name_eq(a: &str, b: &str, case_insensitive: bool) -> bool {
+    a == b || (case_insensitive && a.eq_ignore_ascii_case(b))
+}
+
+impl Compound {
+
+    fn matches(&self, tags: &Document, index: usize, options: crate::FilterOptions) -> bool {
+
+        let tagname = tags.tagname(index);
+
+        if let Some(name) = &self.tagname {
+            if !name_eq(crate::parse::local_name(tagname), name, options.case_insensitive) && !name_eq(tagname, name, options.case_insensitive) {
+                return false;
+            }
+        }
+
+        if let Some(namespace) = &self.namespace {
+            if !tags.namespace(index).eq_ignore_ascii_case(namespace) {
+                return false;
+            }
+        }
+
+        if (self.id.is_some() || !self.classes.is_empty() || !self.attrs.is_empty()) && !self.matches_attrs(&tags.attributes(index), options) {
+            return false;
+        }
+
+        for p in &self.pseudos {
+            if !matches_pseudo(p, tags, index) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn matches_attrs(&self, tag_attrs: &[(String, String)], options: crate::FilterOptions) -> bool {
+
+        if let Some(id) = &self.id {
+            match tag_attrs.iter().find(|(n, _)| name_eq(n, "id", options.case_insensitive)) {
+                Some((_, v)) if name_eq(v, id, options.case_insensitive_values) => (),
+                _ => return false
+            }
+        }
+
+        if !self.classes.is_empty() {
+            let class = match tag_attrs.iter().find(|(n, _)| name_eq(n, "class", options.case_insensitive)) {
+                Some((_, v)) => v,
+                None => return false
+            };
+            for c in &self.classes {
+                if !class.split_whitespace().any(|t| name_eq(t, c, options.case_insensitive_values)) {
+                    return false;
+                }
+            }
+        }
+
+        for a in &self.attrs {
+            let value = match tag_attrs.iter().find(|(n, _)| name_eq(n, &a.name, options.case_insensitive)) {
+                Some((_, v)) => v,
+                None => return false
+            };
+            let (value, expected) = if a.ignore_case || options.case_insensitive_values {
+                (value.to_lowercase(), a.value.to_lowercase())
+            } else {
+                (value.to_string(), a.value.clone())
+            };
+            let found = match a.op {
+                AttrOp::Exists => true,
+                AttrOp::Equals => value == expected,
+                AttrOp::StartsWith => !expected.is_empty() && value.starts_with(&expected),
+                AttrOp::EndsWith => !expected.is_empty() && value.ends_with(&expected),
+                AttrOp::Contains => !expected.is_empty() && value.contains(&expected),
+                AttrOp::Includes => value.split_whitespace().any(|t| t == expected),
+                AttrOp::DashMatch => value == expected || value.strip_prefix(&expected).is_some_and(|rest| rest.starts_with('-')),
+            };
+            if !found {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+fn matches_pseudo(pseudo: &Pseudo, tags: &Document, index: usize) -> bool {
+
+    if let Pseudo::Contains(text) = pseudo {
+        return tags.text(index).contains(text.as_str());
+    }
+
+    let mut siblings = tree::siblings(tags, index);
+
+    let (a, b, last) = match pseudo {
+        Pseudo::NthChild(a, b) => (*a, *b, false),
+        Pseudo::NthLastChild(a, b) => (*a, *b, true),
+        Pseudo::NthOfType(a, b) | Pseudo::NthLastOfType(a, b) => {
+            siblings.retain(|s| tags.tagname(*s) == tags.tagname(index));
+            (*a, *b, matches!(pseudo, Pseudo::NthLastOfType(_, _)))
+        },
+        Pseudo::OnlyChild => return siblings.len() == 1,
+        Pseudo::OnlyOfType => return siblings.iter().filter(|s| tags.tagname(**s) == tags.tagname(index)).count() == 1,
+        Pseudo::Contains(_) => return true,
+    };
+
+    let position = match siblings.iter().position(|s| *s == index) {
+        Some(v) => v,
+        None => return false
+    };
+
+    let position = if last { siblings.len() - position } else { position + 1 };
+
+    nth_matches(a, b, position as i64)
+}
@@ -0,0 +1,109 @@
+use crate::parse::document::Document;
+
+// Reads a html table into rows of cell texts. Cells spanning several columns or rows by `colspan` or `rowspan`
+// are repeated in every column and row they span, so all rows line up.
+
+// Browsers ignore larger spans
+const MAX_COLSPAN: usize = 1000;
+const MAX_ROWSPAN: usize = 65534;
+
+pub (crate) fn read(document: &Document, index: usize) -> crate::Table {
+
+    let rows = rows(document, index);
+
+    // leading rows in `<thead>` or made of `<th>` cells only are header rows
+    let header_rows = rows.iter().take_while(|(in_thead, cells)| *in_thead || cells.iter().all(|c| document.tagname(*c).eq_ignore_ascii_case("th"))).count();
+
+    let mut grid = grid(document, &rows);
+    let body = grid.split_off(header_rows.min(grid.len()));
+
+    crate::Table{headers: grid.pop().unwrap_or_default(), rows: body}
+}
+
+// Returns the rows of a table without those of nested tables, each with whether it is part of `<thead>` and its cells
+fn rows(document: &Document, table: usize) -> Vec<(bool, Vec<usize>)> {
+
+    let mut out: Vec<(bool, Vec<usize>)> = vec![];
+
+    for child in document.children(table) {
+        let tagname = document.tagname(child);
+        if tagname.eq_ignore_ascii_case("tr") {
+            out.push((false, cells(document, child)));
+        } else if ["thead", "tbody", "tfoot"].iter().any(|n| tagname.eq_ignore_ascii_case(n)) {
+            for row in document.children(child).into_iter().filter(|r| document.tagname(*r).eq_ignore_ascii_case("tr")) {
+                out.push((tagname.eq_ignore_ascii_case("thead"), cells(document, row)));
+            }
+        }
+    }
+
+    // rows without any cells are left out, as they do not add anything to the grid
+    out.retain(|(_, cells)| !cells.is_empty());
+
+    out
+}
+
+fn cells(document: &Document, row: usize) -> Vec<usize> {
+    document.children(row).into_iter().filter(|c| ["td", "th"].iter().any(|n| document.tagname(*c).eq_ignore_ascii_case(n))).collect()
+}
+
+// Places the cells of the rows into a grid, repeating cells that span several columns or rows
+fn grid(document: &Document, rows: &[(bool, Vec<usize>)]) -> Vec<Vec<String>> {
+
+    let mut out: Vec<Vec<String>> = vec![];
+    // the text of cells spanning into the following rows and how many rows they still span, by column
+    let mut spanning: Vec<Option<(String, usize)>> = vec![];
+
+    for (n, (_, cells)) in rows.iter().enumerate() {
+
+        let mut row: Vec<String> = vec![];
+        let mut cells = cells.iter();
+
+        loop {
+            let column = row.len();
+
+            if let Some(Some((text, rest))) = spanning.get_mut(column) {
+                row.push(text.clone());
+                *rest -= 1;
+                if *rest == 0 {
+                    spanning[column] = None;
+                }
+                continue;
+            }
+
+            let cell = match cells.next() {
+                Some(v) => *v,
+                None => break
+            };
+
+            let text = document.text(cell).split_whitespace().collect::<Vec<&str>>().join(" ");
+            let colspan = span(document, cell, "colspan").clamp(1, MAX_COLSPAN);
+            // a rowspan of 0 spans all following rows
+            let rowspan = match span(document, cell, "rowspan") {
+                0 => rows.len() - n,
+                v => v.min(MAX_ROWSPAN)
+            };
+
+            for _ in 0..colspan {
+                if rowspan > 1 {
+                    if spanning.len() <= row.len() {
+                        spanning.resize(row.len() + 1, None);
+                    }
+                    spanning[row.len()] = Some((text.clone(), rowspan - 1));
+                }
+                row.push(text.clone());
+            }
+        }
+
+        out.push(row);
+    }
+
+    out
+}
+
+// Returns the value of the `colspan` or `rowspan` attribute of a cell, which is 1 if it is missing or invalid
+fn span(document: &Document, cell: usize, attr: &str) -> usize {
+    document.attributes(cell).into_iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(attr))
+        .and_then(|(_, value)| value.trim().parse().ok())
+        .unwrap_or(1)
+}
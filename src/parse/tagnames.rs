@@ -133,4 +133,104 @@ pub (in crate::parse) const TAGNAMES: [&str; 136] = ["a",
 	"var",
 	"video",
 	"wbr",
-	"xmp"];
\ No newline at end of file
+	"xmp"];
+
+pub (in crate::parse) const RAW_TEXT_TAGNAMES: [&str; 8] = ["iframe",
+	"noembed",
+	"noframes",
+	"script",
+	"style",
+	"textarea",
+	"title",
+	"xmp"];
+
+pub (in crate::parse) const VOID_TAGNAMES: [&str; 18] = ["area",
+	"base",
+	"basefont",
+	"bgsound",
+	"br",
+	"col",
+	"embed",
+	"frame",
+	"hr",
+	"img",
+	"input",
+	"keygen",
+	"link",
+	"meta",
+	"param",
+	"source",
+	"track",
+	"wbr"];
+
+pub (in crate::parse) const SVG_TAGNAMES: [&str; 40] = ["a",
+	"animate",
+	"animateMotion",
+	"animateTransform",
+	"circle",
+	"clipPath",
+	"defs",
+	"desc",
+	"ellipse",
+	"feBlend",
+	"feColorMatrix",
+	"feComposite",
+	"feGaussianBlur",
+	"feOffset",
+	"filter",
+	"foreignObject",
+	"g",
+	"image",
+	"line",
+	"linearGradient",
+	"marker",
+	"mask",
+	"metadata",
+	"path",
+	"pattern",
+	"polygon",
+	"polyline",
+	"radialGradient",
+	"rect",
+	"set",
+	"stop",
+	"svg",
+	"switch",
+	"symbol",
+	"text",
+	"textPath",
+	"title",
+	"tspan",
+	"use",
+	"view"];
+
+pub (in crate::parse) const MATHML_TAGNAMES: [&str; 30] = ["math",
+	"maction",
+	"annotation",
+	"menclose",
+	"merror",
+	"mfenced",
+	"mfrac",
+	"mi",
+	"mmultiscripts",
+	"mn",
+	"mo",
+	"mover",
+	"mpadded",
+	"mphantom",
+	"mroot",
+	"mrow",
+	"ms",
+	"mspace",
+	"msqrt",
+	"mstyle",
+	"msub",
+	"msubsup",
+	"msup",
+	"mtable",
+	"mtd",
+	"mtext",
+	"mtr",
+	"munder",
+	"munderover",
+	"semantics"];
@@ -1,124 +1,49 @@
-use crate::parse::{fetch, innerhtml};
-use slicestring::Slice;
+use crate::parse::fetch;
 
-pub (crate) fn get(tagname: &str, mut innerhtml: String) -> String {
-
-    if !innerhtml.contains(">") {
-        return innerhtml;
-    }
-    if !innerhtml.contains("<") {
-        return innerhtml;
-    }
-
-    let checkbreak = innerhtml::check_break(tagname, &innerhtml);
-
-    if checkbreak {
-        innerhtml=innerhtml.replace("<br>", innerhtml::BREAK_SUBST);
-    }
-
-    let firstpart = innerhtml.slice(0, innerhtml.find("<").unwrap());
-    let mut middle = innerhtml.slice(innerhtml.find("<").unwrap(), innerhtml.rfind(">").unwrap()+1);
-    let lastpart = innerhtml.slice(innerhtml.rfind(">").unwrap()+1, innerhtml.len());
-
-    let appearance = middle.matches("<").count();
-
-    let mut parts1: Vec<String> = vec![];
-
-    for _ in 0..appearance-1 {
-
-        let (tagname, _ ) = fetch::get_tagname_and_content(&middle);
-        if fetch::check_tagname(&tagname) {
-            break
-        } else {
-            middle=middle.slice(1, middle.len());
-            if !middle.contains("<") {
-                break
-            }
-            parts1.push(format!("<{}", middle.slice(0, middle.find("<").unwrap())));
-            middle=middle.slice(middle.find("<").unwrap(), middle.len());
-        }
-
-    }
-
-    let mut parts2: Vec<String> = vec![];
-
-    loop {
-
-        if !middle.contains("<") {
-            break
-        }
-
-        let part = middle.slice(middle.rfind("<").unwrap(), middle.len());
-
-        let (mut tagname, _) = fetch::get_tagname_and_content(&part);
+// Whether the text of a tag is returned as it is written: the content of tags like `<script>`,
+// except for `<textarea>` and `<title>`, which may contain character references
+pub (crate) fn is_verbatim(tagname: &str) -> bool {
+    fetch::check_raw_text(tagname) && !tagname.eq_ignore_ascii_case("textarea") && !tagname.eq_ignore_ascii_case("title")
+}
 
-        if tagname.contains("/") {
-            tagname=tagname.replace( "/", "");
-        }
-        if fetch::check_tagname(&tagname) {
-            break
+// Whether an inline style hides a tag by `display: none` or `visibility: hidden`
+pub (crate) fn hides(style: &str) -> bool {
+    style.split(';').filter_map(|d| d.split_once(':')).any(|(property, value)| {
+        let value = value.to_ascii_lowercase().replace("!important", "");
+        match property.trim().to_ascii_lowercase().as_str() {
+            "display" => value.trim() == "none",
+            "visibility" => value.trim() == "hidden" || value.trim() == "collapse",
+            _ => false
         }
-
-        parts2.push(part.clone());
-        middle=middle.slice(0, middle.rfind(&part).unwrap());
-    }
-
-    
-    //run filter for middle
-    let middle_new = get_middle(middle.to_string());
-
-    //continue with sorting parts
-    let mut parts2sorted: Vec<&str> = vec![];
-    let mut x: i32 = parts2.len()as i32 - 1;
-
-    loop {
-        if x < 0 {
-			break
-		}
-
-        parts2sorted.push(&parts2[x as usize]);
-
-        x=x-1;
-    }
-
-    let mut out = format!("{} {} {} {} {}", firstpart, parts1.concat(), middle_new, parts2sorted.concat(), lastpart);
-
-    if checkbreak {
-        out=out.replace(innerhtml::BREAK_SUBST, "");
-        let br: &[_] = &['<', 'b', 'r', '>'];
-        out=out.trim_matches(br).to_string();
-    }
-
-    out.trim().to_string()
-
+    })
 }
 
-fn get_middle(middle: String) -> String {
+// Puts pieces of text together as given by the options
+pub (crate) fn join(segments: Vec<String>, options: &crate::TextOptions) -> String {
 
-    if !middle.contains("<") {
-        return "".to_string();
-    }
-
-    let mut out = middle.clone();
-
-    let dm = fetch::fetch(out.to_string());
-
-
-    for t in dm.tag {
-        let mut closing_tag = String::new();
-        let mut closing_tag_in = false;
+    let collapse = |text: &str| match options.collapse_whitespace {
+        true => {
+            let mut out = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+            // whitespace at the beginning and end is kept as a single space, as it separates the text from the text around it
+            if text.starts_with(char::is_whitespace) && !out.is_empty() {
+                out.insert(0, ' ');
+            }
+            if text.ends_with(char::is_whitespace) {
+                out.push(' ');
+            }
+            out
+        },
+        false => text.to_string()
+    };
 
-        if middle.contains(&format!("</{}", t.tagname)) {
-            closing_tag=middle.slice(middle.find(&format!("</{}", t.tagname)).unwrap(), middle.len());
-            closing_tag=closing_tag.to_string().slice(0, closing_tag.find(">").unwrap()+1);
-            closing_tag_in=true;
-        }
+    let out = match &options.separator {
+        // pieces that are only whitespace are left out, so the separator is only put between actual text
+        Some(separator) => segments.iter().map(|s| s.trim()).filter(|s| !s.is_empty()).map(collapse).collect::<Vec<String>>().join(separator),
+        None => collapse(&segments.concat())
+    };
 
-        out=out.replace(&t.tagcontent, "");
-        if closing_tag_in {
-            out=out.replace(&closing_tag, "");
-        }
+    match options.trim {
+        true => out.trim().to_string(),
+        false => out
     }
-
-    out.trim().to_string()
-}
\ No newline at end of file
+}
@@ -0,0 +1,27 @@
+use crate::parse::document::Document;
+
+// Every tag of a document knows its parent and where its descendants end,
+// so the structure of the document can be walked without searching through all of its tags.
+
+pub (crate) fn roots(tags: &Document) -> Vec<usize> {
+    tags.roots()
+}
+
+pub (crate) fn parent(tags: &Document, index: usize) -> Option<usize> {
+    tags[index].parent
+}
+
+pub (crate) fn children(tags: &Document, index: usize) -> Vec<usize> {
+    tags.children(index)
+}
+
+pub (crate) fn descendants(tags: &Document, index: usize) -> std::ops::Range<usize> {
+    index + 1..tags[index].subtree_end
+}
+
+pub (crate) fn siblings(tags: &Document, index: usize) -> Vec<usize> {
+    match parent(tags, index) {
+        Some(p) => children(tags, p),
+        None => roots(tags),
+    }
+}
@@ -0,0 +1,1035 @@
+use std::io::{Error, ErrorKind};
+use crate::parse::{tree, attributes};
+use crate::parse::document::Document;
+
+// A small XPath 1.0 evaluator working on the flat tag list of a parsed Dom.
+// Text nodes are approximated by the text of the element they belong to.
+
+pub (crate) fn select(tags: &Document, expr: &str) -> Result<Vec<usize>, Error> {
+
+    let tokens = tokenize(expr)?;
+    let mut parser = Parser{tokens, pos: 0};
+    let ast = parser.expr()?;
+
+    if parser.pos < parser.tokens.len() {
+        return Err(invalid(&format!("Unexpected token at position {}", parser.pos)));
+    }
+
+    let ctx = Context{node: Node::Root, position: 1, size: 1};
+
+    let mut out: Vec<usize> = vec![];
+
+    match eval(tags, &ast, &ctx)? {
+        Value::Nodes(nodes) => {
+            for n in nodes {
+                let i = match n {
+                    Node::Root => continue,
+                    Node::Element(i) => i,
+                    Node::Attribute(i, _) => i,
+                    Node::Text(i) => i,
+                };
+                if !out.contains(&i) {
+                    out.push(i);
+                }
+            }
+        },
+        _ => return Err(invalid("The expression does not select any nodes"))
+    }
+
+    out.sort();
+    Ok(out)
+}
+
+fn invalid(msg: &str) -> Error {
+    Error::new(ErrorKind::InvalidInput, format!("An error has occurred when trying to evaluate the xpath-expression! ({})", msg))
+}
+
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Slash,
+    DoubleSlash,
+    LBracket,
+    RBracket,
+    LParen,
+    RParen,
+    At,
+    Comma,
+    Pipe,
+    Dot,
+    DotDot,
+    Star,
+    ColonColon,
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    Plus,
+    Minus,
+    Name(String),
+    Literal(String),
+    Number(f64),
+}
+
+fn tokenize(expr: &str) -> Result<Vec<Token>, Error> {
+
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens: Vec<Token> = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = if i + 1 < chars.len() { Some(chars[i + 1]) } else { None };
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let (token, len) = match (c, next) {
+            ('/', Some('/')) => (Token::DoubleSlash, 2),
+            ('/', _) => (Token::Slash, 1),
+            ('[', _) => (Token::LBracket, 1),
+            (']', _) => (Token::RBracket, 1),
+            ('(', _) => (Token::LParen, 1),
+            (')', _) => (Token::RParen, 1),
+            ('@', _) => (Token::At, 1),
+            (',', _) => (Token::Comma, 1),
+            ('|', _) => (Token::Pipe, 1),
+            ('*', _) => (Token::Star, 1),
+            (':', Some(':')) => (Token::ColonColon, 2),
+            ('=', _) => (Token::Eq, 1),
+            ('!', Some('=')) => (Token::NotEq, 2),
+            ('<', Some('=')) => (Token::LtEq, 2),
+            ('<', _) => (Token::Lt, 1),
+            ('>', Some('=')) => (Token::GtEq, 2),
+            ('>', _) => (Token::Gt, 1),
+            ('+', _) => (Token::Plus, 1),
+            ('-', _) => (Token::Minus, 1),
+            ('.', Some('.')) => (Token::DotDot, 2),
+            ('.', Some(d)) if !d.is_ascii_digit() => (Token::Dot, 1),
+            ('.', None) => (Token::Dot, 1),
+            ('"', _) | ('\'', _) => {
+                let mut end = i + 1;
+                while end < chars.len() && chars[end] != c {
+                    end += 1;
+                }
+                if end >= chars.len() {
+                    return Err(invalid("Unterminated string literal"));
+                }
+                (Token::Literal(chars[i + 1..end].iter().collect()), end - i + 1)
+            },
+            ('.', _) | ('0'..='9', _) => number(&chars[i..]),
+            (n, _) if n.is_alphabetic() || n == '_' => {
+                let mut end = i;
+                while end < chars.len() {
+                    let ch = chars[end];
+                    let namespace = ch == ':' && end + 1 < chars.len() && chars[end + 1] != ':';
+                    if ch.is_alphanumeric() || ch == '_' || ch == '-' || ch == '.' || namespace {
+                        end += 1;
+                    } else {
+                        break
+                    }
+                }
+                (Token::Name(chars[i..end].iter().collect()), end - i)
+            },
+            _ => return Err(invalid(&format!("Unexpected character '{}'", c)))
+        };
+
+        tokens.push(token);
+        i += len;
+    }
+
+    Ok(tokens)
+}
+
+fn number(chars: &[char]) -> (Token, usize) {
+    let mut end = 0;
+    while end < chars.len() && (chars[end].is_ascii_digit() || chars[end] == '.') {
+        end += 1;
+    }
+    let s: String = chars[..end].iter().collect();
+    (Token::Number(s.parse().unwrap_or(f64::NAN)), end)
+}
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Axis {
+    Child,
+    Descendant,
+    DescendantOrSelf,
+    Parent,
+    Ancestor,
+    AncestorOrSelf,
+    FollowingSibling,
+    PrecedingSibling,
+    Following,
+    Preceding,
+    Current,
+    Attribute,
+}
+
+#[derive(Clone, Debug)]
+enum NodeTest {
+    Name(String),
+    Any,
+    Node,
+    Text,
+}
+
+#[derive(Clone, Debug)]
+struct Step {
+    axis: Axis,
+    test: NodeTest,
+    predicates: Vec<Expr>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Op {
+    Or,
+    And,
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    Plus,
+    Minus,
+    Multiply,
+    Div,
+    Mod,
+}
+
+#[derive(Clone, Debug)]
+enum Expr {
+    Binary(Op, Box<Expr>, Box<Expr>),
+    Negate(Box<Expr>),
+    Union(Box<Expr>, Box<Expr>),
+    Literal(String),
+    Number(f64),
+    Function(String, Vec<Expr>),
+    Filter(Box<Expr>, Vec<Expr>),
+    Path(Option<Box<Expr>>, bool, Vec<Step>),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset)
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), Error> {
+        if self.eat(&token) {
+            return Ok(());
+        }
+        Err(invalid(&format!("Expected {:?} at position {}", token, self.pos)))
+    }
+
+    fn expr(&mut self) -> Result<Expr, Error> {
+        let mut left = self.and_expr()?;
+        while self.peek() == Some(&Token::Name("or".to_string())) {
+            self.pos += 1;
+            let right = self.and_expr()?;
+            left = Expr::Binary(Op::Or, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn and_expr(&mut self) -> Result<Expr, Error> {
+        let mut left = self.equality_expr()?;
+        while self.peek() == Some(&Token::Name("and".to_string())) {
+            self.pos += 1;
+            let right = self.equality_expr()?;
+            left = Expr::Binary(Op::And, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn equality_expr(&mut self) -> Result<Expr, Error> {
+        let mut left = self.relational_expr()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Eq) => Op::Eq,
+                Some(Token::NotEq) => Op::NotEq,
+                _ => break
+            };
+            self.pos += 1;
+            let right = self.relational_expr()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn relational_expr(&mut self) -> Result<Expr, Error> {
+        let mut left = self.additive_expr()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Lt) => Op::Lt,
+                Some(Token::LtEq) => Op::LtEq,
+                Some(Token::Gt) => Op::Gt,
+                Some(Token::GtEq) => Op::GtEq,
+                _ => break
+            };
+            self.pos += 1;
+            let right = self.additive_expr()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn additive_expr(&mut self) -> Result<Expr, Error> {
+        let mut left = self.multiplicative_expr()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Plus) => Op::Plus,
+                Some(Token::Minus) => Op::Minus,
+                _ => break
+            };
+            self.pos += 1;
+            let right = self.multiplicative_expr()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    // After a complete operand '*', 'div' and 'mod' can only be operators, never a node test or a name.
+    fn multiplicative_expr(&mut self) -> Result<Expr, Error> {
+        let mut left = self.unary_expr()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Star) => Op::Multiply,
+                Some(Token::Name(name)) if name == "div" => Op::Div,
+                Some(Token::Name(name)) if name == "mod" => Op::Mod,
+                _ => break
+            };
+            self.pos += 1;
+            let right = self.unary_expr()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary_expr(&mut self) -> Result<Expr, Error> {
+        if self.eat(&Token::Minus) {
+            return Ok(Expr::Negate(Box::new(self.unary_expr()?)));
+        }
+        let mut left = self.path_expr()?;
+        while self.eat(&Token::Pipe) {
+            let right = self.path_expr()?;
+            left = Expr::Union(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn path_expr(&mut self) -> Result<Expr, Error> {
+
+        let primary = match self.peek() {
+            Some(Token::Literal(s)) => {
+                let s = s.clone();
+                self.pos += 1;
+                Some(Expr::Literal(s))
+            },
+            Some(Token::Number(n)) => {
+                let n = *n;
+                self.pos += 1;
+                Some(Expr::Number(n))
+            },
+            Some(Token::LParen) => {
+                self.pos += 1;
+                let e = self.expr()?;
+                self.expect(Token::RParen)?;
+                Some(e)
+            },
+            Some(Token::Name(name)) if self.peek_at(1) == Some(&Token::LParen) && !is_node_type(name) => {
+                let name = name.clone();
+                self.pos += 2;
+                let mut args: Vec<Expr> = vec![];
+                if !self.eat(&Token::RParen) {
+                    loop {
+                        args.push(self.expr()?);
+                        if self.eat(&Token::RParen) {
+                            break
+                        }
+                        self.expect(Token::Comma)?;
+                    }
+                }
+                Some(Expr::Function(name, args))
+            },
+            _ => None
+        };
+
+        match primary {
+            Some(mut p) => {
+                let mut predicates: Vec<Expr> = vec![];
+                while self.eat(&Token::LBracket) {
+                    predicates.push(self.expr()?);
+                    self.expect(Token::RBracket)?;
+                }
+                if !predicates.is_empty() {
+                    p = Expr::Filter(Box::new(p), predicates);
+                }
+
+                let mut steps: Vec<Step> = vec![];
+                if !self.steps_after(&mut steps)? {
+                    return Ok(p);
+                }
+                Ok(Expr::Path(Some(Box::new(p)), false, steps))
+            },
+            None => {
+                let mut steps: Vec<Step> = vec![];
+                let mut absolute = false;
+
+                if self.eat(&Token::Slash) {
+                    absolute = true;
+                    if !self.starts_step() {
+                        return Ok(Expr::Path(None, true, steps));
+                    }
+                } else if self.eat(&Token::DoubleSlash) {
+                    absolute = true;
+                    steps.push(descendant_or_self());
+                }
+
+                steps.push(self.step()?);
+                self.steps_after(&mut steps)?;
+                Ok(Expr::Path(None, absolute, steps))
+            }
+        }
+    }
+
+    fn steps_after(&mut self, steps: &mut Vec<Step>) -> Result<bool, Error> {
+        let mut found = false;
+        loop {
+            if self.eat(&Token::Slash) {
+                steps.push(self.step()?);
+            } else if self.eat(&Token::DoubleSlash) {
+                steps.push(descendant_or_self());
+                steps.push(self.step()?);
+            } else {
+                break
+            }
+            found = true;
+        }
+        Ok(found)
+    }
+
+    fn starts_step(&self) -> bool {
+        matches!(self.peek(), Some(Token::Name(_)) | Some(Token::Star) | Some(Token::At) | Some(Token::Dot) | Some(Token::DotDot))
+    }
+
+    fn step(&mut self) -> Result<Step, Error> {
+
+        if self.eat(&Token::Dot) {
+            return Ok(Step{axis: Axis::Current, test: NodeTest::Node, predicates: vec![]});
+        }
+        if self.eat(&Token::DotDot) {
+            return Ok(Step{axis: Axis::Parent, test: NodeTest::Node, predicates: vec![]});
+        }
+
+        let mut axis = Axis::Child;
+
+        if self.eat(&Token::At) {
+            axis = Axis::Attribute;
+        } else if let (Some(Token::Name(name)), Some(Token::ColonColon)) = (self.peek(), self.peek_at(1)) {
+            axis = match name.as_str() {
+                "child" => Axis::Child,
+                "descendant" => Axis::Descendant,
+                "descendant-or-self" => Axis::DescendantOrSelf,
+                "parent" => Axis::Parent,
+                "ancestor" => Axis::Ancestor,
+                "ancestor-or-self" => Axis::AncestorOrSelf,
+                "following-sibling" => Axis::FollowingSibling,
+                "preceding-sibling" => Axis::PrecedingSibling,
+                "following" => Axis::Following,
+                "preceding" => Axis::Preceding,
+                "self" => Axis::Current,
+                "attribute" => Axis::Attribute,
+                _ => return Err(invalid(&format!("Unknown axis '{}'", name)))
+            };
+            self.pos += 2;
+        }
+
+        let test = match self.peek() {
+            Some(Token::Star) => {
+                self.pos += 1;
+                NodeTest::Any
+            },
+            Some(Token::Name(name)) => {
+                let name = name.clone();
+                self.pos += 1;
+                if is_node_type(&name) && self.eat(&Token::LParen) {
+                    self.expect(Token::RParen)?;
+                    if name == "text" { NodeTest::Text } else { NodeTest::Node }
+                } else {
+                    NodeTest::Name(name)
+                }
+            },
+            _ => return Err(invalid(&format!("Expected a node test at position {}", self.pos)))
+        };
+
+        let mut predicates: Vec<Expr> = vec![];
+        while self.eat(&Token::LBracket) {
+            predicates.push(self.expr()?);
+            self.expect(Token::RBracket)?;
+        }
+
+        Ok(Step{axis, test, predicates})
+    }
+}
+
+fn is_node_type(name: &str) -> bool {
+    name == "node" || name == "text"
+}
+
+fn descendant_or_self() -> Step {
+    Step{axis: Axis::DescendantOrSelf, test: NodeTest::Node, predicates: vec![]}
+}
+
+
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Root,
+    Element(usize),
+    Attribute(usize, usize),
+    Text(usize),
+}
+
+#[derive(Clone, Debug)]
+enum Value {
+    Nodes(Vec<Node>),
+    Str(String),
+    Num(f64),
+    Bool(bool),
+}
+
+struct Context {
+    node: Node,
+    position: usize,
+    size: usize,
+}
+
+fn order(node: &Node) -> (usize, usize, usize) {
+    match node {
+        Node::Root => (0, 0, 0),
+        Node::Element(i) => (i + 1, 0, 0),
+        Node::Attribute(i, a) => (i + 1, 1, *a),
+        Node::Text(i) => (i + 1, 2, 0),
+    }
+}
+
+fn sort_nodes(nodes: &mut Vec<Node>) {
+    nodes.sort_by_key(order);
+    nodes.dedup();
+}
+
+fn string_value(tags: &Document, node: &Node) -> String {
+    match node {
+        Node::Root => {
+            let mut s: Vec<String> = vec![];
+            for i in tree::roots(tags) {
+                s.push(tags.text(i));
+            }
+            s.concat()
+        },
+        Node::Element(i) | Node::Text(i) => tags.text(*i),
+        Node::Attribute(i, a) => tags.attr_value(attributes::parse(tags.tagcontent(*i))[*a].1.clone()),
+    }
+}
+
+fn to_string(tags: &Document, value: &Value) -> String {
+    match value {
+        Value::Nodes(n) => match n.first() {
+            Some(node) => string_value(tags, node),
+            None => String::new()
+        },
+        Value::Str(s) => s.clone(),
+        Value::Num(n) => {
+            if n.is_nan() {
+                "NaN".to_string()
+            } else if n.is_infinite() {
+                if *n > 0.0 { "Infinity".to_string() } else { "-Infinity".to_string() }
+            } else if n.fract() == 0.0 {
+                format!("{}", *n as i64)
+            } else {
+                format!("{}", n)
+            }
+        },
+        Value::Bool(b) => b.to_string(),
+    }
+}
+
+fn to_number(tags: &Document, value: &Value) -> f64 {
+    match value {
+        Value::Num(n) => *n,
+        Value::Bool(b) => if *b { 1.0 } else { 0.0 },
+        _ => to_string(tags, value).trim().parse().unwrap_or(f64::NAN)
+    }
+}
+
+fn to_bool(value: &Value) -> bool {
+    match value {
+        Value::Nodes(n) => !n.is_empty(),
+        Value::Str(s) => !s.is_empty(),
+        Value::Num(n) => *n != 0.0 && !n.is_nan(),
+        Value::Bool(b) => *b,
+    }
+}
+
+fn axis_nodes(tags: &Document, node: &Node, axis: Axis) -> Vec<Node> {
+
+    let mut out: Vec<Node> = vec![];
+
+    let index = match node {
+        Node::Root => {
+            match axis {
+                Axis::Child => {
+                    for i in tree::roots(tags) {
+                        out.push(Node::Element(i));
+                    }
+                },
+                Axis::Descendant | Axis::DescendantOrSelf => {
+                    if axis == Axis::DescendantOrSelf {
+                        out.push(Node::Root);
+                    }
+                    for i in 0..tags.len() {
+                        out.push(Node::Element(i));
+                        out.push(Node::Text(i));
+                    }
+                },
+                Axis::Current | Axis::AncestorOrSelf => out.push(Node::Root),
+                _ => ()
+            }
+            return out;
+        },
+        Node::Attribute(i, _) | Node::Text(i) => {
+            match axis {
+                Axis::Parent | Axis::Ancestor | Axis::AncestorOrSelf => {
+                    if axis == Axis::AncestorOrSelf {
+                        out.push(node.clone());
+                    }
+                    out.push(Node::Element(*i));
+                    if axis != Axis::Parent {
+                        out.extend(axis_nodes(tags, &Node::Element(*i), Axis::Ancestor));
+                    }
+                },
+                Axis::Current | Axis::DescendantOrSelf => out.push(node.clone()),
+                _ => ()
+            }
+            return out;
+        },
+        Node::Element(i) => *i
+    };
+
+    match axis {
+        Axis::Child => {
+            out.push(Node::Text(index));
+            for c in tree::children(tags, index) {
+                out.push(Node::Element(c));
+            }
+        },
+        Axis::Descendant | Axis::DescendantOrSelf => {
+            if axis == Axis::DescendantOrSelf {
+                out.push(Node::Element(index));
+            }
+            out.push(Node::Text(index));
+            for d in tree::descendants(tags, index) {
+                out.push(Node::Element(d));
+                out.push(Node::Text(d));
+            }
+        },
+        Axis::Parent => {
+            match tree::parent(tags, index) {
+                Some(p) => out.push(Node::Element(p)),
+                None => out.push(Node::Root)
+            }
+        },
+        Axis::Ancestor | Axis::AncestorOrSelf => {
+            if axis == Axis::AncestorOrSelf {
+                out.push(Node::Element(index));
+            }
+            let mut current = index;
+            while let Some(p) = tree::parent(tags, current) {
+                out.push(Node::Element(p));
+                current = p;
+            }
+            out.push(Node::Root);
+        },
+        Axis::FollowingSibling | Axis::PrecedingSibling => {
+            let siblings = tree::siblings(tags, index);
+            let pos = siblings.iter().position(|s| *s == index).unwrap_or(0);
+            if axis == Axis::FollowingSibling {
+                for s in &siblings[pos + 1..] {
+                    out.push(Node::Element(*s));
+                }
+            } else {
+                for s in siblings[..pos].iter().rev() {
+                    out.push(Node::Element(*s));
+                }
+            }
+        },
+        Axis::Following => {
+            let start = tree::descendants(tags, index).end;
+            for i in start..tags.len() {
+                out.push(Node::Element(i));
+            }
+        },
+        Axis::Preceding => {
+            let mut ancestors: Vec<usize> = vec![];
+            let mut current = index;
+            while let Some(p) = tree::parent(tags, current) {
+                ancestors.push(p);
+                current = p;
+            }
+            for i in (0..index).rev() {
+                if !ancestors.contains(&i) {
+                    out.push(Node::Element(i));
+                }
+            }
+        },
+        Axis::Current => out.push(Node::Element(index)),
+        Axis::Attribute => {
+            for a in 0..attributes::parse(tags.tagcontent(index)).len() {
+                out.push(Node::Attribute(index, a));
+            }
+        },
+    }
+
+    out
+}
+
+fn node_test(tags: &Document, node: &Node, axis: Axis, test: &NodeTest) -> bool {
+    match test {
+        NodeTest::Node => true,
+        NodeTest::Text => matches!(node, Node::Text(_)),
+        NodeTest::Any => match node {
+            Node::Element(_) => axis != Axis::Attribute,
+            Node::Attribute(_, _) => axis == Axis::Attribute,
+            _ => false
+        },
+        NodeTest::Name(name) => match node {
+            Node::Element(i) => axis != Axis::Attribute && tags.tagname(*i) == name,
+            Node::Attribute(i, a) => axis == Axis::Attribute && &attributes::parse(tags.tagcontent(*i))[*a].0 == name,
+            _ => false
+        },
+    }
+}
+
+fn eval_step(tags: &Document, input: &[Node], step: &Step) -> Result<Vec<Node>, Error> {
+
+    let mut out: Vec<Node> = vec![];
+
+    for node in input {
+        let mut selected: Vec<Node> = vec![];
+
+        for n in axis_nodes(tags, node, step.axis) {
+            if node_test(tags, &n, step.axis, &step.test) {
+                selected.push(n);
+            }
+        }
+
+        out.extend(filter(tags, selected, &step.predicates)?);
+    }
+
+    sort_nodes(&mut out);
+    Ok(out)
+}
+
+fn filter(tags: &Document, mut selected: Vec<Node>, predicates: &[Expr]) -> Result<Vec<Node>, Error> {
+
+    for predicate in predicates {
+        let size = selected.len();
+        let mut kept: Vec<Node> = vec![];
+
+        for (pos, n) in selected.into_iter().enumerate() {
+            let ctx = Context{node: n.clone(), position: pos + 1, size};
+            let keep = match eval(tags, predicate, &ctx)? {
+                Value::Num(v) => v == (pos + 1) as f64,
+                v => to_bool(&v)
+            };
+            if keep {
+                kept.push(n);
+            }
+        }
+
+        selected = kept;
+    }
+
+    Ok(selected)
+}
+
+fn eval(tags: &Document, expr: &Expr, ctx: &Context) -> Result<Value, Error> {
+
+    match expr {
+        Expr::Literal(s) => Ok(Value::Str(s.clone())),
+        Expr::Number(n) => Ok(Value::Num(*n)),
+        Expr::Negate(e) => Ok(Value::Num(-to_number(tags, &eval(tags, e, ctx)?))),
+        Expr::Union(a, b) => {
+            match (eval(tags, a, ctx)?, eval(tags, b, ctx)?) {
+                (Value::Nodes(mut x), Value::Nodes(y)) => {
+                    x.extend(y);
+                    sort_nodes(&mut x);
+                    Ok(Value::Nodes(x))
+                },
+                _ => Err(invalid("The operands of '|' must be node-sets"))
+            }
+        },
+        Expr::Filter(e, predicates) => {
+            match eval(tags, e, ctx)? {
+                Value::Nodes(mut n) => {
+                    sort_nodes(&mut n);
+                    Ok(Value::Nodes(filter(tags, n, predicates)?))
+                },
+                _ => Err(invalid("A predicate can only be applied to a node-set"))
+            }
+        },
+        Expr::Path(start, absolute, steps) => {
+            let mut nodes = match start {
+                Some(e) => match eval(tags, e, ctx)? {
+                    Value::Nodes(n) => n,
+                    _ => return Err(invalid("A path can only be applied to a node-set"))
+                },
+                None => {
+                    if *absolute {
+                        vec![Node::Root]
+                    } else {
+                        vec![ctx.node.clone()]
+                    }
+                }
+            };
+            for step in steps {
+                nodes = eval_step(tags, &nodes, step)?;
+            }
+            Ok(Value::Nodes(nodes))
+        },
+        Expr::Binary(op, a, b) => {
+            match op {
+                Op::Or => {
+                    if to_bool(&eval(tags, a, ctx)?) {
+                        return Ok(Value::Bool(true));
+                    }
+                    Ok(Value::Bool(to_bool(&eval(tags, b, ctx)?)))
+                },
+                Op::And => {
+                    if !to_bool(&eval(tags, a, ctx)?) {
+                        return Ok(Value::Bool(false));
+                    }
+                    Ok(Value::Bool(to_bool(&eval(tags, b, ctx)?)))
+                },
+                Op::Plus | Op::Minus | Op::Multiply | Op::Div | Op::Mod => {
+                    let x = to_number(tags, &eval(tags, a, ctx)?);
+                    let y = to_number(tags, &eval(tags, b, ctx)?);
+                    match op {
+                        Op::Plus => Ok(Value::Num(x + y)),
+                        Op::Minus => Ok(Value::Num(x - y)),
+                        Op::Multiply => Ok(Value::Num(x * y)),
+                        Op::Div => Ok(Value::Num(x / y)),
+                        _ => Ok(Value::Num(x % y)),
+                    }
+                },
+                _ => {
+                    let x = eval(tags, a, ctx)?;
+                    let y = eval(tags, b, ctx)?;
+                    Ok(Value::Bool(compare(tags, *op, &x, &y)))
+                }
+            }
+        },
+        Expr::Function(name, args) => function(tags, name, args, ctx),
+    }
+}
+
+fn compare(tags: &Document, op: Op, a: &Value, b: &Value) -> bool {
+
+    match (a, b) {
+        (Value::Nodes(x), _) => {
+            for n in x {
+                if compare(tags, op, &Value::Str(string_value(tags, n)), b) {
+                    return true;
+                }
+            }
+            false
+        },
+        (_, Value::Nodes(y)) => {
+            for n in y {
+                if compare(tags, op, a, &Value::Str(string_value(tags, n))) {
+                    return true;
+                }
+            }
+            false
+        },
+        _ => {
+            match op {
+                Op::Eq | Op::NotEq => {
+                    let equal = match (a, b) {
+                        (Value::Bool(_), _) | (_, Value::Bool(_)) => to_bool(a) == to_bool(b),
+                        (Value::Num(_), _) | (_, Value::Num(_)) => to_number(tags, a) == to_number(tags, b),
+                        _ => to_string(tags, a) == to_string(tags, b)
+                    };
+                    match op {
+                        Op::Eq => equal,
+                        _ => !equal,
+                    }
+                },
+                _ => {
+                    let x = to_number(tags, a);
+                    let y = to_number(tags, b);
+                    match op {
+                        Op::Lt => x < y,
+                        Op::LtEq => x <= y,
+                        Op::Gt => x > y,
+                        _ => x >= y,
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn function(tags: &Document, name: &str, args: &[Expr], ctx: &Context) -> Result<Value, Error> {
+
+    let mut values: Vec<Value> = vec![];
+    for a in args {
+        values.push(eval(tags, a, ctx)?);
+    }
+
+    let arg_count = |min: usize, max: usize| -> Result<(), Error> {
+        if values.len() < min || values.len() > max {
+            return Err(invalid(&format!("Wrong number of arguments for {}()", name)));
+        }
+        Ok(())
+    };
+
+    let string_arg = |i: usize| -> String {
+        match values.get(i) {
+            Some(v) => to_string(tags, v),
+            None => string_value(tags, &ctx.node)
+        }
+    };
+
+    match name {
+        "last" => {
+            arg_count(0, 0)?;
+            Ok(Value::Num(ctx.size as f64))
+        },
+        "position" => {
+            arg_count(0, 0)?;
+            Ok(Value::Num(ctx.position as f64))
+        },
+        "count" => {
+            arg_count(1, 1)?;
+            match &values[0] {
+                Value::Nodes(n) => Ok(Value::Num(n.len() as f64)),
+                _ => Err(invalid("count() expects a node-set"))
+            }
+        },
+        "name" | "local-name" => {
+            arg_count(0, 1)?;
+            let node = match values.first() {
+                Some(Value::Nodes(n)) => n.first().cloned(),
+                Some(_) => return Err(invalid("name() expects a node-set")),
+                None => Some(ctx.node.clone())
+            };
+            let mut out = match node {
+                Some(Node::Element(i)) => tags.tagname(i).to_string(),
+                Some(Node::Attribute(i, a)) => attributes::parse(tags.tagcontent(i))[a].0.clone(),
+                _ => String::new()
+            };
+            if name == "local-name" {
+                if let Some(v) = out.rfind(':') {
+                    out = out[v + 1..].to_string();
+                }
+            }
+            Ok(Value::Str(out))
+        },
+        "string" => {
+            arg_count(0, 1)?;
+            Ok(Value::Str(string_arg(0)))
+        },
+        "concat" => {
+            let mut s = String::new();
+            for v in &values {
+                s.push_str(&to_string(tags, v));
+            }
+            Ok(Value::Str(s))
+        },
+        "contains" => {
+            arg_count(2, 2)?;
+            Ok(Value::Bool(string_arg(0).contains(&string_arg(1))))
+        },
+        "starts-with" => {
+            arg_count(2, 2)?;
+            Ok(Value::Bool(string_arg(0).starts_with(&string_arg(1))))
+        },
+        "ends-with" => {
+            arg_count(2, 2)?;
+            Ok(Value::Bool(string_arg(0).ends_with(&string_arg(1))))
+        },
+        "substring-before" => {
+            arg_count(2, 2)?;
+            let s = string_arg(0);
+            match s.find(&string_arg(1)) {
+                Some(v) => Ok(Value::Str(s[..v].to_string())),
+                None => Ok(Value::Str(String::new()))
+            }
+        },
+        "substring-after" => {
+            arg_count(2, 2)?;
+            let s = string_arg(0);
+            let pattern = string_arg(1);
+            match s.find(&pattern) {
+                Some(v) => Ok(Value::Str(s[v + pattern.len()..].to_string())),
+                None => Ok(Value::Str(String::new()))
+            }
+        },
+        "string-length" => {
+            arg_count(0, 1)?;
+            Ok(Value::Num(string_arg(0).chars().count() as f64))
+        },
+        "normalize-space" => {
+            arg_count(0, 1)?;
+            Ok(Value::Str(string_arg(0).split_whitespace().collect::<Vec<&str>>().join(" ")))
+        },
+        "not" => {
+            arg_count(1, 1)?;
+            Ok(Value::Bool(!to_bool(&values[0])))
+        },
+        "boolean" => {
+            arg_count(1, 1)?;
+            Ok(Value::Bool(to_bool(&values[0])))
+        },
+        "number" => {
+            arg_count(0, 1)?;
+            match values.first() {
+                Some(v) => Ok(Value::Num(to_number(tags, v))),
+                None => Ok(Value::Num(to_number(tags, &Value::Str(string_value(tags, &ctx.node)))))
+            }
+        },
+        "true" => {
+            arg_count(0, 0)?;
+            Ok(Value::Bool(true))
+        },
+        "false" => {
+            arg_count(0, 0)?;
+            Ok(Value::Bool(false))
+        },
+        _ => Err(invalid(&format!("Unknown function {}()", name)))
+    }
+}
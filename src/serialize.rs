@@ -0,0 +1,132 @@
+use crate::parse;
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::sync::Arc;
+
+// Serde support for Dom and Tag, enabled by the `serde` feature.
+// A Tag is written as its tag-name, attributes, text and contents, which are its child tags, text and comments.
+// When it is read again, its html is put together from the tag-name, attributes and contents and parsed.
+// A parsed Dom is written as the outermost tags of the document, a filtered Dom as all of its tags.
+
+impl Serialize for crate::Tag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Tag", 4)?;
+        s.serialize_field("name", &self.get_tagname())?;
+        s.serialize_field("attributes", &self.get_attributes())?;
+        s.serialize_field("text", &self.get_text())?;
+        s.serialize_field("children", &self.nodes())?;
+        s.end()
+    }
+}
+
+impl Serialize for crate::Dom {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+
+        let tags: Vec<crate::Tag> = match self.is_parsed {
+            true => {
+                let (document, _) = self.selection();
+                document.roots().into_iter().map(|index| crate::Tag{document: document.clone(), index}).collect()
+            },
+            false => self.tag.clone()
+        };
+
+        let mut s = serializer.serialize_struct("Dom", 2)?;
+        s.serialize_field("parsed", &self.is_parsed)?;
+        s.serialize_field("tag", &tags)?;
+        s.end()
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct TagData {
+    name: String,
+    #[serde(default)]
+    attributes: Vec<(String, String)>,
+    #[serde(default)]
+    children: Vec<NodeData>,
+}
+
+#[derive(serde::Deserialize)]
+enum NodeData {
+    Element(TagData),
+    Text(String),
+    Comment(String),
+}
+
+#[derive(serde::Deserialize)]
+struct DomData {
+    #[serde(default)]
+    parsed: bool,
+    tag: Vec<TagData>,
+}
+
+impl TagData {
+
+    // Puts the html of the tag together again
+    fn write_html(&self, out: &mut String) {
+
+        out.push('<');
+        out.push_str(&self.name);
+
+        for (name, value) in &self.attributes {
+            out.push_str(&format!(" {}=\"{}\"", name, crate::entities::encode(value)));
+        }
+
+        if parse::fetch::check_void(&self.name) {
+            out.push('>');
+            return;
+        }
+
+        out.push('>');
+
+        for c in &self.children {
+            match c {
+                NodeData::Element(tag) => tag.write_html(out),
+                NodeData::Text(text) => out.push_str(text),
+                NodeData::Comment(comment) => out.push_str(&format!("<!--{}-->", comment)),
+            }
+        }
+
+        out.push_str(&format!("</{}>", self.name));
+    }
+}
+
+impl<'de> Deserialize<'de> for crate::Tag {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<crate::Tag, D::Error> {
+
+        let data = TagData::deserialize(deserializer)?;
+
+        let mut html = String::new();
+        data.write_html(&mut html);
+
+        let document = parse::fetch::parse(html);
+
+        if document.len() == 0 {
+            return Err(D::Error::custom(format!("An error has occurred when trying to read the tag! (Unknown tag-name: {})", data.name)));
+        }
+
+        Ok(crate::Tag{document: Arc::new(document), index: 0})
+    }
+}
+
+impl<'de> Deserialize<'de> for crate::Dom {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<crate::Dom, D::Error> {
+
+        let data = DomData::deserialize(deserializer)?;
+
+        let mut html = String::new();
+        for tag in &data.tag {
+            tag.write_html(&mut html);
+        }
+
+        let document = parse::fetch::parse(html);
+
+        if data.parsed {
+            return Ok(parse::fetch::from_document(document));
+        }
+
+        let roots = document.roots();
+
+        Ok(crate::Dom::select(Arc::new(document), roots))
+    }
+}
@@ -0,0 +1,162 @@
+/// A [`Candidate`] is an image of a `srcset` attribute as returned by [`parse`]
+///
+/// [`Candidate`]: struct.Candidate.html#
+/// [`parse`]: fn.parse.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct Candidate {
+    /// The url of the image as it is written, so relative urls are not resolved
+    pub url: String,
+    /// The width or pixel density the image is meant for
+    pub descriptor: Descriptor,
+}
+
+/// The descriptors of a [`Candidate`] telling which width or pixel density the image is meant for
+///
+/// [`Candidate`]: struct.Candidate.html#
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Descriptor {
+    /// The width of the image in pixels, e.g. `800w`
+    Width(u32),
+    /// The pixel density of the image, e.g. `2x`. Candidates without a descriptor have a density of `1x`.
+    Density(f64),
+}
+
+/// Parses the value of a `srcset` attribute like `small.jpg 480w, large.jpg 1080w` into its [`Candidate`]s in the order they are written,
+/// the way browsers do. Candidates with invalid descriptors are left out.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper::srcset;
+///
+/// for candidate in srcset::parse("logo.png, logo@2x.png 2x") {
+///     println!("{} {:?}", candidate.url, candidate.descriptor);
+/// }
+/// //Output: logo.png Density(1.0)
+/// //        logo@2x.png Density(2.0)
+/// ```
+/// [`Candidate`]: struct.Candidate.html#
+pub fn parse(srcset: &str) -> Vec<Candidate> {
+
+    let mut candidates: Vec<Candidate> = vec![];
+    let mut rest = srcset;
+
+    loop {
+        // candidates are separated by commas and whitespace
+        rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == ',');
+        if rest.is_empty() {
+            break;
+        }
+
+        let end = rest.find(|c: char| c.is_ascii_whitespace()).unwrap_or(rest.len());
+        let mut url = &rest[..end];
+        rest = &rest[end..];
+
+        // a url ending with commas has no descriptors, commas inside of it like in `data:` urls belong to it
+        let descriptors = match url.ends_with(',') {
+            true => {
+                url = url.trim_end_matches(',');
+                String::new()
+            },
+            false => {
+                let end = descriptors_end(rest);
+                let descriptors = rest[..end].to_string();
+                rest = &rest[end..];
+                descriptors
+            }
+        };
+
+        if let Some(descriptor) = descriptor(&descriptors) {
+            candidates.push(Candidate{url: url.to_string(), descriptor});
+        }
+    }
+
+    candidates
+}
+
+/// Returns the [`Candidate`] that fits an image shown with the given width in pixels best: the smallest image with a width
+/// descriptor that is at least as wide or else the widest one. If there are only candidates with density descriptors,
+/// the one a browser picks for a screen with a pixel density of `1x` is returned, which is the smallest density of at least `1x`
+/// or else the largest one. It returns [`None`] if there are no candidates.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper::srcset;
+///
+/// let candidates = srcset::parse("small.jpg 480w, medium.jpg 800w, large.jpg 1080w");
+///
+/// println!("{}", srcset::best(&candidates, 600).unwrap().url);
+/// //Output: medium.jpg
+/// ```
+/// [`Candidate`]: struct.Candidate.html#
+pub fn best(candidates: &[Candidate], width: u32) -> Option<&Candidate> {
+
+    let widths: Vec<(u32, &Candidate)> = candidates.iter().filter_map(|c| match c.descriptor {
+        Descriptor::Width(w) => Some((w, c)),
+        Descriptor::Density(_) => None
+    }).collect();
+
+    if !widths.is_empty() {
+        return widths.iter().filter(|(w, _)| *w >= width).min_by_key(|(w, _)| *w)
+            .or_else(|| widths.iter().max_by_key(|(w, _)| *w))
+            .map(|(_, c)| *c);
+    }
+
+    let densities: Vec<(f64, &Candidate)> = candidates.iter().filter_map(|c| match c.descriptor {
+        Descriptor::Density(d) => Some((d, c)),
+        Descriptor::Width(_) => None
+    }).collect();
+
+    densities.iter().filter(|(d, _)| *d >= 1.0).min_by(|a, b| a.0.total_cmp(&b.0))
+        .or_else(|| densities.iter().max_by(|a, b| a.0.total_cmp(&b.0)))
+        .map(|(_, c)| *c)
+}
+
+// Returns the end of the descriptors following a url, which is the next comma outside of parentheses
+fn descriptors_end(rest: &str) -> usize {
+
+    let mut parentheses = false;
+
+    for (i, c) in rest.char_indices() {
+        match c {
+            '(' => parentheses = true,
+            ')' => parentheses = false,
+            ',' if !parentheses => return i,
+            _ => ()
+        }
+    }
+
+    rest.len()
+}
+
+// Reads the descriptors of a candidate, of which there may be a width or a density and a height, which is ignored
+fn descriptor(descriptors: &str) -> Option<Descriptor> {
+
+    let mut width: Option<u32> = None;
+    let mut density: Option<f64> = None;
+    let mut height = false;
+
+    for d in descriptors.split_ascii_whitespace() {
+        let (value, unit) = d.split_at(d.char_indices().last().map_or(0, |(i, _)| i));
+        match unit {
+            "w" if width.is_none() && density.is_none() => width = Some(value.parse().ok().filter(|w| *w > 0)?),
+            "x" if width.is_none() && density.is_none() => density = Some(value.parse().ok().filter(|d: &f64| d.is_finite() && *d >= 0.0 && !value.starts_with('+'))?),
+            "h" if !height => {
+                value.parse::<u32>().ok().filter(|h| *h > 0)?;
+                height = true;
+            },
+            _ => return None
+        }
+    }
+
+    // a height is only allowed together with a width
+    if height && width.is_none() {
+        return None;
+    }
+
+    match width {
+        Some(w) => Some(Descriptor::Width(w)),
+        None => Some(Descriptor::Density(density.unwrap_or(1.0)))
+    }
+}
@@ -0,0 +1,170 @@
+use crate::parse::{attributes, fetch, lexer};
+use crate::parse::lexer::{Markup, Next};
+use std::ops::Range;
+
+/// A [`Token`] is a piece of a html-document found by the [`Tokenizer`]. Its `span` is the byte range it takes up in the html.
+///
+/// [`Token`]: enum.Token.html#
+/// [`Tokenizer`]: struct.Tokenizer.html#
+#[derive(Clone, Debug, PartialEq)]
+pub enum Token<'a> {
+    /// An opening tag like `<a href="/">` with its tag-name and its attribute-names and attribute-values
+    StartTag{name: &'a str, attrs: Vec<(String, String)>, self_closing: bool, span: Range<usize>},
+    /// A closing tag like `</a>` with its tag-name
+    EndTag{name: &'a str, span: Range<usize>},
+    /// Text between tags as it is written in the html, including anything between `<` and `>` that is not a tag
+    Text{text: &'a str, span: Range<usize>},
+    /// The text of a comment without `<!--` and `-->`
+    Comment{text: &'a str, span: Range<usize>},
+    /// A doctype declaration without `<!DOCTYPE` and `>`
+    Doctype{text: &'a str, span: Range<usize>},
+}
+
+/// A [`Tokenizer`] splits a html-[`str`] into [`Token`]s in document order. Unlike [`parse_html`], it does not
+/// build a tree, so closing tags are returned as they are written in the html, and tags with unknown tag-names are returned as well.
+/// The content of tags like `<script>` and `<style>` is returned as text up to their closing tag.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper::tokenizer::{self, Token};
+///
+/// let html = "<p class='note'>Hello <b>World!</b></p>";
+///
+/// for token in tokenizer::tokenize(html) {
+///     match token {
+///         Token::StartTag{name, attrs, ..} => println!("start {} {:?}", name, attrs),
+///         Token::EndTag{name, ..} => println!("end {}", name),
+///         Token::Text{text, ..} => println!("text {:?}", text),
+///         _ => ()
+///     }
+/// }
+/// //Output: start p [("class", "note")]
+/// //        text "Hello "
+/// //        start b []
+/// //        text "World!"
+/// //        end b
+/// //        end p
+/// ```
+/// [`Tokenizer`]: struct.Tokenizer.html#
+/// [`Token`]: enum.Token.html#
+/// [`parse_html`]: ../fn.parse_html.html
+pub struct Tokenizer<'a> {
+    html: &'a str,
+    position: usize,
+    // the markup following the text that was returned last
+    next: Option<Token<'a>>,
+    // the name of the tag whose raw text follows, like `script`
+    raw: Option<&'a str>,
+}
+
+/// Returns a [`Tokenizer`] over the given html-[`str`]
+///
+/// [`Tokenizer`]: struct.Tokenizer.html#
+pub fn tokenize(html: &str) -> Tokenizer<'_> {
+    Tokenizer{html, position: 0, next: None, raw: None}
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+
+        if let Some(token) = self.next.take() {
+            return Some(token);
+        }
+
+        let html = self.html;
+        let text = self.position;
+
+        // the content of a tag like `<script>` is text up to its closing tag
+        if let Some(name) = self.raw.take() {
+            self.position = lexer::raw_text_end(html, self.position, name).unwrap_or(html.len());
+        }
+
+        loop {
+
+            let markup = match lexer::next(html, self.position, true) {
+                Next::Markup(v) => v,
+                _ => {
+                    self.position = html.len();
+                    return text_token(html, text..html.len());
+                }
+            };
+
+            let token = match markup {
+                Markup::Comment(span) => {
+                    let comment = &html[span.clone()];
+                    let comment = comment.strip_prefix("<!--").unwrap_or(comment);
+                    Token::Comment{text: comment.strip_suffix("-->").unwrap_or(comment), span}
+                },
+                Markup::Tag(span) => match tag_token(html, span.clone()) {
+                    Some(v) => v,
+                    None => {
+                        // not a tag, so it is part of the text
+                        self.position = span.end;
+                        continue;
+                    }
+                },
+            };
+
+            let span = match &token {
+                Token::StartTag{span, ..} | Token::EndTag{span, ..} | Token::Text{span, ..} | Token::Comment{span, ..} | Token::Doctype{span, ..} => span.clone(),
+            };
+
+            self.position = span.end;
+
+            if let Token::StartTag{name, self_closing: false, ..} = token {
+                if fetch::check_raw_text(name) {
+                    self.raw = Some(name);
+                }
+            }
+
+            return match text_token(html, text..span.start) {
+                Some(t) => {
+                    self.next = Some(token);
+                    Some(t)
+                },
+                None => Some(token)
+            };
+        }
+    }
+}
+
+fn text_token(html: &str, span: Range<usize>) -> Option<Token<'_>> {
+
+    if span.is_empty() {
+        return None;
+    }
+
+    Some(Token::Text{text: &html[span.clone()], span})
+}
+
+// Returns the token of the markup between `<` and `>`, or None if it is not a tag
+fn tag_token(html: &str, span: Range<usize>) -> Option<Token<'_>> {
+
+    let tagcontent = &html[span.clone()];
+    let tagname = fetch::get_tagname(tagcontent);
+
+    if tagname.eq_ignore_ascii_case("!doctype") {
+        let text = tagcontent[9..].trim_end_matches('>').trim();
+        return Some(Token::Doctype{text, span});
+    }
+
+    let closing = tagname.starts_with('/');
+    let name = tagname.trim_start_matches('/');
+
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    // the tag-name as a slice of the html
+    let offset = span.start + if closing { 2 } else { 1 };
+    let name = &html[offset..offset + name.len()];
+
+    if closing {
+        return Some(Token::EndTag{name, span});
+    }
+
+    Some(Token::StartTag{name, attrs: attributes::parse(tagcontent), self_closing: tagcontent.ends_with("/>"), span})
+}
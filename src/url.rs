@@ -0,0 +1,30 @@
+use reqwest::Url;
+use std::io::{Error, ErrorKind};
+
+/// Resolves a url that may be relative, like `../img/logo.png`, `/about` or `//cdn.example.com/app.js`,
+/// against an absolute base url the way browsers do. Urls that are already absolute are returned as they are.
+/// It returns a [`Result`] that can be unwrapped to the absolute url if the base url is absolute.
+///
+/// # Example
+///
+/// ```
+/// use sitescraper::url;
+///
+/// println!("{}", url::resolve("https://example.com/shop/items/", "../img/logo.png").unwrap());
+/// //Output: https://example.com/shop/img/logo.png
+/// ```
+pub fn resolve(base: &str, url: &str) -> Result<String, Error> {
+
+    join(&parse(base)?, url)
+}
+
+pub (crate) fn parse(base: &str) -> Result<Url, Error> {
+    Url::parse(base).map_err(|e| Error::new(ErrorKind::InvalidInput, format!("An error has occurred when trying to parse the base url! ({})", e)))
+}
+
+pub (crate) fn join(base: &Url, url: &str) -> Result<String, Error> {
+    match base.join(url.trim()) {
+        Ok(v) => Ok(v.to_string()),
+        Err(e) => Err(Error::new(ErrorKind::InvalidInput, format!("An error has occurred when trying to resolve the url! ({})", e)))
+    }
+}